@@ -0,0 +1,36 @@
+// api.rs
+// json from the server crate, with the content built into the app standing in whenever it doesn't answer
+use gloo_net::http::Request;
+use serde::de::DeserializeOwned;
+use yew::prelude::*;
+
+pub async fn fetch_json<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let response = Request::get(path).send().await.map_err(|e| format!("Network error: {}", e))?;
+    if !response.ok() {
+        return Err(format!("HTTP {} error", response.status()));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+// starts out with the built-in copy so the page renders at once, then swaps in the server's
+// (static hosting without the server just 404s, dev servers answer index.html, neither parses)
+#[hook]
+pub fn use_content<T>(path: &'static str, builtin: fn() -> T) -> T
+where
+    T: Clone + PartialEq + DeserializeOwned + 'static,
+{
+    let content = use_state(builtin);
+    {
+        let content = content.clone();
+        use_effect_with(path, move |&path| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_json::<T>(path).await {
+                    Ok(loaded) => content.set(loaded),
+                    Err(err) => tracing::debug!("{} unavailable, using built-in content: {}", path, err),
+                }
+            });
+            || ()
+        });
+    }
+    (*content).clone()
+}
@@ -9,10 +9,16 @@ use crate::pages::about::About;
 use crate::pages::projects::Projects;
 use crate::pages::doom_projects::DoomProjects;
 use crate::pages::contact::Contact;
+use crate::pages::play::Play;
+use crate::pages::admin::Admin;
+use crate::pages::cv::Cv;
 use crate::components::header::Header;
 use crate::components::hud::Hud;
 use crate::components::navbar_toggle::NavbarToggle;
 use crate::components::fade_wrapper::FadeWrapper;
+use crate::components::responsive_image::ImagesContext;
+use crate::images::{ImageManifest, MANIFEST_PATH};
+use crate::api::fetch_json;
 
 // context for navbar style
 #[derive(Clone, PartialEq)]
@@ -44,11 +50,26 @@ fn switch(routes: Route) -> Html {
                 <DoomProjects /> 
             </FadeWrapper>
         },
+        Route::Play { map } => html! {
+            <FadeWrapper>
+                <Play map={map} />
+            </FadeWrapper>
+        },
         Route::Contact => html! { 
             <FadeWrapper>
                 <Contact /> 
             </FadeWrapper>
          },
+        Route::Admin => html! {
+            <FadeWrapper>
+                <Admin />
+            </FadeWrapper>
+        },
+        Route::Cv => html! {
+            <FadeWrapper>
+                <Cv />
+            </FadeWrapper>
+        },
         Route::NotFound => html! { 
             <FadeWrapper>
                 <div>{"404 - Page not found"}</div> 
@@ -81,6 +102,22 @@ pub fn app_content() -> Html {
         toggle: toggle_navbar.clone(),
     };
 
+    // loaded once for the whole site
+    let images = use_state(ImagesContext::default);
+    {
+        let images = images.clone();
+        use_effect_with((), move |_| {
+            // image derivatives are optional, without the manifest every image loads its original
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_json::<ImageManifest>(MANIFEST_PATH).await {
+                    Ok(manifest) => images.set(ImagesContext::new(manifest)),
+                    Err(err) => tracing::warn!("Image manifest unavailable, using originals: {}", err),
+                }
+            });
+            || ()
+        });
+    }
+
     // special styles for specific pages
     let mut main_classes = String::new();
 
@@ -125,6 +162,7 @@ pub fn app_content() -> Html {
 
     html! {
         <ContextProvider<NavbarContext> context={navbar_context}>
+        <ContextProvider<ImagesContext> context={(*images).clone()}>
             <Header show={*is_mobile || *is_default_navbar} />  // header always shown if mobile
             <Hud show={!*is_default_navbar}   />                
 
@@ -141,6 +179,7 @@ pub fn app_content() -> Html {
                     <Switch<Route> render={switch} />
                 </div>
             </main>
+        </ContextProvider<ImagesContext>>
         </ContextProvider<NavbarContext>>
         }
 }
@@ -0,0 +1,54 @@
+// bin/cv.rs
+// renders the cv pdf from the built-in about page content, trunk runs it before every build (Trunk.toml)
+//
+//   cargo run --bin cv
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use app::cv::{self, Cv};
+
+const USAGE: &str = "\
+usage:
+  cv [--out <file.pdf>]
+
+lays out the experience, education and technologies the about page shows, plus the profile from
+src/content/about.rs, as an a4 pdf at static/derived/KR_CV.pdf (or --out). the output only changes
+when the content does";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let out = match args.as_slice() {
+        [] => PathBuf::from(cv::PDF_PATH.trim_start_matches('/')),
+        [flag, path] if flag == "--out" => PathBuf::from(path),
+        _ => {
+            eprintln!("error: unexpected arguments\n\n{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match write(&out) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn write(out: &Path) -> Result<(), String> {
+    let pdf = cv::render(&Cv::built_in());
+    // untouched when nothing changed, so trunk serve's watcher doesn't rebuild in a loop
+    if std::fs::read(out).is_ok_and(|existing| existing == pdf) {
+        return Ok(());
+    }
+    if let Some(parent) = out.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(out, &pdf).map_err(|e| format!("failed to write {}: {}", out.display(), e))?;
+    println!("wrote {} ({} bytes)", out.display(), pdf.len());
+    Ok(())
+}
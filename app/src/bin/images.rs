@@ -0,0 +1,246 @@
+// bin/images.rs
+// resized webp/avif copies of every image the pages reference, plus the manifest ResponsiveImage reads
+//
+//   cargo run --release --bin images -- src/pages src/content
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use app::images::{self, Format, ImageInfo, ImageManifest, Variant};
+use image::codecs::png::{CompressionType, FilterType as PngFilter, PngEncoder};
+use image::imageops::FilterType;
+use image::DynamicImage;
+
+const USAGE: &str = "\
+usage:
+  images <source dir or file>...
+
+collects every \"/static/....png|jpg\" string literal in the given rust sources, then writes
+static/derived/<path>-<width>.<ext> for each width in 480/960/1600 below the original's (and the
+original width) as webp and in the original format (the original itself stands in at full size),
+plus static/derived/images.json with every variant's dimensions and a tiny placeholder. avif
+variants are added when avifenc (libavif) is on the path. images that aren't checked out are skipped
+with a warning, up to date outputs are kept";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args.iter().map(PathBuf::from).collect::<Vec<_>>()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(sources: &[PathBuf]) -> Result<(), String> {
+    let avif = Command::new("avifenc").arg("--version").output().is_ok();
+    if !avif {
+        eprintln!("warning: avifenc not found, skipping avif variants");
+    }
+
+    let mut manifest = ImageManifest::default();
+    for src in referenced_images(sources)? {
+        let path = Path::new(src.trim_start_matches('/'));
+        if !path.exists() {
+            eprintln!("warning: {} not found, skipping", path.display());
+            continue;
+        }
+        // formats the image crate wasn't built to decode keep loading their original
+        match derive(&src, path, avif) {
+            Ok(info) => {
+                manifest.0.insert(src, info);
+            }
+            Err(err) => eprintln!("warning: {}, skipping", err),
+        }
+    }
+
+    let output = Path::new(images::MANIFEST_PATH.trim_start_matches('/'));
+    let json = serde_json::to_string(&manifest).map_err(|e| e.to_string())?;
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(output, json).map_err(|e| format!("failed to write {}: {}", output.display(), e))?;
+    println!("wrote {} ({} images)", output.display(), manifest.0.len());
+    Ok(())
+}
+
+// site paths of the png/jpg string literals in the sources, derived images excluded
+fn referenced_images(sources: &[PathBuf]) -> Result<BTreeSet<String>, String> {
+    let mut files = Vec::new();
+    for source in sources {
+        collect_rust_files(source, &mut files)?;
+    }
+
+    let mut found = BTreeSet::new();
+    for file in files {
+        let text = std::fs::read_to_string(&file).map_err(|e| format!("failed to read {}: {}", file.display(), e))?;
+        for (start, _) in text.match_indices("\"/static/") {
+            let literal = &text[start + 1..];
+            let Some(end) = literal.find('"') else { continue };
+            let literal = &literal[..end];
+            let raster = matches!(Format::from_path(literal), Some(Format::Png | Format::Jpeg));
+            if raster && !literal.starts_with(images::DERIVED_DIR) {
+                found.insert(literal.to_string());
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn collect_rust_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let entries = std::fs::read_dir(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?.path();
+        if entry.is_dir() || entry.extension().is_some_and(|extension| extension == "rs") {
+            collect_rust_files(&entry, files)?;
+        }
+    }
+    Ok(())
+}
+
+fn derive(src: &str, path: &Path, avif: bool) -> Result<ImageInfo, String> {
+    let format = Format::from_path(src).ok_or_else(|| format!("{}: unsupported format", src))?;
+    let read_error = |e: image::ImageError| format!("failed to read {}: {}", path.display(), e);
+    let (width, height) = image::image_dimensions(path).map_err(read_error)?;
+
+    let mut formats = vec![format, Format::Webp];
+    if avif {
+        formats.push(Format::Avif);
+    }
+
+    let original_size = file_size(path);
+
+    // only decoded and resized when some output is out of date
+    let mut original: Option<DynamicImage> = None;
+    let mut variants = Vec::new();
+    for variant_width in images::widths_for(width) {
+        let variant_height = ((height as u64 * variant_width as u64) / width as u64).max(1) as u32;
+        let mut resized: Option<DynamicImage> = None;
+        let mut fallback_size = u64::MAX;   // formats come fallback first, the others have to beat it
+
+        for &variant_format in &formats {
+            // full size in the original format is the original itself
+            if variant_width == width && variant_format == format {
+                fallback_size = file_size(path);
+                variants.push(Variant { src: src.to_string(), width, height, format });
+                continue;
+            }
+            let variant_src = images::derived_path(src, variant_width, variant_format);
+            let output = PathBuf::from(variant_src.trim_start_matches('/'));
+            if !up_to_date(path, &output) {
+                let resized = match resized {
+                    Some(ref resized) => resized,
+                    None => {
+                        let source = match original {
+                            Some(ref original) => original,
+                            None => original.insert(image::open(path).map_err(read_error)?),
+                        };
+                        resized.insert(if variant_width == width {
+                            source.clone()
+                        } else {
+                            source.resize_exact(variant_width, variant_height, FilterType::Lanczos3)
+                        })
+                    }
+                };
+                encode(resized, variant_format, &output)?;
+            }
+
+            // resampled pixel art and lossless webp of photos can come out bigger than what they'd replace,
+            // then they aren't worth offering
+            let size = file_size(&output);
+            if size >= original_size || (variant_format != format && size >= fallback_size) {
+                let _ = std::fs::remove_file(&output);
+                println!("dropped {}, not smaller than the {}", output.display(), format.extension());
+                continue;
+            }
+            if variant_format == format {
+                fallback_size = size;
+            }
+            variants.push(Variant { src: variant_src, width: variant_width, height: variant_height, format: variant_format });
+        }
+    }
+
+    // cheap enough to redo every run, and it needs the decoded image anyway
+    let source = match original {
+        Some(original) => original,
+        None => image::open(path).map_err(read_error)?,
+    };
+    let placeholder = placeholder(&source)?;
+
+    Ok(ImageInfo { width, height, format, variants, placeholder: Some(placeholder) })
+}
+
+// data:image/png;base64,... of a PLACEHOLDER_WIDTH wide copy
+fn placeholder(image: &DynamicImage) -> Result<String, String> {
+    let height = (image.height() as u64 * images::PLACEHOLDER_WIDTH as u64 / image.width().max(1) as u64).max(1) as u32;
+    let small = image.resize_exact(images::PLACEHOLDER_WIDTH, height, FilterType::Triangle);
+    let mut png = Vec::new();
+    let encoder = PngEncoder::new_with_quality(&mut png, CompressionType::Best, PngFilter::Adaptive);
+    small.write_with_encoder(encoder).map_err(|e| e.to_string())?;
+    Ok(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| group | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |metadata| metadata.len())
+}
+
+fn up_to_date(source: &Path, output: &Path) -> bool {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    matches!((modified(source), modified(output)), (Some(source), Some(output)) if output >= source)
+}
+
+fn encode(image: &DynamicImage, format: Format, output: &Path) -> Result<(), String> {
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+    let written = match format {
+        // the image crate only encodes lossless webp, which still beats png for screenshots
+        Format::Webp => image.to_rgba8().save(output).map_err(|e| e.to_string()),
+        Format::Png => std::fs::File::create(output).map_err(|e| e.to_string()).and_then(|file| {
+            let encoder = PngEncoder::new_with_quality(file, CompressionType::Best, PngFilter::Adaptive);
+            image.write_with_encoder(encoder).map_err(|e| e.to_string())
+        }),
+        Format::Jpeg => image.to_rgb8().save(output).map_err(|e| e.to_string()),
+        Format::Avif => {
+            // avifenc reads png, so go through a temporary one next to the output
+            let staging = output.with_extension("avif.png");
+            image.save(&staging).map_err(|e| e.to_string())?;
+            let status = Command::new("avifenc").args(["--speed", "6", "-q", "60"]).arg(&staging).arg(output).output();
+            let _ = std::fs::remove_file(&staging);
+            match status {
+                Ok(result) if result.status.success() => Ok(()),
+                Ok(result) => Err(String::from_utf8_lossy(&result.stderr).trim().to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+    };
+    written.map_err(|e| format!("failed to write {}: {}", output.display(), e))?;
+    println!("wrote {}", output.display());
+    Ok(())
+}
@@ -0,0 +1,329 @@
+// bin/mock_contact.rs
+// stand-in for the contact services, so the form can be submitted end to end without the internet
+//
+//   cargo run --bin mock_contact -- --write-config webhook
+//   trunk serve
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use app::contact::attachments::{self, ATTACHMENT_FIELD};
+use app::contact::backend::BackendConfig;
+use app::contact::multipart::{self, Part};
+use app::contact::spam::{self, SpamRules, SPAM_CODE};
+use app::contact::validate::validate;
+use app::contact::{ContactConfig, Submission, LOCAL_CONFIG_PATH};
+use serde_json::{json, Value};
+
+const USAGE: &str = "\
+usage:
+  mock_contact [--port <port>] [--out <messages.jsonl>] [--reject <status>] [--write-config <service>]
+               [--min-seconds <seconds>] [--max-links <links>] [--rate-limit <messages>] [--rate-window <seconds>]
+
+answers POST /apps_script, /webhook and /formspree like the real services would and appends every
+message it accepts to the output file (default mock_contact/messages.jsonl), one json object per line.
+files sent to /webhook or /formspree as multipart/form-data are saved next to it under attachments/.
+--reject answers every message with that status and the service's error body instead.
+like the real server it drops as spam what was sent less than --min-seconds (3) after opening the form,
+has more than --max-links (3) links or the honeypot filled in, and more than --rate-limit (3) messages
+per address every --rate-window (600) seconds.
+--write-config points the site at one of the services by writing static/contact/backend.local.json
+(no captcha, mailto fallback to the mock's own address); delete it to go back to the deployed config";
+
+const DEFAULT_PORT: u16 = 8787;
+// room for a full set of attachments plus the multipart framing
+const MAX_BODY: usize = 25 << 20;
+
+struct Options {
+    port: u16,
+    out: PathBuf,
+    reject: Option<u16>,
+    write_config: Option<String>,
+    spam: SpamRules,
+}
+
+// what every connection's thread shares
+struct Mock {
+    out: Mutex<PathBuf>,
+    reject: Option<u16>,
+    spam: SpamRules,
+    sent: Mutex<HashMap<IpAddr, Vec<u64>>>,     // when each address last got messages through, for the rate limit
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_options(&args).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        port: DEFAULT_PORT,
+        out: PathBuf::from("mock_contact/messages.jsonl"),
+        reject: None,
+        write_config: None,
+        spam: SpamRules::default(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("{} needs a value\n\n{}", arg, USAGE));
+        let number = |value: String| value.parse::<u64>().map_err(|e| format!("bad {}: {}", arg, e));
+        match arg.as_str() {
+            "--port" => options.port = value()?.parse().map_err(|e| format!("bad port: {}", e))?,
+            "--out" => options.out = PathBuf::from(value()?),
+            "--reject" => options.reject = Some(value()?.parse().map_err(|e| format!("bad status: {}", e))?),
+            "--write-config" => options.write_config = Some(value()?),
+            "--min-seconds" => options.spam.min_seconds = number(value()?)?,
+            "--max-links" => options.spam.max_links = number(value()?)? as usize,
+            "--rate-limit" => options.spam.max_messages = number(value()?)? as usize,
+            "--rate-window" => options.spam.window_seconds = number(value()?)?,
+            _ => return Err(format!("unknown argument {}\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+fn run(options: Options) -> Result<(), String> {
+    if let Some(service) = &options.write_config {
+        write_config(service, options.port, options.spam)?;
+    }
+    if let Some(parent) = options.out.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", options.port)).map_err(|e| format!("failed to listen on {}: {}", options.port, e))?;
+    println!("listening on http://localhost:{}, writing to {}", options.port, options.out.display());
+
+    let mock = Arc::new(Mock { out: Mutex::new(options.out), reject: options.reject, spam: options.spam, sent: Mutex::default() });
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let mock = mock.clone();
+        std::thread::spawn(move || {
+            if let Err(err) = handle(stream, &mock) {
+                eprintln!("warning: {}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn write_config(service: &str, port: u16, spam: SpamRules) -> Result<(), String> {
+    let url = format!("http://localhost:{}/{}", port, service);
+    let backend = match service {
+        "apps_script" => BackendConfig::AppsScript { url },
+        "webhook" => BackendConfig::Webhook { url, headers: Default::default() },
+        "formspree" => BackendConfig::Formspree { url },
+        "mailto" => BackendConfig::Mailto { address: "mock@localhost".to_string() },
+        _ => return Err(format!("unknown service {} (apps_script, webhook, formspree or mailto)", service)),
+    };
+    let config = ContactConfig { backend, captcha: None, fallback_email: Some("mock@localhost".to_string()), spam };
+
+    let path = Path::new(LOCAL_CONFIG_PATH.trim_start_matches('/'));
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+struct Request {
+    method: String,
+    path: String,
+    content_type: String,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(format!("bad request line {:?}", line.trim()));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let (mut length, mut content_type) = (0, String::new());
+    loop {
+        line.clear();
+        reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| format!("bad content-length {:?}", value.trim()))?;
+            } else if name.eq_ignore_ascii_case("content-type") {
+                content_type = value.trim().to_string();
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return Err(format!("{} byte body is over the limit", length));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+    Ok(Request { method, path, content_type, body })
+}
+
+fn handle(mut stream: TcpStream, mock: &Mock) -> Result<(), String> {
+    let address = stream.peer_addr().map_err(|e| e.to_string())?.ip();
+    let request = read_request(&stream)?;
+    let service = request.path.trim_start_matches('/').split(['?', '#']).next().unwrap_or_default().to_string();
+
+    let (status, body) = match (request.method.as_str(), service.as_str()) {
+        // the site is served from another port, so every service has to answer cors preflights
+        ("OPTIONS", _) => (204, String::new()),
+        ("POST", "apps_script" | "webhook" | "formspree") => answer(&service, &request, address, mock),
+        _ => (404, json!({ "error": "not found" }).to_string()),
+    };
+    println!("{} {} -> {}", request.method, request.path, status);
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: *\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).map_err(|e| e.to_string())
+}
+
+// the text fields as json and the files, from either a json body or a multipart one
+fn read_fields(service: &str, request: &Request) -> Result<(Value, Vec<Part>), String> {
+    let Some(boundary) = multipart::boundary(&request.content_type) else {
+        return Ok((serde_json::from_slice(&request.body).unwrap_or(Value::Null), Vec::new()));
+    };
+    // apps script only ever gets json from the form
+    if service == "apps_script" {
+        return Err("apps script doesn't take multipart bodies".to_string());
+    }
+    let (files, text): (Vec<Part>, Vec<Part>) =
+        multipart::parse(&request.body, &boundary)?.into_iter().partition(|part| part.name == ATTACHMENT_FIELD);
+    let fields = text.iter().map(|part| (part.name.clone(), Value::String(part.text()))).collect();
+    Ok((Value::Object(fields), files))
+}
+
+// what each service says to a message, in its own shape
+fn answer(service: &str, request: &Request, address: IpAddr, mock: &Mock) -> (u16, String) {
+    let (fields, files, unreadable) = match read_fields(service, request) {
+        Ok((fields, files)) => (fields, files, None),
+        Err(err) => (Value::Null, Vec::new(), Some(err)),
+    };
+    // formspree names the subject _subject
+    let text = |key: &str| fields[key].as_str().unwrap_or_default().to_string();
+    let submission = Submission {
+        name: text("name"),
+        email: text("email"),
+        subject: if service == "formspree" { text("_subject") } else { text("subject") },
+        message: text("message"),
+        recaptcha_response: text("g-recaptcha-response"),
+        proof_of_work: None,
+        honeypot: text("website"),
+        // a number in json, text in a multipart body
+        form_seconds: fields["form_seconds"].as_u64().or_else(|| fields["form_seconds"].as_str()?.parse().ok()),
+        form_token: None,
+    };
+
+    let sizes: Vec<(String, u64)> = files.iter().map(|file| (file.filename.clone().unwrap_or_default(), file.data.len() as u64)).collect();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let problem = if let Some(status) = mock.reject {
+        Some((status, "rejected by --reject".to_string(), "MOCK"))
+    } else if let Some(err) = unreadable {
+        Some((400, err, "MOCK"))
+    } else if let Err(err) = validate(&submission) {
+        Some((422, err.to_string(), "MOCK"))
+    } else if let Err(err) = attachments::check_all(&sizes) {
+        Some((422, err.to_string(), "MOCK"))
+    } else if let Err(reason) = mock.spam.check(&submission).and_then(|()| mock.spam.check_time(submission.form_seconds)).and_then(|()| {
+        let mut sent = mock.sent.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        mock.spam.check_rate(sent.entry(address).or_default(), now)
+    }) {
+        println!("dropped a message from {} as spam: {}", address, reason);
+        Some((403, spam::REJECTION.to_string(), SPAM_CODE))
+    } else {
+        None
+    };
+
+    if let Some((status, message, code)) = problem {
+        return match service {
+            // apps script answers everything with a 200 and says what happened in the body
+            "apps_script" => (200, json!({ "result": "error", "error": message, "code": code }).to_string()),
+            "formspree" => (status, json!({ "errors": [{ "code": code.to_uppercase(), "message": message }] }).to_string()),
+            _ => (status, json!({ "error": message, "code": code }).to_string()),
+        };
+    }
+
+    if let Err(err) = store(&mock.out, service, &submission, &files) {
+        return (500, json!({ "error": err }).to_string());
+    }
+    println!("{} <{}>: {} ({} attachments)", submission.name, submission.email, submission.subject, files.len());
+    match service {
+        "apps_script" => (200, json!({ "result": "success" }).to_string()),
+        _ => (200, json!({ "ok": true }).to_string()),
+    }
+}
+
+// keeps letters, digits, dots, dashes and underscores, enough to stop a file name from leaving the folder
+fn sanitize(name: &str) -> String {
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || "._-".contains(c) { c } else { '_' }).collect();
+    name.trim_start_matches('.').to_string()
+}
+
+fn store(out: &Mutex<PathBuf>, service: &str, submission: &Submission, files: &[Part]) -> Result<(), String> {
+    let received = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    let path = out.lock().map_err(|e| e.to_string())?;
+
+    let folder = path.parent().unwrap_or(Path::new("")).join("attachments");
+    let mut saved = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        let name = file.filename.clone().unwrap_or_default();
+        let target = folder.join(format!("{}-{}-{}", received, index, sanitize(&name)));
+        std::fs::create_dir_all(&folder).map_err(|e| format!("failed to create {}: {}", folder.display(), e))?;
+        std::fs::write(&target, &file.data).map_err(|e| format!("failed to write {}: {}", target.display(), e))?;
+        saved.push(json!({ "name": name, "size": file.data.len(), "type": file.content_type, "saved": target.display().to_string() }));
+    }
+
+    let line = json!({ "received": received, "service": service, "submission": submission, "attachments": saved }).to_string();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&*path)
+        .map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
@@ -0,0 +1,37 @@
+// bin/pow_worker.rs
+// web worker that solves the contact form's proof of work off the main thread, so the page keeps animating.
+// trunk builds it next to the app (see index.html), the contact page starts it with a challenge as json
+// and gets WorkerMessage json back: progress every so often, then the nonce
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use app::contact::captcha::{self, Challenge, WorkerMessage};
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+    use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
+
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+    let post = {
+        let scope = scope.clone();
+        move |message: WorkerMessage| {
+            if let Ok(json) = serde_json::to_string(&message) {
+                let _ = scope.post_message(&json.into());
+            }
+        }
+    };
+
+    let on_message = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+        let Some(challenge) = event.data().as_string().and_then(|json| serde_json::from_str::<Challenge>(&json).ok()) else {
+            return;
+        };
+        let proof = captcha::solve(&challenge, |tried| post(WorkerMessage::Progress { tried }));
+        post(WorkerMessage::Solved { nonce: proof.nonce });
+    });
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("pow_worker only runs in the browser, trunk builds it alongside the site");
+    std::process::exit(1);
+}
@@ -0,0 +1,351 @@
+// bin/rasterize.rs
+// renders obj models on the cpu, for the ModelViewer turntable fallback and doom project thumbnails
+//
+//   cargo run --release --bin rasterize -- turntable static/cathedral/cathedral.obj static/cathedral/cathedral_turntable.png --strip
+//   cargo run --release --bin rasterize -- hero static/cathedral/cathedral.obj static/doom_projects/cathedral_of_charybdis/thumbnail.webp --size 640x360
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use app::renderer::camera::CameraPose;
+use app::renderer::metadata::{self, TurntableManifest};
+use app::renderer::mtl::{self, Material};
+use app::renderer::obj::{self, Model};
+use app::renderer::raster::{self, Image, Shot, Surface, Texture};
+use app::renderer::staging;
+
+const USAGE: &str = "\
+usage:
+  rasterize hero <model.obj> <output.png|webp> [options]
+  rasterize turntable <model.obj> <output> [--frames N] [--strip] [options]
+
+turntable writes <output>/frame_000.png ... or, with --strip, a single horizontal sprite strip
+plus <model>.turntable.json next to the obj for the ModelViewer fallback
+
+options:
+  --size WxH           output size in pixels (default 650x650)
+  --yaw DEG            camera yaw around the model (default 0)
+  --pitch DEG          camera height above the horizon (default 0)
+  --distance D         camera distance (default 10, model is scaled to 10 units)
+  --rotation DEG       model turn for the hero shot (default 0)
+  --frames N           turntable frame count (default 36)
+  --background RRGGBB  solid background, transparent when omitted (RRGGBBAA also works)
+  --supersample N      anti-aliasing factor (default 2)";
+
+struct Options {
+    mode: Mode,
+    model: PathBuf,
+    output: PathBuf,
+    width: u32,
+    height: u32,
+    camera: CameraPose,
+    rotation: f32,
+    frames: u32,
+    strip: bool,
+    background: [f32; 4],
+    supersample: u32,
+}
+
+#[derive(PartialEq)]
+enum Mode {
+    Hero,
+    Turntable,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(&args).and_then(|options| run(&options)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mode = match args[0].as_str() {
+        "hero" => Mode::Hero,
+        "turntable" => Mode::Turntable,
+        other => return Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+    };
+    let (Some(model), Some(output)) = (args.get(1), args.get(2)) else {
+        return Err(format!("missing model or output path\n\n{}", USAGE));
+    };
+
+    let mut options = Options {
+        mode,
+        model: PathBuf::from(model),
+        output: PathBuf::from(output),
+        width: 650,
+        height: 650,
+        camera: CameraPose::default(),
+        rotation: 0.0,
+        frames: 36,
+        strip: false,
+        background: [0.0; 4],
+        supersample: 2,
+    };
+
+    let mut rest = args[3..].iter();
+    while let Some(flag) = rest.next() {
+        if flag == "--strip" {
+            options.strip = true;
+            continue;
+        }
+        let value = rest.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        let number = || value.parse::<f32>().map_err(|_| format!("invalid number for {}: {}", flag, value));
+        let count = || value.parse::<u32>().map_err(|_| format!("invalid count for {}: {}", flag, value));
+
+        match flag.as_str() {
+            "--size" => {
+                let (width, height) = value.split_once('x').ok_or("size must look like 650x650")?;
+                options.width = width.parse().map_err(|_| "invalid width")?;
+                options.height = height.parse().map_err(|_| "invalid height")?;
+            }
+            "--yaw" => options.camera.yaw = number()?.to_radians(),
+            "--pitch" => options.camera.pitch = number()?.to_radians(),
+            "--distance" => options.camera.distance = number()?,
+            "--rotation" => options.rotation = number()?.to_radians(),
+            "--frames" => options.frames = count()?,
+            "--background" => options.background = parse_color(value)?,
+            "--supersample" => options.supersample = count()?,
+            other => return Err(format!("unknown option '{}'\n\n{}", other, USAGE)),
+        }
+    }
+
+    if options.width == 0 || options.height == 0 || options.frames == 0 {
+        return Err("size and frame count must be positive".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_color(hex: &str) -> Result<[f32; 4], String> {
+    let hex = hex.trim_start_matches('#');
+    let value = u32::from_str_radix(hex, 16).map_err(|_| format!("invalid colour: {}", hex))?;
+    let rgba = match hex.len() {
+        6 => (value << 8) | 0xff,
+        8 => value,
+        _ => return Err(format!("colour must be RRGGBB or RRGGBBAA: {}", hex)),
+    };
+    Ok(std::array::from_fn(|i| ((rgba >> (24 - i * 8)) & 0xff) as f32 / 255.0))
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let source = std::fs::read_to_string(&options.model)
+        .map_err(|e| format!("failed to read {}: {}", options.model.display(), e))?;
+    let model = obj::parse(&source);
+    let bounds = model.bounds().ok_or("model contains no geometry")?;
+    println!("loaded {} meshes from {}", model.meshes.len(), options.model.display());
+
+    let directory = options.model.parent().unwrap_or(Path::new("."));
+    let materials = load_materials(&model, &options.model);
+    let (textures, paints) = load_textures(&model, &materials, directory);
+    let surfaces: Vec<Surface> = model
+        .meshes
+        .iter()
+        .zip(&paints)
+        .map(|(mesh, (texture, color))| Surface {
+            mesh,
+            texture: texture.as_ref().and_then(|path| textures.get(path)),
+            color: *color,
+        })
+        .collect();
+
+    let transform = staging::fit_model(&bounds);
+    let shot = |rotation: f32| Shot {
+        width: options.width,
+        height: options.height,
+        camera: options.camera,
+        rotation,
+        background: options.background,
+        supersample: options.supersample,
+    };
+
+    match options.mode {
+        Mode::Hero => {
+            let image = raster::render(&surfaces, transform, &shot(options.rotation));
+            save(&image, &options.output)
+        }
+        Mode::Turntable => {
+            let frames = render_frames(options.frames, |frame| {
+                let rotation = options.rotation + TAU * frame as f32 / options.frames as f32;
+                raster::render(&surfaces, transform, &shot(rotation))
+            });
+            if options.strip {
+                save(&strip(&frames), &options.output)?;
+                write_manifest(options)
+            } else {
+                std::fs::create_dir_all(&options.output)
+                    .map_err(|e| format!("failed to create {}: {}", options.output.display(), e))?;
+                for (index, frame) in frames.iter().enumerate() {
+                    save(frame, &options.output.join(format!("frame_{:03}.png", index)))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// mtllib files referenced by the obj, or <model>.mtl when there are none
+fn load_materials(model: &Model, obj_path: &Path) -> HashMap<String, Material> {
+    let directory = obj_path.parent().unwrap_or(Path::new("."));
+    let mut libraries: Vec<PathBuf> = model.material_libraries.iter().map(|name| directory.join(name)).collect();
+    if libraries.is_empty() {
+        libraries.push(obj_path.with_extension("mtl"));
+    }
+
+    let mut materials = HashMap::new();
+    for path in libraries {
+        match std::fs::read_to_string(&path) {
+            Ok(source) => materials.extend(mtl::parse(&source).into_iter().map(|(name, mut material)| {
+                // map_Kd is relative to the mtl file, store it relative to the model instead
+                material.texture = material.texture.map(|texture| {
+                    path.parent().unwrap_or(directory).join(texture).to_string_lossy().into_owned()
+                });
+                (name, material)
+            })),
+            Err(err) => eprintln!("warning: no materials from {}: {}", path.display(), err),
+        }
+    }
+    materials
+}
+
+// texture path (if any) and base colour of a mesh
+type Paint = (Option<PathBuf>, [f32; 3]);
+
+// texture (by path) and base colour for every mesh, matching how the webgl viewer picks them
+fn load_textures(
+    model: &Model,
+    materials: &HashMap<String, Material>,
+    directory: &Path,
+) -> (HashMap<PathBuf, Texture>, Vec<Paint>) {
+    let mut textures: HashMap<PathBuf, Texture> = HashMap::new();
+    let mut missing: Vec<PathBuf> = Vec::new();
+    let mut paints = Vec::with_capacity(model.meshes.len());
+
+    for mesh in &model.meshes {
+        let material = mesh.material.as_ref().and_then(|name| materials.get(name));
+        let name = obj::texture_name(&mesh.name).or_else(|| mesh.material.clone());
+
+        // explicit map_Kd first, then the viewer's textures/<NAME>.png convention
+        let mut candidates: Vec<PathBuf> = material.and_then(|m| m.texture.clone()).map(PathBuf::from).into_iter().collect();
+        if let Some(name) = &name {
+            candidates.push(directory.join("textures").join(format!("{}.png", name)));
+        }
+
+        let mut found = None;
+        for path in candidates {
+            if textures.contains_key(&path) {
+                found = Some(path);
+                break;
+            }
+            if missing.contains(&path) {
+                continue;
+            }
+            match load_image(&path) {
+                Ok(texture) => {
+                    textures.insert(path.clone(), texture);
+                    found = Some(path);
+                    break;
+                }
+                Err(_) => missing.push(path),
+            }
+        }
+
+        let color = match (&found, &name) {
+            (Some(_), _) => [1.0; 3],
+            // no texture name at all gets the neutral colour, a missing file gets a guess from its name
+            (None, None) => obj::fallback_color(None),
+            (None, Some(name)) => {
+                eprintln!("warning: no texture for {} ({})", mesh.name, name);
+                obj::fallback_color(Some(name))
+            }
+        };
+        paints.push((found, color));
+    }
+
+    println!("loaded {} textures", textures.len());
+    (textures, paints)
+}
+
+fn load_image(path: &Path) -> Result<Image, String> {
+    let image = image::open(path).map_err(|e| format!("failed to load {}: {}", path.display(), e))?.into_rgba8();
+    Ok(Image { width: image.width(), height: image.height(), pixels: image.into_raw() })
+}
+
+// frames are independent, so spread them over all cores
+fn render_frames(count: u32, render: impl Fn(u32) -> Image + Sync) -> Vec<Image> {
+    let workers = std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1).min(count);
+    let render = &render;
+
+    let mut frames: Vec<(u32, Image)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                scope.spawn(move || {
+                    (worker..count).step_by(workers as usize).map(|frame| (frame, render(frame))).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().expect("render thread panicked")).collect()
+    });
+
+    frames.sort_by_key(|(frame, _)| *frame);
+    println!("rendered {} frames", frames.len());
+    frames.into_iter().map(|(_, image)| image).collect()
+}
+
+// lay frames out left to right
+fn strip(frames: &[Image]) -> Image {
+    let (width, height) = (frames[0].width, frames[0].height);
+    let row = (width * 4) as usize;
+    let mut pixels = Vec::with_capacity(row * frames.len() * height as usize);
+
+    for y in 0..height as usize {
+        for frame in frames {
+            pixels.extend_from_slice(&frame.pixels[y * row..(y + 1) * row]);
+        }
+    }
+    Image { width: width * frames.len() as u32, height, pixels }
+}
+
+fn save(image: &Image, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+    let buffer = image::RgbaImage::from_raw(image.width, image.height, image.pixels.clone())
+        .ok_or("image buffer has the wrong size")?;
+    buffer.save(path).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+// <model>.turntable.json next to the obj, pointing at the strip by its url
+fn write_manifest(options: &Options) -> Result<(), String> {
+    let manifest = TurntableManifest {
+        src: web_path(&options.output),
+        frames: options.frames,
+        frame_width: options.width,
+        frame_height: options.height,
+    };
+    let path = PathBuf::from(metadata::turntable_path(&options.model.to_string_lossy()));
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json + "\n").map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+// files under static/ are served from /static/ by trunk
+fn web_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    match path.find("static/") {
+        Some(index) => format!("/{}", &path[index..]),
+        None => path,
+    }
+}
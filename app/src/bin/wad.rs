@@ -0,0 +1,389 @@
+// bin/wad.rs
+// reads released wads and writes the map stats shown on the doom projects page
+//
+//   cargo run --release --bin wad -- stats static/doom_projects/wads.json static/doom_projects/map_stats.json
+//   cargo run --release --bin wad -- automap static/doom_projects/wads.json static/doom_projects/automap
+//   cargo run --release --bin wad -- graphics doom_graphics.json
+//   cargo run --release --bin wad -- fonts doom_fonts.json
+//   cargo run --release --bin wad -- doomify doom_thumbnails.json
+//   cargo run --release --bin wad -- info path/to/map.wad
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use app::doom::automap::{self, AutomapMap};
+use app::doom::font::{self, TextColor};
+use app::doom::graphics::{Colormap, Palette, Picture};
+use app::doom::map::Map;
+use app::doom::quantize::{self, Dither};
+use app::doom::stats::{self, WadStats, SKILLS};
+use app::doom::wad::{Lump, Wad, WadError};
+use serde::Deserialize;
+
+const USAGE: &str = "\
+usage:
+  wad stats <wads.json> <map_stats.json>
+  wad automap <wads.json> <output directory>
+  wad graphics <doom_graphics.json>
+  wad fonts <doom_fonts.json>
+  wad doomify <doom_thumbnails.json>
+  wad info <file.wad>...
+
+stats and automap read a json object of project slug -> wad path (relative to the working directory);
+stats writes the numbers of every wad into one file, automap writes <slug>.json with the geometry of
+each map. wads that aren't checked out are skipped with a warning
+
+graphics converts the patches and flats listed in the manifest to png through PLAYPAL/COLORMAP,
+looking lumps up in its wads like the engine does (later wads override earlier ones). fonts packs
+per-character patches, or a zdoom FON1/FON2 lump like BIGFONT, into <out>.json metrics plus one
+<out>_<colour>.png atlas per text colour.
+doomify downscales ordinary images and remaps them onto PLAYPAL, optionally dithered and shaded
+through a COLORMAP light level";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let result = match (args[0].as_str(), &args[1..]) {
+        ("stats", [manifest, output]) => write_stats(Path::new(manifest), Path::new(output)),
+        ("automap", [manifest, output]) => write_automaps(Path::new(manifest), Path::new(output)),
+        ("graphics", [manifest]) => write_graphics(Path::new(manifest)),
+        ("fonts", [manifest]) => write_fonts(Path::new(manifest)),
+        ("doomify", [manifest]) => write_doomified(Path::new(manifest)),
+        ("info", files) if !files.is_empty() => files.iter().try_for_each(|file| print_info(Path::new(file))),
+        _ => Err(format!("invalid arguments\n\n{}", USAGE)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_wad(path: &Path) -> Result<Wad, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Wad::parse(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_stats(path: &Path) -> Result<WadStats, String> {
+    let wad = read_wad(path)?;
+    let file = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    stats::wad_stats(&file, &wad).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_automaps(path: &Path) -> Result<Vec<AutomapMap>, String> {
+    let wad = read_wad(path)?;
+    wad.maps()
+        .iter()
+        .map(|lumps| Map::parse(lumps).map(|map| automap::automap(&map)))
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// slug -> wad path entries whose wad is checked out
+fn available_wads(manifest: &Path) -> Result<Vec<(String, PathBuf)>, String> {
+    let source = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("failed to read {}: {}", manifest.display(), e))?;
+    let wads: BTreeMap<String, PathBuf> =
+        serde_json::from_str(&source).map_err(|e| format!("invalid {}: {}", manifest.display(), e))?;
+
+    Ok(wads
+        .into_iter()
+        .filter(|(slug, path)| {
+            let exists = path.exists();
+            if !exists {
+                eprintln!("warning: {} not found, skipping {}", path.display(), slug);
+            }
+            exists
+        })
+        .collect())
+}
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn write_stats(manifest: &Path, output: &Path) -> Result<(), String> {
+    let mut all = BTreeMap::new();
+    for (slug, path) in available_wads(manifest)? {
+        let stats = read_stats(&path)?;
+        println!("{}: {} maps from {}", slug, stats.maps.len(), path.display());
+        all.insert(slug, stats);
+    }
+    write_json(output, &all)
+}
+
+fn write_automaps(manifest: &Path, output: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(output).map_err(|e| format!("failed to create {}: {}", output.display(), e))?;
+    for (slug, path) in available_wads(manifest)? {
+        write_json(&output.join(format!("{}.json", slug)), &read_automaps(&path)?)?;
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct GraphicsManifest {
+    wads: Vec<PathBuf>,         // load order, relative to the working directory
+    assets: Vec<GraphicAsset>,
+}
+
+#[derive(Deserialize)]
+struct GraphicAsset {
+    lump: String,
+    out: PathBuf,
+    #[serde(default)]
+    kind: GraphicKind,
+    #[serde(default)]
+    palette: usize,             // PLAYPAL index, e.g. 1-8 for the pain tint
+    #[serde(default)]
+    colormap: Option<usize>,    // COLORMAP light level, 0 brightest
+    #[serde(default = "default_scale")]
+    scale: u32,                 // nearest neighbour upscale
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum GraphicKind {
+    #[default]
+    Patch,
+    Flat,
+}
+
+fn default_scale() -> u32 {
+    1
+}
+
+// wads in load order, missing ones skipped with a warning
+fn read_wads(paths: &[PathBuf]) -> Result<Vec<Wad>, String> {
+    paths
+        .iter()
+        .filter(|path| {
+            let exists = path.exists();
+            if !exists {
+                eprintln!("warning: {} not found, its lumps won't be available", path.display());
+            }
+            exists
+        })
+        .map(|path| read_wad(path))
+        .collect()
+}
+
+fn required_lump<'a>(wads: &'a [Wad], name: &str) -> Result<&'a Lump, String> {
+    wads.iter().rev().find_map(|wad| wad.lump(name)).ok_or_else(|| WadError::MissingLump(name.to_string()).to_string())
+}
+
+fn write_graphics(manifest: &Path) -> Result<(), String> {
+    let source = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("failed to read {}: {}", manifest.display(), e))?;
+    let manifest: GraphicsManifest =
+        serde_json::from_str(&source).map_err(|e| format!("invalid {}: {}", manifest.display(), e))?;
+
+    let wads = read_wads(&manifest.wads)?;
+    let find = |lookup: &dyn Fn(&Wad) -> Option<&Lump>| wads.iter().rev().find_map(lookup);
+
+    let playpal = required_lump(&wads, "PLAYPAL")?;
+    let colormap = Colormap::from_lump(&required_lump(&wads, "COLORMAP")?.data).map_err(|e| e.to_string())?;
+
+    let mut skipped = 0;
+    for asset in &manifest.assets {
+        let lump = match asset.kind {
+            GraphicKind::Patch => find(&|wad| wad.lump(&asset.lump)),
+            GraphicKind::Flat => find(&|wad| wad.flat(&asset.lump)),
+        };
+        let Some(lump) = lump else {
+            eprintln!("warning: {} not found, skipping {}", asset.lump, asset.out.display());
+            skipped += 1;
+            continue;
+        };
+
+        let picture = match asset.kind {
+            GraphicKind::Patch => Picture::from_patch(&lump.name, &lump.data),
+            GraphicKind::Flat => Picture::from_flat(&lump.name, &lump.data),
+        };
+        let picture = picture.map_err(|e| e.to_string())?;
+        let palette = Palette::from_playpal(&playpal.data, asset.palette).map_err(|e| e.to_string())?;
+        let level = match asset.colormap {
+            Some(level) => Some(colormap.level(level).ok_or_else(|| format!("{}: no colormap level {}", asset.lump, level))?),
+            None => None,
+        };
+        save_png(&picture, &picture.to_rgba(&palette, level), asset.scale.max(1), &asset.out)?;
+    }
+
+    if skipped > 0 {
+        eprintln!("{} of {} assets skipped", skipped, manifest.assets.len());
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct FontsManifest {
+    wads: Vec<PathBuf>,
+    fonts: Vec<FontSource>,
+}
+
+#[derive(Deserialize)]
+struct FontSource {
+    #[serde(flatten)]
+    lumps: FontLumps,
+    space: u32,
+    out: PathBuf,       // <out>.json and <out>_<colour>.png
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FontLumps {
+    // one patch per character, {code} is the zero padded character code: "STCFN{code}"
+    Patches { lumps: String, first: u32, last: u32 },
+    // a zdoom FON1/FON2 lump holding the whole font: "BIGFONT"
+    Lump { lump: String },
+}
+
+impl FontLumps {
+    fn name(&self) -> &str {
+        match self {
+            FontLumps::Patches { lumps, .. } => lumps,
+            FontLumps::Lump { lump } => lump,
+        }
+    }
+}
+
+fn write_fonts(manifest: &Path) -> Result<(), String> {
+    let source = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("failed to read {}: {}", manifest.display(), e))?;
+    let manifest: FontsManifest =
+        serde_json::from_str(&source).map_err(|e| format!("invalid {}: {}", manifest.display(), e))?;
+    let wads = read_wads(&manifest.wads)?;
+    let palette = Palette::from_playpal(&required_lump(&wads, "PLAYPAL")?.data, 0).map_err(|e| e.to_string())?;
+
+    for font in &manifest.fonts {
+        let (glyphs, palette) = match &font.lumps {
+            FontLumps::Patches { lumps, first, last } => (patch_glyphs(&wads, lumps, *first, *last)?, palette.clone()),
+            FontLumps::Lump { lump } => match wads.iter().rev().find_map(|wad| wad.lump(lump)) {
+                Some(lump) => {
+                    let lump = font::from_font_lump(&lump.name, &lump.data).map_err(|e| e.to_string())?;
+                    (lump.glyphs, lump.palette)
+                }
+                None => (Vec::new(), palette.clone()),
+            },
+        };
+        if glyphs.is_empty() {
+            eprintln!("warning: no {} lumps found, skipping {}", font.lumps.name(), font.out.display());
+            continue;
+        }
+
+        let atlas = font::build_atlas(&glyphs, font.space);
+        for color in TextColor::ALL {
+            let path = font.out.with_file_name(format!(
+                "{}_{}.png",
+                font.out.file_name().map(|name| name.to_string_lossy()).unwrap_or_default(),
+                color.name()
+            ));
+            save_png(&atlas.picture, &font::translate(&atlas.picture, &palette, color), 1, &path)?;
+        }
+        write_json(&font.out.with_extension("json"), &atlas.metrics)?;
+    }
+    Ok(())
+}
+
+fn patch_glyphs(wads: &[Wad], lumps: &str, first: u32, last: u32) -> Result<Vec<(char, Picture)>, String> {
+    let mut glyphs = Vec::new();
+    for code in first..=last {
+        let name = lumps.replace("{code}", &format!("{:03}", code));
+        let (Some(c), Some(lump)) = (char::from_u32(code), wads.iter().rev().find_map(|wad| wad.lump(&name))) else {
+            continue;   // fonts commonly skip characters
+        };
+        glyphs.push((c, Picture::from_patch(&lump.name, &lump.data).map_err(|e| e.to_string())?));
+    }
+    Ok(glyphs)
+}
+
+#[derive(Deserialize)]
+struct DoomifyManifest {
+    wads: Vec<PathBuf>,         // only PLAYPAL and COLORMAP are read
+    images: Vec<DoomifyImage>,
+}
+
+#[derive(Deserialize)]
+struct DoomifyImage {
+    src: PathBuf,
+    out: PathBuf,
+    #[serde(default)]
+    width: Option<u32>,         // downscale to this width first, keeping the aspect ratio
+    #[serde(default)]
+    dither: Dither,
+    #[serde(default)]
+    palette: usize,
+    #[serde(default)]
+    colormap: Option<usize>,
+}
+
+fn write_doomified(manifest: &Path) -> Result<(), String> {
+    let source = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("failed to read {}: {}", manifest.display(), e))?;
+    let manifest: DoomifyManifest =
+        serde_json::from_str(&source).map_err(|e| format!("invalid {}: {}", manifest.display(), e))?;
+    let wads = read_wads(&manifest.wads)?;
+    let playpal = required_lump(&wads, "PLAYPAL")?;
+    let colormap = Colormap::from_lump(&required_lump(&wads, "COLORMAP")?.data).map_err(|e| e.to_string())?;
+
+    for entry in &manifest.images {
+        let image = image::open(&entry.src).map_err(|e| format!("failed to read {}: {}", entry.src.display(), e))?;
+        let image = match entry.width {
+            Some(width) if width < image.width() => {
+                let height = (image.height() as u64 * width as u64 / image.width() as u64).max(1) as u32;
+                image.resize_exact(width, height, image::imageops::FilterType::Triangle)
+            }
+            _ => image,
+        }
+        .to_rgba8();
+
+        let palette = Palette::from_playpal(&playpal.data, entry.palette).map_err(|e| e.to_string())?;
+        let level = match entry.colormap {
+            Some(level) => Some(colormap.level(level).ok_or_else(|| format!("{}: no colormap level {}", entry.src.display(), level))?),
+            None => None,
+        };
+        let picture = quantize::quantize(image.as_raw(), image.width() as usize, image.height() as usize, &palette, entry.dither);
+        save_png(&picture, &picture.to_rgba(&palette, level), 1, &entry.out)?;
+    }
+    Ok(())
+}
+
+fn save_png(picture: &Picture, rgba: &[u8], scale: u32, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+    let (width, height) = (picture.width as u32, picture.height as u32);
+    let image = image::RgbaImage::from_raw(width, height, rgba.to_vec()).ok_or("image buffer has the wrong size")?;
+    let image = if scale > 1 {
+        image::imageops::resize(&image, width * scale, height * scale, image::imageops::FilterType::Nearest)
+    } else {
+        image
+    };
+    image.save(path).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn print_info(path: &Path) -> Result<(), String> {
+    let stats = read_stats(path)?;
+    println!("{}", stats.file);
+    for map in &stats.maps {
+        println!(
+            "  {} ({:?}): {}x{} units, {} lines, {} sectors, {} secrets",
+            map.name, map.format, map.width, map.height, map.lines, map.sectors, map.secrets
+        );
+        for (skill, counts) in SKILLS.iter().zip(&map.skills) {
+            println!("    {:<5} {:>4} monsters {:>4} items", skill, counts.monsters, counts.items);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,162 @@
+// components/attachment_picker.rs
+use web_sys::{DragEvent, File, FileList, HtmlInputElement, Url};
+use yew::prelude::*;
+
+use crate::components::loading_bar::format_bytes;
+use crate::contact::attachments::{self, MAX_ATTACHMENTS, MAX_ATTACHMENT_SIZE};
+
+#[derive(Properties, PartialEq)]
+pub struct AttachmentPickerProps {
+    pub files: Vec<File>,
+    pub on_change: Callback<Vec<File>>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+// drop zone plus file input, refusing what the attachment rules don't allow, with a preview list below
+#[function_component(AttachmentPicker)]
+pub fn attachment_picker(props: &AttachmentPickerProps) -> Html {
+    let dragging = use_state(|| false);
+    let refused = use_state(Vec::<String>::new);
+
+    // thumbnails for images, revoked once the list changes
+    let previews = use_memo(props.files.clone(), |files| {
+        files
+            .iter()
+            .map(|file| attachments::is_image(&file.name()).then(|| Url::create_object_url_with_blob(file).ok()).flatten())
+            .collect::<Vec<_>>()
+    });
+    use_effect_with(previews.clone(), |previews| {
+        let urls = (**previews).clone();
+        move || {
+            for url in urls.into_iter().flatten() {
+                let _ = Url::revoke_object_url(&url);
+            }
+        }
+    });
+
+    let add = {
+        let (files, on_change, refused) = (props.files.clone(), props.on_change.clone(), refused.clone());
+        Callback::from(move |list: FileList| {
+            let mut files = files.clone();
+            let mut problems = Vec::new();
+            for file in (0..list.length()).filter_map(|index| list.get(index)) {
+                let attached: Vec<u64> = files.iter().map(|file| file.size() as u64).collect();
+                match attachments::check(&file.name(), file.size() as u64, &attached) {
+                    Ok(()) => files.push(file),
+                    Err(err) => problems.push(err.to_string()),
+                }
+            }
+            refused.set(problems);
+            on_change.emit(files);
+        })
+    };
+
+    let on_pick = {
+        let add = add.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(list) = input.files() {
+                add.emit(list);
+            }
+            // picking the same file again should still fire
+            input.set_value("");
+        })
+    };
+    let on_drag_over = {
+        let (dragging, disabled) = (dragging.clone(), props.disabled);
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            if !disabled && !*dragging {
+                dragging.set(true);
+            }
+        })
+    };
+    let on_drag_leave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| dragging.set(false))
+    };
+    let on_drop = {
+        let (dragging, disabled) = (dragging.clone(), props.disabled);
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(false);
+            if let Some(list) = e.data_transfer().and_then(|transfer| transfer.files()).filter(|_| !disabled) {
+                add.emit(list);
+            }
+        })
+    };
+
+    html! {
+        <div>
+            <label
+                for="attachments"
+                ondragover={on_drag_over}
+                ondragleave={on_drag_leave}
+                ondrop={on_drop}
+                class={classes!(
+                    "block", "p-6", "border-2", "border-dashed", "rounded-lg", "text-center", "font-mono", "text-sm",
+                    "transition-colors", "duration-200",
+                    if props.disabled { "opacity-50 cursor-not-allowed border-gray-600" }
+                    else if *dragging { "border-red-600 bg-red-600/10 cursor-copy" }
+                    else { "border-gray-600 hover:border-red-600 cursor-pointer" },
+                )}
+            >
+                <span class="text-gray-300">{"Drop files here or "}<span class="text-red-600 underline">{"browse"}</span></span>
+                <span class="block text-gray-500 text-xs mt-1">
+                    {format!("Up to {} files, {} MB each: {}", MAX_ATTACHMENTS, MAX_ATTACHMENT_SIZE / (1024 * 1024), attachments::ALLOWED_EXTENSIONS.join(", "))}
+                </span>
+                <input
+                    id="attachments"
+                    type="file"
+                    multiple=true
+                    accept={attachments::accept()}
+                    disabled={props.disabled}
+                    onchange={on_pick}
+                    class="sr-only"
+                />
+            </label>
+
+            { for refused.iter().map(|problem| html! {
+                <div class="mt-1 text-sm text-red-400" role="alert">{problem}</div>
+            })}
+
+            if !props.files.is_empty() {
+                <ul class="mt-3 space-y-2">
+                    { for props.files.iter().zip(previews.iter()).enumerate().map(|(index, (file, preview))| {
+                        let name = file.name();
+                        let on_remove = {
+                            let (files, on_change) = (props.files.clone(), props.on_change.clone());
+                            Callback::from(move |_| {
+                                let mut files = files.clone();
+                                files.remove(index);
+                                on_change.emit(files);
+                            })
+                        };
+                        html! {
+                            <li class="flex items-center gap-3 p-2 bg-[#1a1a1a] border border-gray-600 rounded-lg">
+                                {match preview {
+                                    Some(url) => html! { <img src={url.clone()} alt="" class="w-10 h-10 object-cover flex-shrink-0" /> },
+                                    None => html! {
+                                        <span class="w-10 h-10 flex-shrink-0 flex items-center justify-center bg-[#2b2b2b] border-2 border-[#0b0b0a] text-red-600 text-xs font-mono uppercase">
+                                            {name.rsplit_once('.').map(|(_, extension)| extension.to_string()).unwrap_or_default()}
+                                        </span>
+                                    },
+                                }}
+                                <span class="min-w-0 flex-1 truncate text-white">{&name}</span>
+                                <span class="text-xs text-gray-400 font-mono flex-shrink-0">{format_bytes(file.size() as u64)}</span>
+                                <button
+                                    type="button"
+                                    onclick={on_remove}
+                                    disabled={props.disabled}
+                                    aria-label={format!("Remove {}", name)}
+                                    class="px-2 text-gray-400 hover:text-red-600 cursor-pointer disabled:cursor-not-allowed"
+                                >{"✕"}</button>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,418 @@
+// components/automap.rs
+use gloo_events::{EventListener, EventListenerOptions};
+use gloo_net::http::Request;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, PointerEvent, WheelEvent};
+use yew::prelude::*;
+
+use crate::components::loading_bar::LoadingBar;
+use crate::doom::automap::{AutomapMap, LineKind, ThingKind};
+use crate::doom::things::Key;
+
+// classic automap palette (doom's PLAYPAL entries for each line type)
+const WALL_COLOR: &str = "#fc0000";
+const FLOOR_STEP_COLOR: &str = "#bc7848";
+const CEILING_STEP_COLOR: &str = "#fcfc00";
+const TELEPORTER_COLOR: &str = "#9c0000";
+const THING_COLOR: &str = "#74fc6c";
+const PLAYER_COLOR: &str = "#ffffff";
+
+const ZOOM_STEP: f64 = 1.25;
+const PAN_STEP: f64 = 0.1;      // fraction of the view per arrow key press
+const THING_SIZE: f64 = 16.0;   // map units, like the engine's thing triangles
+
+#[derive(Properties, PartialEq)]
+pub struct AutomapProps {
+    pub src: String,            // json written by `cargo run --bin wad -- automap`
+    pub title: String,
+    pub on_close: Callback<()>,
+}
+
+#[derive(Clone, PartialEq)]
+enum AutomapState {
+    Loading,
+    Ready(Vec<AutomapMap>),
+    Failed(String),
+}
+
+// visible square of the map in svg coordinates (y points down, so doom's y is negated)
+#[derive(Clone, Copy, PartialEq)]
+struct View {
+    x: f64,
+    y: f64,
+    span: f64,
+}
+
+impl View {
+    fn fit(map: &AutomapMap) -> View {
+        let span = (map.max[0] - map.min[0]).max(map.max[1] - map.min[1]).max(64.0) * 1.05;
+        View { x: (map.min[0] + map.max[0]) / 2.0, y: -(map.min[1] + map.max[1]) / 2.0, span }
+    }
+
+    // zoom by factor, keeping the point at (dx, dy) from the centre (in map units) in place
+    fn zoom(self, factor: f64, dx: f64, dy: f64) -> View {
+        View { x: self.x + dx * (1.0 - factor), y: self.y + dy * (1.0 - factor), span: self.span * factor }
+    }
+}
+
+fn key_color(key: Key) -> &'static str {
+    match key {
+        Key::Blue => "#5c5cff",
+        Key::Yellow => "#fcfc00",
+        Key::Red => "#fc0000",
+    }
+}
+
+fn line_style(kind: LineKind) -> (&'static str, f64) {
+    match kind {
+        LineKind::Wall => (WALL_COLOR, 1.5),
+        LineKind::FloorStep => (FLOOR_STEP_COLOR, 1.5),
+        LineKind::CeilingStep => (CEILING_STEP_COLOR, 1.5),
+        LineKind::Teleporter => (TELEPORTER_COLOR, 1.5),
+        LineKind::KeyDoor(key) => (key_color(key), 4.0),
+    }
+}
+
+// triangle pointing along the thing's angle, like the iddt cheat draws them
+fn thing_points(x: f64, y: f64, angle: f64, size: f64) -> String {
+    let angle = angle.to_radians();
+    [(1.0, 0.0), (-0.7, 0.6), (-0.7, -0.6)]
+        .iter()
+        .map(|(u, v)| {
+            let px = x + size * (u * angle.cos() - v * angle.sin());
+            let py = y + size * (u * angle.sin() + v * angle.cos());
+            format!("{:.1},{:.1}", px, -py)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+async fn fetch_automap(path: &str) -> Result<Vec<AutomapMap>, String> {
+    let response = Request::get(path).send().await.map_err(|e| format!("Network error: {}", e))?;
+    if !response.ok() {
+        return Err(format!("HTTP {} error", response.status()));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+// top-down line drawing of a released map, panned by dragging and zoomed with the wheel or keys
+#[function_component(Automap)]
+pub fn automap(props: &AutomapProps) -> Html {
+    let state = use_state(|| AutomapState::Loading);
+    let map_index = use_state(|| 0usize);
+    let show_things = use_state(|| false);
+    let view = use_mut_ref(|| None::<View>);
+    let drag = use_mut_ref(|| None::<(i32, f64, f64)>);    // pointer id and last client position
+    let force_update = use_force_update();
+    let container_ref = use_node_ref();
+    let svg_ref = use_node_ref();
+
+    {
+        let state = state.clone();
+        use_effect_with(props.src.clone(), move |src| {
+            let src = src.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_automap(&src).await {
+                    Ok(maps) => state.set(AutomapState::Ready(maps)),
+                    Err(err) => {
+                        tracing::error!("Failed to load automap {}: {}", src, err);
+                        state.set(AutomapState::Failed(err));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let map = match &*state {
+        AutomapState::Ready(maps) => maps.get(*map_index).or(maps.first()).cloned(),
+        _ => None,
+    };
+
+    // fit the whole map whenever another one is shown
+    {
+        let view = view.clone();
+        let force_update = force_update.clone();
+        use_effect_with(map.as_ref().map(|map| map.name.clone()), move |_| {
+            *view.borrow_mut() = None;
+            force_update.force_update();
+            || ()
+        });
+    }
+
+    // keyboard focus goes to the dialog so the shortcuts work straight away
+    {
+        let container_ref = container_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(container) = container_ref.cast::<HtmlElement>() {
+                let _ = container.focus();
+            }
+            || ()
+        });
+    }
+
+    // map units per css pixel (the svg keeps the square view box fully visible)
+    let units_per_pixel = {
+        let svg_ref = svg_ref.clone();
+        let view = view.clone();
+        move || -> Option<f64> {
+            let svg = svg_ref.cast::<Element>()?;
+            let size = svg.client_width().min(svg.client_height()) as f64;
+            view.borrow().map(|view| view.span / size.max(1.0))
+        }
+    };
+
+    // wheel zoom around the cursor (manual listener so it can prevent page scroll)
+    {
+        let svg_ref = svg_ref.clone();
+        let view = view.clone();
+        let force_update = force_update.clone();
+        let units_per_pixel = units_per_pixel.clone();
+        use_effect_with(map.is_some(), move |_| {
+            let listener = svg_ref.cast::<Element>().map(|svg| {
+                let target = svg.clone();
+                EventListener::new_with_options(&svg, "wheel", EventListenerOptions::enable_prevent_default(), move |event| {
+                    let Some(event) = event.dyn_ref::<WheelEvent>() else { return };
+                    event.prevent_default();
+                    let Some(scale) = units_per_pixel() else { return };
+                    let dx = (event.offset_x() as f64 - target.client_width() as f64 / 2.0) * scale;
+                    let dy = (event.offset_y() as f64 - target.client_height() as f64 / 2.0) * scale;
+                    let factor = if event.delta_y() > 0.0 { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+                    let current = *view.borrow();
+                    if let Some(current) = current {
+                        *view.borrow_mut() = Some(current.zoom(factor, dx, dy));
+                        force_update.force_update();
+                    }
+                })
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_pointer_down = {
+        let drag = drag.clone();
+        Callback::from(move |e: PointerEvent| {
+            if let Some(target) = e.target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                let _ = target.set_pointer_capture(e.pointer_id());
+            }
+            *drag.borrow_mut() = Some((e.pointer_id(), e.client_x() as f64, e.client_y() as f64));
+        })
+    };
+
+    let on_pointer_move = {
+        let drag = drag.clone();
+        let view = view.clone();
+        let force_update = force_update.clone();
+        let units_per_pixel = units_per_pixel.clone();
+        Callback::from(move |e: PointerEvent| {
+            let Some((id, last_x, last_y)) = *drag.borrow() else { return };
+            if id != e.pointer_id() {
+                return;
+            }
+            let (x, y) = (e.client_x() as f64, e.client_y() as f64);
+            *drag.borrow_mut() = Some((id, x, y));
+            let (Some(scale), Some(current)) = (units_per_pixel(), *view.borrow()) else { return };
+            *view.borrow_mut() = Some(View { x: current.x - (x - last_x) * scale, y: current.y - (y - last_y) * scale, ..current });
+            force_update.force_update();
+        })
+    };
+
+    let on_pointer_up = {
+        let drag = drag.clone();
+        Callback::from(move |_: PointerEvent| *drag.borrow_mut() = None)
+    };
+
+    let zoom = {
+        let view = view.clone();
+        let force_update = force_update.clone();
+        Callback::from(move |factor: f64| {
+            let current = *view.borrow();
+            if let Some(current) = current {
+                *view.borrow_mut() = Some(current.zoom(factor, 0.0, 0.0));
+                force_update.force_update();
+            }
+        })
+    };
+
+    let reset = {
+        let view = view.clone();
+        let force_update = force_update.clone();
+        Callback::from(move |_| {
+            *view.borrow_mut() = None;
+            force_update.force_update();
+        })
+    };
+
+    let toggle_things = {
+        let show_things = show_things.clone();
+        Callback::from(move |_| show_things.set(!*show_things))
+    };
+
+    let on_key_down = {
+        let view = view.clone();
+        let zoom = zoom.clone();
+        let reset = reset.clone();
+        let toggle_things = toggle_things.clone();
+        let on_close = props.on_close.clone();
+        let force_update = force_update.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let pan = |dx: f64, dy: f64| {
+                let current = *view.borrow();
+                if let Some(current) = current {
+                    let step = current.span * PAN_STEP;
+                    *view.borrow_mut() = Some(View { x: current.x + dx * step, y: current.y + dy * step, ..current });
+                    force_update.force_update();
+                }
+            };
+            match e.key().as_str() {
+                "Escape" => on_close.emit(()),
+                "+" | "=" => zoom.emit(1.0 / ZOOM_STEP),
+                "-" | "_" => zoom.emit(ZOOM_STEP),
+                "0" => reset.emit(()),
+                "t" | "T" => toggle_things.emit(()),
+                "ArrowLeft" => pan(-1.0, 0.0),
+                "ArrowRight" => pan(1.0, 0.0),
+                "ArrowUp" => pan(0.0, -1.0),
+                "ArrowDown" => pan(0.0, 1.0),
+                _ => return,
+            }
+            e.prevent_default();
+        })
+    };
+
+    let close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+
+    let current_view = map.as_ref().map(|map| *view.borrow_mut().get_or_insert_with(|| View::fit(map)));
+
+    let button_class = "bg-[#2b2b2b] hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-1 px-3 rounded transition-all duration-200 cursor-pointer font-mono text-sm";
+
+    html! {
+        <div class="fixed inset-0 backdrop-blur-lg bg-black/80 flex items-center justify-center z-50" onclick={close.clone()}>
+            <div
+                ref={container_ref}
+                role="dialog"
+                aria-modal="true"
+                aria-label={format!("{} automap", props.title)}
+                tabindex="-1"
+                onkeydown={on_key_down}
+                onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                class="relative w-full h-full max-w-5xl max-h-[90vh] m-4 flex flex-col bg-black border-4 border-[#0b0b0a] outline-none"
+            >
+                // toolbar
+                <div class="flex flex-wrap items-center gap-2 p-2 bg-[#1a1a1a] font-mono">
+                    <span class="text-red-600 font-bold mr-auto">{&props.title}</span>
+                    if let AutomapState::Ready(maps) = &*state {
+                        if maps.len() > 1 {
+                            { for maps.iter().enumerate().map(|(index, entry)| {
+                                let map_index = map_index.clone();
+                                let selected = index == *map_index;
+                                html! {
+                                    <button
+                                        onclick={Callback::from(move |_| map_index.set(index))}
+                                        aria-pressed={selected.to_string()}
+                                        class={classes!(button_class, selected.then_some("bg-red-600 text-white"))}
+                                    >
+                                        {&entry.name}
+                                    </button>
+                                }
+                            })}
+                        }
+                    }
+                    <button onclick={toggle_things.reform(|_: MouseEvent| ())} aria-pressed={show_things.to_string()} class={classes!(button_class, show_things.then_some("bg-red-600 text-white"))}>
+                        {"THINGS"}
+                    </button>
+                    <button onclick={{ let zoom = zoom.clone(); Callback::from(move |_| zoom.emit(1.0 / ZOOM_STEP)) }} aria-label="Zoom in" class={button_class}>{"+"}</button>
+                    <button onclick={{ let zoom = zoom.clone(); Callback::from(move |_| zoom.emit(ZOOM_STEP)) }} aria-label="Zoom out" class={button_class}>{"-"}</button>
+                    <button onclick={reset.reform(|_: MouseEvent| ())} class={button_class}>{"RESET"}</button>
+                    <button onclick={close} aria-label="Close automap" class={button_class}>{"✕"}</button>
+                </div>
+
+                // map
+                <div class="relative flex-1 min-h-0">
+                    {match (&*state, map.as_ref(), current_view) {
+                        (AutomapState::Failed(err), _, _) => html! {
+                            <div class="absolute inset-0 flex items-center justify-center text-red-600 font-mono">
+                                {format!("Failed to load automap: {}", err)}
+                            </div>
+                        },
+                        (_, Some(map), Some(view)) => html! {
+                            <svg
+                                ref={svg_ref}
+                                class="w-full h-full cursor-grab active:cursor-grabbing"
+                                style="touch-action: none;"
+                                viewBox={format!("{} {} {} {}", view.x - view.span / 2.0, view.y - view.span / 2.0, view.span, view.span)}
+                                preserveAspectRatio="xMidYMid meet"
+                                onpointerdown={on_pointer_down}
+                                onpointermove={on_pointer_move}
+                                onpointerup={on_pointer_up.clone()}
+                                onpointercancel={on_pointer_up}
+                            >
+                                <title>{format!("{} {} automap", props.title, map.name)}</title>
+                                // key doors last so they draw over overlapping walls
+                                { for map.lines.iter()
+                                    .filter(|line| !matches!(line.kind, LineKind::KeyDoor(_)))
+                                    .chain(map.lines.iter().filter(|line| matches!(line.kind, LineKind::KeyDoor(_))))
+                                    .map(|line| {
+                                        let (color, width) = line_style(line.kind);
+                                        html! {
+                                            <line
+                                                x1={line.from[0].to_string()} y1={(-line.from[1]).to_string()}
+                                                x2={line.to[0].to_string()} y2={(-line.to[1]).to_string()}
+                                                stroke={color}
+                                                stroke-width={width.to_string()}
+                                                stroke-linecap="round"
+                                                vector-effect="non-scaling-stroke"
+                                            />
+                                        }
+                                    })
+                                }
+                                if *show_things {
+                                    { for map.things.iter().map(|thing| {
+                                        let (color, size) = match thing.kind {
+                                            ThingKind::Player => (PLAYER_COLOR, THING_SIZE * 1.5),
+                                            ThingKind::Key(key) => (key_color(key), THING_SIZE),
+                                            ThingKind::Monster | ThingKind::Other => (THING_COLOR, THING_SIZE),
+                                        };
+                                        html! {
+                                            <polygon
+                                                points={thing_points(thing.x, thing.y, thing.angle, size)}
+                                                fill={(thing.kind == ThingKind::Monster).then_some(THING_COLOR)}
+                                                fill-opacity="0.5"
+                                                stroke={color}
+                                                stroke-width="1"
+                                                vector-effect="non-scaling-stroke"
+                                            />
+                                        }
+                                    })}
+                                }
+                            </svg>
+                        },
+                        _ => html! {
+                            <div class="absolute inset-0 flex items-center justify-center">
+                                <LoadingBar label="LOADING AUTOMAP" />
+                            </div>
+                        },
+                    }}
+                </div>
+
+                // legend and shortcuts
+                <div class="flex flex-wrap gap-x-4 gap-y-1 p-2 bg-[#1a1a1a] font-mono text-xs text-gray-400">
+                    <span><span style={format!("color: {};", WALL_COLOR)}>{"━ "}</span>{"WALL"}</span>
+                    <span><span style={format!("color: {};", FLOOR_STEP_COLOR)}>{"━ "}</span>{"FLOOR STEP"}</span>
+                    <span><span style={format!("color: {};", CEILING_STEP_COLOR)}>{"━ "}</span>{"CEILING STEP"}</span>
+                    <span><span style={format!("color: {};", TELEPORTER_COLOR)}>{"━ "}</span>{"TELEPORTER"}</span>
+                    <span>
+                        <span style={format!("color: {};", key_color(Key::Blue))}>{"▬"}</span>
+                        <span style={format!("color: {};", key_color(Key::Yellow))}>{"▬"}</span>
+                        <span style={format!("color: {};", key_color(Key::Red))}>{"▬ "}</span>
+                        {"KEY DOOR"}
+                    </span>
+                    <span class="ml-auto">{"DRAG: PAN · WHEEL/+/-: ZOOM · T: THINGS · 0: RESET · ESC: CLOSE"}</span>
+                </div>
+            </div>
+        </div>
+    }
+}
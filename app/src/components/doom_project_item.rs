@@ -1,5 +1,15 @@
 // components/doom_project_item.rs
 use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::automap::Automap;
+use crate::components::map_stats::MapStats;
+use crate::components::project_item::CARD_SIZES;
+use crate::components::lightbox::Lightbox;
+use crate::media::Media;
+use crate::components::responsive_image::{Loading, ResponsiveImage};
+use crate::doom::stats::WadStats;
+use crate::router::Route;
 
 #[derive(Properties, PartialEq)]
 pub struct ProjectItemProps {
@@ -9,7 +19,15 @@ pub struct ProjectItemProps {
     #[prop_or_default]
     pub image_alt: Option<String>,
     #[prop_or_default]
-    pub additional_images: Vec<String>, 
+    pub gallery: Vec<Media>,            // shown after the cover image
+    #[prop_or_default]
+    pub id: Option<String>,             // anchor for deep links
+    #[prop_or_default]
+    pub open_image: Option<usize>,      // open the lightbox on this image when mounted (deep link to a screenshot)
+    #[prop_or_default]
+    pub stats: Option<WadStats>,        // computed from the released wad at build time
+    #[prop_or_default]
+    pub playable: bool,                 // a source port is installed for the Play route
 }
 
 #[function_component(DoomProjectItem)]
@@ -19,68 +37,54 @@ pub fn project_item(props: &ProjectItemProps) -> Html {
         .clone();
 
     let lightbox_open = use_state(|| false);
-    let current_image_index = use_state(|| 0usize);
-
-    // create combined list of all images (main image + additional images)
-    let all_images = {
-        let mut images = vec![props.image_src.clone()];
-        images.extend(props.additional_images.iter().cloned());
-        images
+    let automap_open = use_state(|| false);
+    let start_image = use_state(|| 0usize);
+
+    // create combined gallery (main image + gallery items)
+    let gallery = {
+        let mut items = vec![Media::image(&props.image_src)];
+        items.extend(props.gallery.iter().cloned());
+        items
     };
 
-    let gallery_click = {
+    // deep link straight into the gallery
+    {
         let lightbox_open = lightbox_open.clone();
-        let current_image_index = current_image_index.clone();
-        Callback::from(move |_| {
-            lightbox_open.set(true);
-            current_image_index.set(0); // reset to first image when opening lightbox
-        })
-    };
+        let start_image = start_image.clone();
+        let total_items = gallery.len();
+        use_effect_with(props.open_image, move |open_image| {
+            if let Some(index) = *open_image {
+                start_image.set(index.min(total_items - 1));
+                lightbox_open.set(true);
+            }
+            || ()
+        });
+    }
 
-    let close_lightbox = {
+    let gallery_click = {
         let lightbox_open = lightbox_open.clone();
+        let start_image = start_image.clone();
         Callback::from(move |_| {
-            lightbox_open.set(false);
+            lightbox_open.set(true);
+            start_image.set(0); // reset to first image when opening lightbox
         })
     };
 
-    let prev_image = {
-        let current_image_index = current_image_index.clone();
-        let total_images = all_images.len();
-        Callback::from(move |e: MouseEvent| {
-            e.stop_propagation(); // prevent lightbox from closing
-            let current = *current_image_index;
-            let new_index = if current == 0 {
-                total_images - 1
-            } else {
-                current - 1
-            };
-            current_image_index.set(new_index);
-        })
+    let toggle_automap = {
+        let automap_open = automap_open.clone();
+        Callback::from(move |_| automap_open.set(!*automap_open))
     };
 
-    let next_image = {
-        let current_image_index = current_image_index.clone();
-        let total_images = all_images.len();
-        Callback::from(move |e: MouseEvent| {
-            e.stop_propagation(); // prevent lightbox from closing
-            let current = *current_image_index;
-            let new_index = (current + 1) % total_images;
-            current_image_index.set(new_index);
-        })
-    };
-    
-    let _download_click = {
-        // TODO: IMPLEMENT
+    let close_lightbox = {
+        let lightbox_open = lightbox_open.clone();
+        Callback::from(move |_| lightbox_open.set(false))
     };
 
-    let current_image_src = all_images.get(*current_image_index)
-        .unwrap_or(&props.image_src)
-        .clone();
+    // TODO: IMPLEMENT download click
 
     html! {
         <>  // fragment to group project item and lightbox
-            <div class="max-w-sm hover:scale-105 transition-all duration-300">
+            <div id={props.id.clone()} class="max-w-sm hover:scale-105 transition-all duration-300">
                 <div 
                     class="relative overflow-hidden shadow-lg hover:shadow-xl transition-all duration-300"
                     style="background-image: url('/static/common/STBAR_MID.png'); 
@@ -98,9 +102,11 @@ pub fn project_item(props: &ProjectItemProps) -> Html {
                     <div class="relative z-10 p-6 h-full flex flex-col">
                         // project image
                         <div class="aspect-video bg-[#2b2b2b] overflow-hidden rounded mb-4">
-                            <img 
+                            <ResponsiveImage
                                 src={props.image_src.clone()}
                                 alt={alt_text}
+                                sizes={CARD_SIZES}
+                                loading={Loading::Lazy}
                                 class="w-full h-full object-contain image-rendering-pixelated"
                             />
                         </div>
@@ -114,6 +120,11 @@ pub fn project_item(props: &ProjectItemProps) -> Html {
                         <p class="text-gray-300 mb-4 text-sm leading-relaxed flex-grow">
                             {&props.description}
                         </p>
+
+                        // map stats
+                        if let Some(stats) = props.stats.clone() {
+                            <MapStats stats={stats} />
+                        }
                         
                         // buttons row
                         <div class="flex justify-between items-start mb-3 gap-3">
@@ -137,75 +148,55 @@ pub fn project_item(props: &ProjectItemProps) -> Html {
                                 </div>
                             </button>
                         </div>
+
+                        // released wads: automap and in-browser play (both built from the wad, so only offered when its stats exist,
+                        // and play only once there is an engine to run it)
+                        if let (Some(_), Some(id)) = (&props.stats, &props.id) {
+                            <div class="flex justify-between items-start mb-3 gap-3">
+                                <button 
+                                    onclick={toggle_automap.reform(|_: MouseEvent| ())}
+                                    class="group w-full bg-[#2b2b2b] hover:bg-red-600 border-2 border-red-600 hover:border-red-600 text-red-600 hover:text-white font-bold py-2 px-4 rounded transition-all duration-200 cursor-pointer font-mono text-sm">
+                                    <div class="flex items-center justify-center gap-2">    
+                                        <span>{"AUTOMAP"}</span>
+                                        <span class="text-xs group-hover:translate-x-1 transition-transform duration-200">{"→"}</span>
+                                    </div>
+                                </button>
+                                if props.playable {
+                                    <Link<Route> 
+                                        to={Route::Play { map: id.clone() }}
+                                        classes="group w-full bg-[#2b2b2b] hover:bg-red-600 border-2 border-red-600 hover:border-red-600 text-red-600 hover:text-white font-bold py-2 px-4 rounded transition-all duration-200 cursor-pointer font-mono text-sm">
+                                        <div class="flex items-center justify-center gap-2">    
+                                            <span>{"PLAY"}</span>
+                                            <span class="text-xs group-hover:translate-x-1 transition-transform duration-200">{"→"}</span>
+                                        </div>
+                                    </Link<Route>>
+                                }
+                            </div>
+                        }
                     </div>
                 </div>
             </div>
 
+            // automap
+            if *automap_open {
+                if let Some(id) = &props.id {
+                    <Automap
+                        src={format!("/static/doom_projects/automap/{}.json", id)}
+                        title={props.title.clone()}
+                        on_close={toggle_automap.clone()}
+                    />
+                }
+            }
+
             // lightbox
             if *lightbox_open {
-                <div 
-                    class="fixed inset-0 backdrop-blur-lg bg-black/60 flex items-center justify-center z-50"
-                    onclick={close_lightbox.clone()}
-                >
-                    // close button (top-right)
-                        <button
-                            onclick={close_lightbox.clone()}
-                            class="absolute top-4 right-4 text-white hover:text-red-600 text-4xl font-bold transition-colors duration-200 cursor-pointer z-20 bg-black/50 rounded-full w-12 h-12 flex items-center justify-center leading-none"
-                        >
-                            // svg 'x'
-                            <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M6 18L18 6M6 6l12 12"></path>
-                            </svg>
-                        </button>
-
-                    // image container
-                    <div 
-                        class="relative w-full h-full flex items-center justify-center p-4">
-                        
-                        // main image display
-                        <img
-                            src={current_image_src}
-                            alt={format!("{} - Image {}", props.title, *current_image_index + 1)}
-                            class="max-w-full max-h-full object-contain"
-                            style="image-rendering: pixelated;"
-                            onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
-                        />
-
-                        // navigation buttons (only show if there are multiple images)
-                        if all_images.len() > 1 {
-                            <>
-                                // left arrow
-                                <button
-                                    onclick={prev_image}
-                                    class="absolute left-4 top-1/2 transform -translate-y-1/2 bg-black/70 hover:bg-black/90 text-white rounded-full p-3 transition-all duration-200 cursor-pointer z-10"
-                                >
-                                    <svg class="w-8 h-8" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7"></path>
-                                    </svg>
-                                </button>
-
-                                // right arrow  
-                                <button
-                                    onclick={next_image}
-                                    class="absolute right-4 top-1/2 transform -translate-y-1/2 bg-black/70 hover:bg-black/90 text-white rounded-full p-3 transition-all duration-200 cursor-pointer z-10"
-                                >
-                                    <svg class="w-8 h-8" fill="none" stroke="currentColor" viewBox="0 0 24 24">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 5l7 7-7 7"></path>
-                                    </svg>
-                                </button>
-
-                            </>
-                        }
-
-                        // image counter and title
-                        <div class="absolute bottom-6 left-1/2 transform -translate-x-1/2 bg-black/70 text-white px-4 py-2 rounded-lg">
-                            <div class="text-center font-mono">
-                                <div class="text-lg font-bold text-red-600">{&props.title}</div>
-                                <div class="text-sm">{format!("{} / {}", *current_image_index + 1, all_images.len())}</div>
-                            </div>
-                        </div>
-                    </div>
-                </div>
+                <Lightbox
+                    items={gallery}
+                    start={*start_image}
+                    title={props.title.clone()}
+                    pixelated={true}
+                    on_close={close_lightbox}
+                />
             }
         </> // end fragment
     }
@@ -0,0 +1,140 @@
+// components/doom_text.rs
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gloo_net::http::Request;
+use yew::prelude::*;
+
+use crate::doom::font::{FontMetrics, TextColor};
+
+// atlases written by `cargo run --bin wad -- fonts doom_fonts.json`
+const FONTS_PATH: &str = "/static/fonts";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DoomFont {
+    Small,      // STCFN, the hud/message font
+    #[default]
+    Big,        // a zdoom BIGFONT lump, menu titles
+}
+
+impl DoomFont {
+    fn name(self) -> &'static str {
+        match self {
+            DoomFont::Small => "small",
+            DoomFont::Big => "big",
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DoomTextProps {
+    pub text: String,
+    #[prop_or_default]
+    pub font: DoomFont,
+    #[prop_or_default]
+    pub color: TextColor,
+    #[prop_or(2)]
+    pub scale: u32,         // whole pixel multiples keep the glyphs crisp
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+thread_local! {
+    // metrics are shared by every DoomText on the page
+    static METRICS: RefCell<HashMap<DoomFont, Rc<FontMetrics>>> = RefCell::new(HashMap::new());
+}
+
+async fn fetch_metrics(font: DoomFont) -> Result<Rc<FontMetrics>, String> {
+    if let Some(metrics) = METRICS.with(|cache| cache.borrow().get(&font).cloned()) {
+        return Ok(metrics);
+    }
+    let path = format!("{}/{}.json", FONTS_PATH, font.name());
+    let response = Request::get(&path).send().await.map_err(|e| format!("Network error: {}", e))?;
+    if !response.ok() {
+        return Err(format!("HTTP {} error", response.status()));
+    }
+    let metrics = Rc::new(response.json::<FontMetrics>().await.map_err(|e| e.to_string())?);
+    METRICS.with(|cache| cache.borrow_mut().insert(font, metrics.clone()));
+    Ok(metrics)
+}
+
+#[derive(Clone, PartialEq)]
+enum FontState {
+    Loading,
+    Ready { font: DoomFont, metrics: Rc<FontMetrics>, scale: u32 },
+    Failed,
+}
+
+// a string drawn with doom's bitmap fonts; the real text stays in the page for screen readers, search and copy
+#[function_component(DoomText)]
+pub fn doom_text(props: &DoomTextProps) -> Html {
+    let state = use_state(|| FontState::Loading);
+
+    {
+        let state = state.clone();
+        use_effect_with((props.font, props.scale), move |&(font, scale)| {
+            wasm_bindgen_futures::spawn_local(async move {
+                // the small font is about half as tall, so it stands in for a missing big font at double size
+                let loaded = match fetch_metrics(font).await {
+                    Ok(metrics) => Ok((font, metrics, scale)),
+                    Err(err) if font == DoomFont::Big => {
+                        fetch_metrics(DoomFont::Small).await.map(|metrics| (DoomFont::Small, metrics, scale * 2)).map_err(|_| err)
+                    }
+                    Err(err) => Err(err),
+                };
+                match loaded {
+                    Ok((font, metrics, scale)) => state.set(FontState::Ready { font, metrics, scale }),
+                    Err(err) => {
+                        tracing::warn!("Doom font {} unavailable, using plain text: {}", font.name(), err);
+                        state.set(FontState::Failed);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let FontState::Ready { font, metrics, scale } = &*state else {
+        // plain text until (or instead of) the font, in the same colour
+        let color = match props.color {
+            TextColor::Red => "text-red-600",
+            TextColor::Gold => "text-yellow-400",
+            TextColor::Gray => "text-gray-300",
+        };
+        return html! {
+            <span class={classes!("font-mono", "font-bold", "uppercase", color, props.class.clone())}>{&props.text}</span>
+        };
+    };
+
+    let scale = *scale;
+    let atlas = format!("{}/{}_{}.png", FONTS_PATH, font.name(), props.color.name());
+    let (placements, width) = metrics.layout(&props.text);
+    let height = metrics.height * scale;
+
+    html! {
+        <span class={classes!("relative", "inline-block", "align-middle", props.class.clone())}>
+            <span class="sr-only">{&props.text}</span>
+            <span
+                aria-hidden="true"
+                class="relative block"
+                style={format!("width: {}px; height: {}px;", width * scale, height)}
+            >
+                { for placements.iter().filter_map(|placement| {
+                    let glyph = placement.glyph?;
+                    Some(html! {
+                        <span
+                            class="absolute top-0"
+                            style={format!(
+                                "left: {}px; width: {}px; height: {}px; background-image: url('{}'); background-position: -{}px 0; \
+                                 background-size: {}px {}px; background-repeat: no-repeat; image-rendering: pixelated;",
+                                placement.x * scale, glyph.width * scale, height, atlas, glyph.x * scale,
+                                metrics.atlas_width * scale, height,
+                            )}
+                        ></span>
+                    })
+                })}
+            </span>
+        </span>
+    }
+}
@@ -1,10 +1,11 @@
 // components/heading.rs
 use yew::prelude::*;
 
+use crate::components::doom_text::{DoomFont, DoomText};
+
 #[derive(Properties, PartialEq)]
 pub struct HeadingProps {
-    pub src: String,
-    pub alt: String, 
+    pub text: String,
     pub sub_heading: Option<String>,
 }
 
@@ -12,15 +13,13 @@ pub struct HeadingProps {
 pub fn heading(props: &HeadingProps) -> Html {
     html! {
         <div class="text-center mb-12">
-            <img 
-                src={props.src.clone()}
-                alt={props.alt.clone()}
-                class="w-auto h-auto mx-auto"
-            />
+            <h1 class="mb-2">
+                <DoomText text={props.text.clone()} font={DoomFont::Big} scale={3} />
+            </h1>
             <img 
                 src="/static/common/DIVIDER_1.png" 
-                alt="Divider"
-                class="w-auto h-auto mx-auto mb-4 -mt-6"
+                alt=""
+                class="w-auto h-auto mx-auto mb-4"
             />
             <p class="text-gray-300 text-lg max-w-2xl mx-auto leading-relaxed">
                 {Some(props.sub_heading.clone())}
@@ -28,4 +27,3 @@ pub fn heading(props: &HeadingProps) -> Html {
         </div>
     }
 }
-                    
\ No newline at end of file
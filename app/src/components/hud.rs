@@ -60,7 +60,7 @@ fn use_mouse_grid() -> (i32, i32) {         // return tuple of signed integers (
         });
     }
     
-    (*grid_pos).clone() // dereference state handle and clone current value to return it
+    *grid_pos // dereference state handle to copy current value and return it
 }
 
 // get avatar image based on grid position
@@ -0,0 +1,516 @@
+// components/lightbox.rs
+use gloo_events::{EventListener, EventListenerOptions};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, PointerEvent, WheelEvent};
+use yew::prelude::*;
+
+use crate::media::{Media, MediaKind};
+use crate::components::responsive_image::{preload, ImagesContext, Loading, ResponsiveImage};
+
+const ZOOM_STEP: f64 = 1.25;
+const DOUBLE_CLICK_ZOOM: f64 = 2.5;
+const MAX_ZOOM: f64 = 6.0;
+const SWIPE_DISTANCE: f64 = 50.0;   // css pixels of horizontal travel that count as a swipe
+const FOCUSABLE: &str = "button, a[href], video, iframe, [tabindex]:not([tabindex='-1'])";
+const YOUTUBE_EMBED: &str = "https://www.youtube-nocookie.com/embed";
+
+#[derive(Properties, PartialEq)]
+pub struct LightboxProps {
+    pub items: Vec<Media>,
+    pub title: String,
+    pub on_close: Callback<()>,
+    #[prop_or_default]
+    pub start: usize,               // item shown first
+    #[prop_or_default]
+    pub alt: Option<String>,        // numbered per item without its own alt text, the title otherwise
+    #[prop_or(AttrValue::from("100vw"))]
+    pub sizes: AttrValue,
+    #[prop_or_default]
+    pub pixelated: bool,            // nearest neighbour scaling, for doom screenshots
+    #[prop_or_default]
+    pub children: Html,             // details shown under the gallery
+}
+
+// offset of the image from the stage centre in css pixels, then scale around its own centre
+#[derive(Clone, Copy, PartialEq)]
+struct Zoom {
+    scale: f64,
+    x: f64,
+    y: f64,
+}
+
+impl Zoom {
+    const NONE: Zoom = Zoom { scale: 1.0, x: 0.0, y: 0.0 };
+
+    // scale by factor, keeping the point (px, py) from the stage centre in place
+    fn around(self, factor: f64, px: f64, py: f64) -> Zoom {
+        let scale = (self.scale * factor).clamp(1.0, MAX_ZOOM);
+        if scale <= 1.0 {
+            return Zoom::NONE;
+        }
+        let applied = scale / self.scale;
+        Zoom { scale, x: px - (px - self.x) * applied, y: py - (py - self.y) * applied }
+    }
+
+    fn zoomed(self) -> bool {
+        self.scale > 1.0
+    }
+}
+
+// active pointers on the stage, for panning, pinching and swiping
+#[derive(Default)]
+struct Gesture {
+    pointers: Vec<(i32, f64, f64)>,     // id and client position
+    swipe_start: Option<(f64, f64)>,
+    pinch_distance: Option<f64>,
+}
+
+impl Gesture {
+    fn pinch(&self) -> Option<(f64, f64, f64)> {
+        let [(_, ax, ay), (_, bx, by)] = self.pointers[..] else { return None };
+        Some(((ax - bx).hypot(ay - by), (ax + bx) / 2.0, (ay + by) / 2.0))
+    }
+}
+
+fn wrap(index: usize, step: isize, len: usize) -> usize {
+    (index as isize + step).rem_euclid(len.max(1) as isize) as usize
+}
+
+// full screen gallery: arrows/swipe to browse, wheel/pinch/double click to zoom images, drag to pan once zoomed
+#[function_component(Lightbox)]
+pub fn lightbox(props: &LightboxProps) -> Html {
+    let count = props.items.len();
+    let index = use_state(|| props.start.min(count.saturating_sub(1)));
+    let embed_loaded = use_state(|| false);     // youtube only connects after a click
+    let zoom = use_mut_ref(|| Zoom::NONE);
+    let gesture = use_mut_ref(Gesture::default);
+    let force_update = use_force_update();
+    let dialog_ref = use_node_ref();
+    let stage_ref = use_node_ref();
+    let manifest = use_context::<ImagesContext>().unwrap_or_default();
+
+    // focus moves into the dialog and back to whatever opened it on close
+    {
+        let dialog_ref = dialog_ref.clone();
+        use_effect_with((), move |_| {
+            let opener = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.active_element())
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+            if let Some(dialog) = dialog_ref.cast::<HtmlElement>() {
+                let _ = dialog.focus();
+            }
+            move || {
+                if let Some(opener) = opener {
+                    let _ = opener.focus();
+                }
+            }
+        });
+    }
+
+    // every item starts unzoomed and unembedded, and its neighbours' stills get fetched in the background
+    {
+        let zoom = zoom.clone();
+        let embed_loaded = embed_loaded.clone();
+        let items = props.items.clone();
+        let sizes = props.sizes.clone();
+        use_effect_with(*index, move |&index| {
+            *zoom.borrow_mut() = Zoom::NONE;
+            embed_loaded.set(false);
+            if items.len() > 1 {
+                for step in [-1, 1] {
+                    if let Some(still) = items[wrap(index, step, items.len())].still() {
+                        preload(&manifest, still, &sizes);
+                    }
+                }
+            }
+            || ()
+        });
+    }
+
+    // videos and embeds handle their own pointer input, only images zoom and swipe
+    let zoomable = props.items.get(*index).is_some_and(Media::is_image);
+
+    let go = {
+        let index = index.clone();
+        Callback::from(move |step: isize| index.set(wrap(*index, step, count)))
+    };
+
+    // point relative to the stage centre
+    let stage_point = {
+        let stage_ref = stage_ref.clone();
+        move |client_x: f64, client_y: f64| -> (f64, f64) {
+            match stage_ref.cast::<Element>() {
+                Some(stage) => {
+                    let rect = stage.get_bounding_client_rect();
+                    (client_x - rect.left() - rect.width() / 2.0, client_y - rect.top() - rect.height() / 2.0)
+                }
+                None => (0.0, 0.0),
+            }
+        }
+    };
+
+    let zoom_by = {
+        let zoom = zoom.clone();
+        let force_update = force_update.clone();
+        Callback::from(move |(factor, px, py): (f64, f64, f64)| {
+            let current = *zoom.borrow();
+            *zoom.borrow_mut() = current.around(factor, px, py);
+            force_update.force_update();
+        })
+    };
+
+    // wheel zoom around the cursor (manual listener so it can prevent page scroll)
+    {
+        let stage_ref = stage_ref.clone();
+        let zoom_by = zoom_by.clone();
+        let stage_point = stage_point.clone();
+        use_effect_with(zoomable, move |&zoomable| {
+            let listener = stage_ref.cast::<Element>().filter(|_| zoomable).map(|stage| {
+                EventListener::new_with_options(&stage, "wheel", EventListenerOptions::enable_prevent_default(), move |event| {
+                    let Some(event) = event.dyn_ref::<WheelEvent>() else { return };
+                    event.prevent_default();
+                    let (px, py) = stage_point(event.client_x() as f64, event.client_y() as f64);
+                    let factor = if event.delta_y() < 0.0 { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+                    zoom_by.emit((factor, px, py));
+                })
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_double_click = {
+        let zoom = zoom.clone();
+        let zoom_by = zoom_by.clone();
+        let stage_point = stage_point.clone();
+        Callback::from(move |e: MouseEvent| {
+            if !zoomable {
+                return;
+            }
+            let (px, py) = stage_point(e.client_x() as f64, e.client_y() as f64);
+            let current = *zoom.borrow();
+            let factor = if current.zoomed() { 1.0 / current.scale } else { DOUBLE_CLICK_ZOOM };
+            zoom_by.emit((factor, px, py));
+        })
+    };
+
+    let on_pointer_down = {
+        let gesture = gesture.clone();
+        Callback::from(move |e: PointerEvent| {
+            if !zoomable {
+                return;
+            }
+            if let Some(target) = e.target().and_then(|t| t.dyn_into::<Element>().ok()) {
+                let _ = target.set_pointer_capture(e.pointer_id());
+            }
+            let mut gesture = gesture.borrow_mut();
+            let (x, y) = (e.client_x() as f64, e.client_y() as f64);
+            gesture.pointers.retain(|&(id, _, _)| id != e.pointer_id());
+            gesture.pointers.push((e.pointer_id(), x, y));
+            // a second finger turns the gesture into a pinch
+            gesture.swipe_start = (gesture.pointers.len() == 1).then_some((x, y));
+            gesture.pinch_distance = gesture.pinch().map(|(distance, _, _)| distance);
+        })
+    };
+
+    let on_pointer_move = {
+        let gesture = gesture.clone();
+        let zoom = zoom.clone();
+        let zoom_by = zoom_by.clone();
+        let force_update = force_update.clone();
+        let stage_point = stage_point.clone();
+        Callback::from(move |e: PointerEvent| {
+            let mut gesture = gesture.borrow_mut();
+            let Some(pointer) = gesture.pointers.iter_mut().find(|(id, _, _)| *id == e.pointer_id()) else { return };
+            let (x, y) = (e.client_x() as f64, e.client_y() as f64);
+            let (last_x, last_y) = (pointer.1, pointer.2);
+            *pointer = (pointer.0, x, y);
+
+            if let Some((distance, mid_x, mid_y)) = gesture.pinch() {
+                if let Some(last) = gesture.pinch_distance.filter(|&last| last > 0.0) {
+                    let (px, py) = stage_point(mid_x, mid_y);
+                    zoom_by.emit((distance / last, px, py));
+                }
+                gesture.pinch_distance = Some(distance);
+                return;
+            }
+            let current = *zoom.borrow();
+            if current.zoomed() {
+                *zoom.borrow_mut() = Zoom { x: current.x + x - last_x, y: current.y + y - last_y, ..current };
+                force_update.force_update();
+            }
+        })
+    };
+
+    let on_pointer_up = {
+        let gesture = gesture.clone();
+        let zoom = zoom.clone();
+        let go = go.clone();
+        Callback::from(move |e: PointerEvent| {
+            let mut gesture = gesture.borrow_mut();
+            // horizontal flicks browse, but only while unzoomed (dragging pans otherwise)
+            if let (Some((start_x, start_y)), false) = (gesture.swipe_start, zoom.borrow().zoomed()) {
+                let (dx, dy) = (e.client_x() as f64 - start_x, e.client_y() as f64 - start_y);
+                if dx.abs() > SWIPE_DISTANCE && dx.abs() > dy.abs() {
+                    go.emit(if dx < 0.0 { 1 } else { -1 });
+                }
+            }
+            gesture.pointers.retain(|&(id, _, _)| id != e.pointer_id());
+            gesture.swipe_start = None;
+            gesture.pinch_distance = None;
+        })
+    };
+
+    let close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+
+    let on_key_down = {
+        let go = go.clone();
+        let index = index.clone();
+        let zoom_by = zoom_by.clone();
+        let zoom = zoom.clone();
+        let on_close = props.on_close.clone();
+        let dialog_ref = dialog_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            // arrows seek inside a focused video rather than browsing
+            let in_player = e.target().and_then(|t| t.dyn_into::<Element>().ok()).is_some_and(|t| t.tag_name() == "VIDEO");
+            match e.key().as_str() {
+                "Escape" => on_close.emit(()),
+                "ArrowLeft" if !in_player => go.emit(-1),
+                "ArrowRight" if !in_player => go.emit(1),
+                "Home" => index.set(0),
+                "End" => index.set(count.saturating_sub(1)),
+                "+" | "=" if zoomable => zoom_by.emit((ZOOM_STEP, 0.0, 0.0)),
+                "-" | "_" if zoomable => zoom_by.emit((1.0 / ZOOM_STEP, 0.0, 0.0)),
+                "0" if zoomable => {
+                    let current = *zoom.borrow();
+                    zoom_by.emit((1.0 / current.scale, 0.0, 0.0));
+                }
+                // keep tab focus cycling inside the dialog
+                "Tab" => {
+                    let Some(dialog) = dialog_ref.cast::<Element>() else { return };
+                    let Ok(nodes) = dialog.query_selector_all(FOCUSABLE) else { return };
+                    let focusable: Vec<HtmlElement> =
+                        (0..nodes.length()).filter_map(|i| nodes.get(i)?.dyn_into::<HtmlElement>().ok()).collect();
+                    let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else { return };
+                    let active = web_sys::window().and_then(|window| window.document()).and_then(|document| document.active_element());
+                    let at = |element: &HtmlElement| active.as_ref().is_some_and(|active| active == element.unchecked_ref::<Element>());
+                    let outside = !focusable.iter().any(at);
+                    if e.shift_key() && (at(first) || outside) {
+                        let _ = last.focus();
+                    } else if !e.shift_key() && (at(last) || outside) {
+                        let _ = first.focus();
+                    } else {
+                        return;
+                    }
+                }
+                _ => return,
+            }
+            e.prevent_default();
+        })
+    };
+
+    let current = *zoom.borrow();
+    let alt = |i: usize| match props.items.get(i).and_then(|item| item.alt.clone()) {
+        Some(alt) => alt,
+        None => format!("{} - Image {}", props.alt.as_ref().unwrap_or(&props.title), i + 1),
+    };
+    let pixelated = props.pixelated.then(|| "image-rendering: pixelated;".to_string());
+    let image_style = format!(
+        "transform: translate({}px, {}px) scale({}); {}",
+        current.x,
+        current.y,
+        current.scale,
+        pixelated.as_deref().unwrap_or("")
+    );
+    let has_details = props.children != Html::default();
+    let nav_class = "absolute top-1/2 -translate-y-1/2 bg-black/70 hover:bg-black/90 text-white rounded-full p-3 transition-all duration-200 cursor-pointer z-10";
+
+    html! {
+        <div class="fixed inset-0 backdrop-blur-lg bg-black/60 flex items-center justify-center p-4 z-50" onclick={close.clone()}>
+            <div
+                ref={dialog_ref}
+                role="dialog"
+                aria-modal="true"
+                aria-label={props.title.clone()}
+                tabindex="-1"
+                onkeydown={on_key_down}
+                onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                class={classes!(
+                    "relative", "w-full", "flex", "flex-col", "bg-[#1a1a1a]", "border-3", "border-red-600", "rounded-lg", "outline-none",
+                    if has_details { "max-w-4xl max-h-[95vh] overflow-y-auto" } else { "max-w-6xl h-full max-h-[95vh]" },
+                )}
+            >
+                // header
+                <div class="flex justify-between items-center px-6 py-3 border-b border-gray-400/50">
+                    <h2 class="text-2xl font-bold text-red-600 font-mono">{&props.title}</h2>
+                    <button
+                        onclick={close}
+                        aria-label="Close"
+                        class="text-gray-400 hover:text-red-600 text-2xl font-bold transition-colors duration-200 cursor-pointer">
+                        {"×"}
+                    </button>
+                </div>
+
+                // stage
+                <div
+                    ref={stage_ref}
+                    class={classes!(
+                        "relative", "overflow-hidden", "bg-[#2b2b2b]", "flex", "items-center", "justify-center", "select-none",
+                        if has_details { "h-[60vh] shrink-0" } else { "flex-1 min-h-0" },
+                        match (zoomable, current.zoomed()) {
+                            (false, _) => "",
+                            (true, true) => "cursor-grab active:cursor-grabbing",
+                            (true, false) => "cursor-zoom-in",
+                        },
+                    )}
+                    style="touch-action: none;"
+                    ondblclick={on_double_click}
+                    onpointerdown={on_pointer_down}
+                    onpointermove={on_pointer_move}
+                    onpointerup={on_pointer_up.clone()}
+                    onpointercancel={on_pointer_up}
+                    ondragstart={Callback::from(|e: DragEvent| e.prevent_default())}
+                >
+                    {match props.items.get(*index).map(|item| (&item.kind, item)) {
+                        Some((MediaKind::Image { src }, _)) => html! {
+                            <ResponsiveImage
+                                src={src.clone()}
+                                alt={alt(*index)}
+                                sizes={props.sizes.clone()}
+                                class="max-w-full max-h-full object-contain"
+                                style={image_style}
+                            />
+                        },
+                        // keyed so moving between videos restarts instead of carrying playback over
+                        Some((MediaKind::Video { src }, item)) => html! {
+                            <video
+                                key={*index}
+                                src={src.clone()}
+                                poster={item.poster.clone()}
+                                aria-label={alt(*index)}
+                                controls=true
+                                playsinline=true
+                                preload="metadata"
+                                class="max-w-full max-h-full"
+                                style={pixelated.clone()}
+                            />
+                        },
+                        Some((MediaKind::YouTube { id }, _)) if *embed_loaded => html! {
+                            <iframe
+                                key={*index}
+                                src={format!("{}/{}?autoplay=1&rel=0", YOUTUBE_EMBED, id)}
+                                title={alt(*index)}
+                                allow="autoplay; encrypted-media; picture-in-picture; fullscreen"
+                                referrerpolicy="strict-origin-when-cross-origin"
+                                class="w-full h-full max-w-[min(100%,calc(60vh*16/9))] aspect-video"
+                            />
+                        },
+                        // nothing is requested from youtube until the visitor asks for it
+                        Some((MediaKind::YouTube { .. }, item)) => html! {
+                            <button
+                                onclick={{ let embed_loaded = embed_loaded.clone(); Callback::from(move |_| embed_loaded.set(true)) }}
+                                aria-label={format!("Play {} (loads YouTube)", alt(*index))}
+                                class="relative w-full h-full flex items-center justify-center cursor-pointer group"
+                            >
+                                if let Some(poster) = &item.poster {
+                                    <ResponsiveImage src={poster.clone()} alt="" sizes={props.sizes.clone()} class="absolute inset-0 w-full h-full object-contain opacity-60" style={pixelated.clone()} />
+                                }
+                                <span class="relative flex flex-col items-center gap-2 font-mono">
+                                    <span class="bg-red-600 group-hover:bg-red-500 text-white text-3xl rounded-full w-16 h-16 flex items-center justify-center">{"▶"}</span>
+                                    <span class="bg-black/70 text-gray-300 text-xs px-2 py-1 rounded">{"PLAYS FROM YOUTUBE (NO COOKIES)"}</span>
+                                </span>
+                            </button>
+                        },
+                        None => html! {},
+                    }}
+
+                    if count > 1 {
+                        <button
+                            onclick={{ let go = go.clone(); Callback::from(move |_| go.emit(-1)) }}
+                            aria-label="Previous image"
+                            class={classes!(nav_class, "left-2")}
+                        >
+                            <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15 19l-7-7 7-7"></path>
+                            </svg>
+                        </button>
+                        <button
+                            onclick={{ let go = go.clone(); Callback::from(move |_| go.emit(1)) }}
+                            aria-label="Next image"
+                            class={classes!(nav_class, "right-2")}
+                        >
+                            <svg class="w-6 h-6" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9 5l7 7-7 7"></path>
+                            </svg>
+                        </button>
+                    }
+
+                    // counter, announced as items change
+                    <div class="absolute bottom-2 right-2 bg-black/70 text-white px-2 py-1 rounded text-sm font-mono" aria-live="polite">
+                        {format!("{} / {}", *index + 1, count)}
+                    </div>
+                </div>
+
+                // caption and credit
+                if let Some(item) = props.items.get(*index).filter(|item| item.caption.is_some() || item.credit.is_some()) {
+                    <div class="flex flex-wrap items-baseline gap-x-4 px-6 pt-2 font-mono text-sm">
+                        if let Some(caption) = &item.caption {
+                            <p class="text-gray-300">{caption}</p>
+                        }
+                        if let Some(credit) = &item.credit {
+                            <p class="text-gray-500 text-xs ml-auto">{format!("CREDIT: {}", credit)}</p>
+                        }
+                    </div>
+                }
+
+                // thumbnail strip
+                if count > 1 {
+                    <div class="flex gap-2 p-2 overflow-x-auto bg-[#1a1a1a] shrink-0">
+                        { for props.items.iter().enumerate().map(|(i, item)| {
+                            let selected = i == *index;
+                            let index = index.clone();
+                            html! {
+                                <button
+                                    onclick={Callback::from(move |_| index.set(i))}
+                                    aria-label={format!("Show item {}", i + 1)}
+                                    aria-current={selected.then_some("true")}
+                                    class={classes!(
+                                        "relative", "shrink-0", "w-20", "h-12", "rounded", "overflow-hidden", "border-2", "cursor-pointer", "bg-[#2b2b2b]",
+                                        if selected { "border-red-600" } else { "border-transparent opacity-60 hover:opacity-100" },
+                                    )}
+                                >
+                                    if let Some(still) = item.still() {
+                                        <ResponsiveImage
+                                            src={still.to_string()}
+                                            alt=""
+                                            sizes="80px"
+                                            loading={Loading::Lazy}
+                                            class="w-full h-full object-cover"
+                                            style={pixelated.clone()}
+                                        />
+                                    }
+                                    if !item.is_image() {
+                                        <span class="absolute inset-0 flex items-center justify-center text-white text-lg bg-black/40">{"▶"}</span>
+                                    }
+                                </button>
+                            }
+                        })}
+                    </div>
+                }
+
+                <p class="px-6 pt-1 text-gray-500 font-mono text-xs">
+                    {"←/→ OR SWIPE: BROWSE · WHEEL/PINCH/DOUBLE CLICK: ZOOM · DRAG: PAN · ESC: CLOSE"}
+                </p>
+
+                if has_details {
+                    <div class="p-6 space-y-6">
+                        {props.children.clone()}
+                    </div>
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,56 @@
+// components/loading_bar.rs
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct LoadingBarProps {
+    pub label: String,
+    #[prop_or_default]
+    pub progress: Option<f64>,          // 0.0 - 1.0, none when the total size is unknown
+    #[prop_or_default]
+    pub detail: Option<String>,         // e.g. "1.2 / 3.4 MB"
+}
+
+// format byte counts for progress labels
+pub fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= MB {
+        format!("{:.1} MB", bytes as f64 / MB)
+    } else {
+        format!("{} KB", bytes / 1024)
+    }
+}
+
+#[function_component(LoadingBar)]
+pub fn loading_bar(props: &LoadingBarProps) -> Html {
+    let percent = props.progress.map(|p| (p.clamp(0.0, 1.0) * 100.0).round());
+
+    html! {
+        <div class="w-64 font-mono text-center" role="progressbar"
+            aria-label={props.label.clone()}
+            aria-valuemin="0"
+            aria-valuemax="100"
+            aria-valuenow={percent.map(|p| p.to_string())}
+        >
+            <div class="text-red-600 text-lg mb-2">{&props.label}</div>
+
+            // doom style bar: dark trough with red fill
+            <div class="h-4 bg-[#1a1a1a] border-2 border-[#0b0b0a] outline-2 outline-red-600 overflow-hidden">
+                if let Some(percent) = percent {
+                    <div class="h-full bg-red-600 transition-all duration-200" style={format!("width: {}%;", percent)}></div>
+                } else {
+                    // unknown total: pulsing full bar
+                    <div class="h-full w-full bg-red-600/60 animate-pulse"></div>
+                }
+            </div>
+
+            <div class="text-gray-300 text-sm mt-2">
+                {match (percent, &props.detail) {
+                    (Some(percent), Some(detail)) => format!("{}% - {}", percent, detail),
+                    (Some(percent), None) => format!("{}%", percent),
+                    (None, Some(detail)) => detail.clone(),
+                    (None, None) => String::new(),
+                }}
+            </div>
+        </div>
+    }
+}
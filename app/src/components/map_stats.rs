@@ -0,0 +1,82 @@
+// components/map_stats.rs
+use yew::prelude::*;
+
+use crate::doom::stats::{WadStats, SKILLS};
+
+#[derive(Properties, PartialEq)]
+pub struct MapStatsProps {
+    pub stats: WadStats,
+}
+
+// intermission style numbers for a released wad, one map and skill at a time
+#[function_component(MapStats)]
+pub fn map_stats(props: &MapStatsProps) -> Html {
+    let map_index = use_state(|| 0usize);
+    let skill = use_state(|| 3usize);   // UV, the skill maps are balanced for
+
+    let Some(map) = props.stats.maps.get(*map_index).or(props.stats.maps.first()) else {
+        return html! {};
+    };
+    let counts = map.skills[*skill];
+
+    let row = |label: &str, value: String| html! {
+        <div class="flex justify-between">
+            <span class="text-gray-400">{label}</span>
+            <span class="text-red-600 font-bold">{value}</span>
+        </div>
+    };
+
+    html! {
+        <div class="mb-4 p-3 bg-[#2b2b2b] border-2 border-[#0b0b0a] font-mono text-xs">
+            // map selector, only for multi-map wads
+            if props.stats.maps.len() > 1 {
+                <div class="flex flex-wrap gap-1 mb-2">
+                    { for props.stats.maps.iter().enumerate().map(|(index, entry)| {
+                        let map_index = map_index.clone();
+                        let selected = index == *map_index;
+                        html! {
+                            <button
+                                onclick={Callback::from(move |_| map_index.set(index))}
+                                aria-pressed={selected.to_string()}
+                                class={classes!(
+                                    "px-2", "py-0.5", "border", "border-red-600", "cursor-pointer", "transition-colors", "duration-200",
+                                    if selected { "bg-red-600 text-white" } else { "text-red-600 hover:bg-red-600/30" },
+                                )}
+                            >
+                                {&entry.name}
+                            </button>
+                        }
+                    })}
+                </div>
+            } else {
+                <div class="text-red-600 font-bold mb-2">{&map.name}</div>
+            }
+
+            // skill selector
+            <div class="flex gap-1 mb-2" role="group" aria-label="Skill level">
+                { for SKILLS.iter().enumerate().map(|(index, name)| {
+                    let skill = skill.clone();
+                    let selected = index == *skill;
+                    html! {
+                        <button
+                            onclick={Callback::from(move |_| skill.set(index))}
+                            aria-pressed={selected.to_string()}
+                            class={classes!(
+                                "flex-1", "py-0.5", "border", "border-gray-500", "cursor-pointer", "transition-colors", "duration-200",
+                                if selected { "bg-gray-500 text-white" } else { "text-gray-400 hover:bg-gray-600" },
+                            )}
+                        >
+                            {*name}
+                        </button>
+                    }
+                })}
+            </div>
+
+            { row("MONSTERS", counts.monsters.to_string()) }
+            { row("ITEMS", counts.items.to_string()) }
+            { row("SECRETS", map.secrets.to_string()) }
+            { row("SIZE", format!("{} x {}", map.width, map.height)) }
+            { row("LINES", map.lines.to_string()) }
+        </div>
+    }
+}
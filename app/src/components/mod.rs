@@ -12,4 +12,13 @@ pub mod education;
 pub mod experience;
 pub mod technologies;
 pub mod model_viewer;
-pub mod navbar_toggle;
\ No newline at end of file
+pub mod navbar_toggle;
+pub mod loading_bar;
+pub mod turntable;
+pub mod map_stats;
+pub mod automap;
+pub mod doom_text;
+pub mod responsive_image;
+pub mod lightbox;
+pub mod volume_chart;
+pub mod attachment_picker;
@@ -1,93 +1,391 @@
 // components/model_viewer.rs
+use std::collections::HashMap;
+
 use yew::prelude::*;
-use web_sys::{HtmlCanvasElement, console};
-use wasm_bindgen::prelude::*;
+use web_sys::{HtmlCanvasElement, PointerEvent, WheelEvent};
+use gloo_events::{EventListener, EventListenerOptions};
 use wasm_bindgen::JsCast;
 
-// JavaScript bindings for Three.js
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_name = "initThreeJsScene")]
-    fn init_threejs_scene(canvas: &HtmlCanvasElement, obj_path: &str);
-}
+use crate::renderer::camera::CameraPose;
+use crate::renderer::metadata::{self, Hotspot};
+use crate::renderer::scene::{LoadState, Viewer, ViewerOptions};
+use crate::renderer::support::{self, FallbackReason};
+use crate::components::loading_bar::{LoadingBar, format_bytes};
+use crate::components::turntable::Turntable;
 
 #[derive(Properties, PartialEq)]
 pub struct ModelViewerProps {
     #[prop_or_default]
     pub obj_path: String,
+    #[prop_or_else(|| "3D model".to_string())]
+    pub alt: String,                    // accessible name for the pre-rendered fallback
     #[prop_or(400)]
     pub width: u32,
     #[prop_or(300)]
     pub height: u32,
+    #[prop_or(true)]
+    pub auto_rotate: bool,              // slow spin around the y axis
+    #[prop_or(true)]
+    pub interactive: bool,              // drag to orbit, wheel/pinch to zoom
+    #[prop_or_default]
+    pub initial_camera: CameraPose,     // also the pose restored by "reset view"
+    #[prop_or(4.0)]
+    pub min_distance: f32,              // zoom limits (camera distance from the model centre)
+    #[prop_or(25.0)]
+    pub max_distance: f32,
+    #[prop_or_default]
+    pub on_hotspot: Callback<Hotspot>,  // clicked hotspot from the model's metadata file
+}
+
+const CLICK_TOLERANCE: f64 = 5.0;   // pixels a pointer may move and still count as a click
+
+// pixel distance between two pointers, used for pinch zoom
+fn pinch_distance(pointers: &HashMap<i32, (f64, f64)>) -> Option<f64> {
+    let mut points = pointers.values();
+    let (a, b) = (points.next()?, points.next()?);
+    Some(((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt())
 }
 
 #[function_component(ModelViewer)]
 pub fn model_viewer(props: &ModelViewerProps) -> Html {
     let canvas_ref = use_node_ref();
-    let loading = use_state(|| true);
+    let load_state = use_state(|| LoadState::Downloading { loaded: 0, total: None });
+    let attempt = use_state(|| 0u32);     // bumped by the retry button to restart loading
+    let viewer = use_mut_ref(|| None::<Viewer>);
+    let pointers = use_mut_ref(HashMap::<i32, (f64, f64)>::new);     // active pointers by id
+    let press_start = use_mut_ref(|| None::<(f64, f64)>);              // where a potential click started
+    let hovered = use_state(|| None::<(Hotspot, (f64, f64))>);         // hotspot under the mouse + tooltip position
+    let fallback = use_state(support::fallback_reason);                 // show the pre-rendered turntable instead
+    let force_live = use_state(|| false);                               // user asked for the live viewer anyway
+    let live = fallback.is_none() || *force_live;
+
     let obj_path = if props.obj_path.is_empty() {
         "/static/cathedral/cathedral.obj".to_string()
     } else {
         props.obj_path.clone()
     };
 
+    let options = ViewerOptions {
+        auto_rotate: props.auto_rotate,
+        initial_camera: props.initial_camera,
+        min_distance: props.min_distance,
+        max_distance: props.max_distance,
+    };
+
     {
         let canvas_ref = canvas_ref.clone();
-        let obj_path = obj_path.clone();
-        let loading = loading.clone();
-        
-        use_effect_with((), move |_| {
-            let canvas_ref = canvas_ref.clone();
-            let obj_path = obj_path.clone();
-            let loading = loading.clone();
-            
-            // Callback from JS -> Rust
-            let callback = {
-                let loading = loading.clone();
-                wasm_bindgen::closure::Closure::wrap(Box::new(move || {
-                    console::log_1(&"Model loading complete callback triggered!".into());
-                    loading.set(false);
-                }) as Box<dyn Fn()>)
-            };
+        let load_state = load_state.clone();
+        let viewer = viewer.clone();
+        let fallback = fallback.clone();
+        let force_live = force_live.clone();
 
-            let window = web_sys::window().unwrap();
-            let js_callback = callback.as_ref().unchecked_ref::<js_sys::Function>();
-            js_sys::Reflect::set(&window, &"modelLoadComplete".into(), js_callback).unwrap();
+        // camera and zoom limits only apply at start, so changing them restarts the viewer; auto rotation is
+        // left out of the key because the effect below toggles it on the running instance
+        let restart_options = ViewerOptions { auto_rotate: false, ..options };
+        use_effect_with((obj_path.clone(), *attempt, live, restart_options), move |(obj_path, _, live, _)| {
+            // effects run after render, so the canvas is already mounted (unless the turntable is shown)
+            let canvas = canvas_ref.cast::<HtmlCanvasElement>().filter(|_| *live);
+            if let Some(canvas) = canvas {
+                load_state.set(LoadState::Downloading { loaded: 0, total: None });
 
-            
-            // small delay to ensure canvas is mounted
-            let timeout = gloo_timers::callback::Timeout::new(100, move || {
-                if let Some(canvas) = canvas_ref.cast::<HtmlCanvasElement>() {
-                    init_threejs_scene(&canvas, &obj_path);
-                } else {
-                    console::error_1(&"Canvas element not found".into());
+                // renderer -> component progress updates
+                let on_state = {
+                    let load_state = load_state.clone();
+                    Callback::from(move |state: LoadState| {
+                        if state == LoadState::Ready {
+                            tracing::info!("Model loading complete");
+                        }
+                        load_state.set(state);
+                    })
+                };
+
+                match Viewer::start(&canvas, obj_path, options, on_state) {
+                    Ok(instance) => *viewer.borrow_mut() = Some(instance),
+                    Err(err) => {
+                        // context creation failed after all, fall back to the turntable
+                        tracing::error!("Failed to initialise WebGL scene: {:?}", err);
+                        fallback.set(Some(FallbackReason::NoWebGl2));
+                        force_live.set(false);
+                    }
                 }
-            });
-            timeout.forget();
+            }
+
+            // tear down this instance on unmount or before reloading
+            move || drop(viewer.borrow_mut().take())
+        });
+    }
 
-            // keep closure alive for JS to call
-            callback.forget();
+    // auto rotation can be toggled without restarting the renderer
+    {
+        let viewer = viewer.clone();
+        use_effect_with(props.auto_rotate, move |auto_rotate| {
+            if let Some(handle) = viewer.borrow().as_ref() {
+                handle.set_auto_rotate(*auto_rotate);
+            }
             || ()
         });
     }
 
+    // wheel zoom (registered manually because yew's wheel listeners are passive and can't prevent page scroll)
+    {
+        let canvas_ref = canvas_ref.clone();
+        let viewer = viewer.clone();
+        use_effect_with(props.interactive, move |interactive| {
+            let listener = match (*interactive, canvas_ref.cast::<HtmlCanvasElement>()) {
+                (true, Some(canvas)) => Some(EventListener::new_with_options(
+                    &canvas,
+                    "wheel",
+                    EventListenerOptions::enable_prevent_default(),
+                    move |event| {
+                        let Some(event) = event.dyn_ref::<WheelEvent>() else { return };
+                        event.prevent_default();
+                        // normalise line/page based deltas to roughly pixels
+                        let delta = match event.delta_mode() {
+                            WheelEvent::DOM_DELTA_LINE => event.delta_y() * 16.0,
+                            WheelEvent::DOM_DELTA_PAGE => event.delta_y() * 400.0,
+                            _ => event.delta_y(),
+                        };
+                        if let Some(handle) = viewer.borrow().as_ref() {
+                            handle.zoom_by_wheel(delta as f32);
+                        }
+                    },
+                )),
+                _ => None,
+            };
+            move || drop(listener)
+        });
+    }
+
+    let on_pointer_down = {
+        let viewer = viewer.clone();
+        let pointers = pointers.clone();
+        let press_start = press_start.clone();
+        let interactive = props.interactive;
+        Callback::from(move |e: PointerEvent| {
+            // only a single pointer can click, a second finger turns it into a pinch
+            *press_start.borrow_mut() = if pointers.borrow().is_empty() {
+                Some((e.offset_x() as f64, e.offset_y() as f64))
+            } else {
+                None
+            };
+            if !interactive {
+                return;
+            }
+            // keep receiving moves when the pointer leaves the canvas mid-drag
+            if let Some(target) = e.target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) {
+                let _ = target.set_pointer_capture(e.pointer_id());
+            }
+            let mut pointers = pointers.borrow_mut();
+            pointers.insert(e.pointer_id(), (e.client_x() as f64, e.client_y() as f64));
+            if let Some(handle) = viewer.borrow().as_ref() {
+                handle.begin_drag();
+            }
+        })
+    };
+
+    let on_pointer_move = {
+        let viewer = viewer.clone();
+        let pointers = pointers.clone();
+        let press_start = press_start.clone();
+        let hovered = hovered.clone();
+        let (width, height) = (props.width as f64, props.height as f64);
+        Callback::from(move |e: PointerEvent| {
+            let viewer = viewer.borrow();
+            let Some(handle) = viewer.as_ref() else { return };
+            let offset = (e.offset_x() as f64, e.offset_y() as f64);
+
+            // moved too far to be a click
+            let moved = press_start.borrow().is_some_and(|(x, y)| {
+                (offset.0 - x).abs() > CLICK_TOLERANCE || (offset.1 - y).abs() > CLICK_TOLERANCE
+            });
+            if moved {
+                *press_start.borrow_mut() = None;
+            }
+
+            let mut pointers = pointers.borrow_mut();
+            let Some(&(last_x, last_y)) = pointers.get(&e.pointer_id()) else {
+                // plain mouse hover: show the tooltip of the hotspot under the cursor
+                if e.pointer_type() == "mouse" {
+                    match handle.hotspot_at(offset.0, offset.1, width, height) {
+                        Some(hotspot) => hovered.set(Some((hotspot, offset))),
+                        None if hovered.is_some() => hovered.set(None),
+                        None => {}
+                    }
+                }
+                return;
+            };
+            if hovered.is_some() {
+                hovered.set(None);
+            }
+
+            let position = (e.client_x() as f64, e.client_y() as f64);
+            let previous_pinch = pinch_distance(&pointers);
+            pointers.insert(e.pointer_id(), position);
+
+            match pointers.len() {
+                1 => handle.drag((position.0 - last_x) as f32, (position.1 - last_y) as f32),
+                2 => {
+                    // pinch: spreading fingers apart moves the camera closer
+                    if let (Some(before), Some(after)) = (previous_pinch, pinch_distance(&pointers)) {
+                        if after > 0.0 {
+                            handle.zoom((before / after) as f32);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        })
+    };
+
+    let on_pointer_up = {
+        let viewer = viewer.clone();
+        let pointers = pointers.clone();
+        let press_start = press_start.clone();
+        let on_hotspot = props.on_hotspot.clone();
+        let (width, height) = (props.width as f64, props.height as f64);
+        Callback::from(move |e: PointerEvent| {
+            // a press that didn't turn into a drag is a click on whatever hotspot is under it
+            if let Some((x, y)) = press_start.borrow_mut().take() {
+                if e.type_() == "pointerup" {
+                    let hotspot = viewer.borrow().as_ref().and_then(|handle| handle.hotspot_at(x, y, width, height));
+                    if let Some(hotspot) = hotspot {
+                        on_hotspot.emit(hotspot);
+                    }
+                }
+            }
+
+            let mut pointers = pointers.borrow_mut();
+            if pointers.remove(&e.pointer_id()).is_none() {
+                return;
+            }
+            if let Some(handle) = viewer.borrow().as_ref() {
+                if pointers.is_empty() {
+                    handle.end_drag();      // let inertia take over
+                } else {
+                    handle.begin_drag();    // pinch ended, continue orbiting with the remaining finger
+                }
+            }
+        })
+    };
+
+    let retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_: MouseEvent| attempt.set(*attempt + 1))
+    };
+
+    let reset_view = {
+        let viewer = viewer.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(handle) = viewer.borrow().as_ref() {
+                handle.reset_view();
+            }
+        })
+    };
+
+    let on_pointer_leave = {
+        let hovered = hovered.clone();
+        Callback::from(move |_: PointerEvent| hovered.set(None))
+    };
+
+    let interaction_style = match (hovered.is_some(), props.interactive) {
+        (true, _) => "touch-action: none; cursor: pointer;",
+        (false, true) => "touch-action: none; cursor: grab;",     // let pointer events handle touch gestures
+        (false, false) => "",
+    };
+
+    if !live {
+        let on_enable_3d = {
+            let force_live = force_live.clone();
+            Callback::from(move |_| force_live.set(true))
+        };
+        return html! {
+            <div class="model-viewer-container relative">
+                <Turntable
+                    manifest_path={metadata::turntable_path(&obj_path)}
+                    width={props.width}
+                    height={props.height}
+                    alt={props.alt.clone()}
+                    reason={*fallback}
+                    on_enable_3d={on_enable_3d}
+                />
+            </div>
+        };
+    }
+
     html! {
         <div class="model-viewer-container relative">
-            <canvas 
+            <canvas
                 ref={canvas_ref}
                 width={props.width.to_string()}
                 height={props.height.to_string()}
-                style={format!("width: {}px; height: {}px; background: transparent;", props.width, props.height)}
-                id="threejs-canvas"
+                style={format!("width: {}px; height: {}px; background: transparent; {}", props.width, props.height, interaction_style)}
+                onpointerdown={on_pointer_down}
+                onpointermove={on_pointer_move}
+                onpointerup={on_pointer_up.clone()}
+                onpointercancel={on_pointer_up}
+                onpointerleave={on_pointer_leave}
             />
-            if *loading {
-                <div class="absolute inset-0 flex items-center justify-center">
-                    <div class="text-red-600 text-center">
-                        <div class="animate-spin rounded-full h-12 w-12 border-b-2 border-red-600 mx-auto mb-2"></div>
-                        <div class="text-xl">{"Loading 3D Model..."}</div>
-                    </div>
+            if let Some((hotspot, (x, y))) = &*hovered {
+                // hotspot tooltip next to the cursor
+                <div
+                    class="absolute z-10 pointer-events-none bg-[#1a1a1a]/90 border-2 border-red-600 rounded px-3 py-2 font-mono max-w-xs"
+                    style={format!("left: {}px; top: {}px;", x + 14.0, y + 14.0)}
+                    role="tooltip"
+                >
+                    <div class="text-red-600 text-sm font-bold">{&hotspot.label}</div>
+                    if let Some(description) = &hotspot.description {
+                        <div class="text-gray-300 text-xs mt-1">{description}</div>
+                    }
                 </div>
             }
+            {match &*load_state {
+                LoadState::Downloading { loaded, total } => html! {
+                    <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                        <LoadingBar
+                            label="LOADING 3D MODEL"
+                            progress={total.map(|total| *loaded as f64 / total.max(1) as f64)}
+                            detail={match total {
+                                Some(total) => format!("{} / {}", format_bytes(*loaded), format_bytes(*total)),
+                                None => format_bytes(*loaded),
+                            }}
+                        />
+                    </div>
+                },
+                LoadState::Textures { loaded, total } => html! {
+                    <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                        <LoadingBar
+                            label="LOADING TEXTURES"
+                            progress={Some(*loaded as f64 / (*total).max(1) as f64)}
+                            detail={format!("{} / {}", loaded, total)}
+                        />
+                    </div>
+                },
+                LoadState::Failed(message) => html! {
+                    <div class="absolute inset-0 flex items-center justify-center">
+                        <div class="bg-[#1a1a1a]/90 border-2 border-red-600 rounded p-6 text-center font-mono max-w-sm" role="alert">
+                            <div class="text-red-600 text-lg font-bold mb-2">{"FAILED TO LOAD 3D MODEL"}</div>
+                            <div class="text-gray-300 text-sm mb-4">{message}</div>
+                            <button
+                                onclick={retry}
+                                class="bg-[#2b2b2b] hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-2 px-4 rounded transition-all duration-200 cursor-pointer text-sm"
+                            >
+                                {"RETRY"}
+                            </button>
+                        </div>
+                    </div>
+                },
+                LoadState::Ready => html! {},
+            }}
+            if *load_state == LoadState::Ready && props.interactive {
+                // reset view
+                <button
+                    onclick={reset_view}
+                    title="Reset view"
+                    class="absolute top-2 right-2 bg-[#2b2b2b]/80 hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-1 px-3 rounded transition-all duration-200 cursor-pointer font-mono text-xs"
+                >
+                    {"RESET VIEW"}
+                </button>
+            }
         </div>
     }
-}
\ No newline at end of file
+}
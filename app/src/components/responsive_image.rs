@@ -0,0 +1,204 @@
+// components/responsive_image.rs
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+use yew::prelude::*;
+
+use crate::images::{Format, ImageInfo, ImageManifest};
+
+// derivatives written by `cargo run --bin images -- src/pages src/content`, provided once by AppContent
+pub type ImagesContext = Rc<ImageManifest>;
+
+// how far below the fold lazy images start loading
+const LAZY_MARGIN: &str = "300px";
+
+type IntersectCallback = dyn FnMut(Vec<IntersectionObserverEntry>);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Loading {
+    #[default]
+    Eager,
+    Lazy,       // blurred placeholder until the image scrolls near the viewport
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ResponsiveImageProps {
+    pub src: String,
+    #[prop_or_default]
+    pub alt: String,
+    #[prop_or_default]
+    pub class: Classes,
+    #[prop_or_default]
+    pub style: Option<String>,
+    #[prop_or(AttrValue::from("100vw"))]
+    pub sizes: AttrValue,           // rendered width hint for the srcset, e.g. "(min-width: 1024px) 384px, 100vw"
+    #[prop_or_default]
+    pub loading: Loading,
+    #[prop_or_default]
+    pub onclick: Option<Callback<MouseEvent>>,
+    #[prop_or_default]
+    pub onerror: Option<Callback<Event>>,
+}
+
+// the smallest variant wide enough for a card is the src browsers without srcset support get
+fn fallback_src(info: &ImageInfo, src: &str) -> String {
+    info.fallback(480).map_or(src.to_string(), |variant| variant.src.clone())
+}
+
+// fetches an image into the http cache exactly as its <picture> would pick it, so showing it later is instant;
+// detached pictures still resolve their sources, no need to put it in the document
+pub fn preload(manifest: &ImageManifest, src: &str, sizes: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else { return };
+    let Ok(image) = document.create_element("img") else { return };
+    let Some(info) = manifest.get(src) else {
+        let _ = image.set_attribute("src", src);
+        return;
+    };
+
+    let Ok(picture) = document.create_element("picture") else { return };
+    for format in Format::MODERN {
+        let (Some(srcset), Ok(source)) = (info.srcset(format), document.create_element("source")) else { continue };
+        let _ = source.set_attribute("type", format.mime());
+        let _ = source.set_attribute("srcset", &srcset);
+        let _ = source.set_attribute("sizes", sizes);
+        let _ = picture.append_child(&source);
+    }
+    let _ = picture.append_child(&image);
+    if let Some(srcset) = info.srcset(info.format) {
+        let _ = image.set_attribute("srcset", &srcset);
+    }
+    let _ = image.set_attribute("sizes", sizes);
+    let _ = image.set_attribute("src", &fallback_src(info, src));
+}
+
+// <picture> with avif/webp sources and the intrinsic size set, a plain <img> for images without derivatives
+#[function_component(ResponsiveImage)]
+pub fn responsive_image(props: &ResponsiveImageProps) -> Html {
+    let manifest = use_context::<ImagesContext>().unwrap_or_default();
+    let visible = use_state(|| props.loading == Loading::Eager);
+    let loaded = use_state(|| false);
+    let placeholder_ref = use_node_ref();
+
+    // swap the placeholder for the real image once it nears the viewport
+    {
+        let visible = visible.clone();
+        let placeholder_ref = placeholder_ref.clone();
+        use_effect_with(*visible, move |&is_visible| {
+            let mut observer: Option<(IntersectionObserver, Closure<IntersectCallback>)> = None;
+            if let Some(element) = placeholder_ref.cast::<web_sys::Element>().filter(|_| !is_visible) {
+                let on_intersect = visible.clone();
+                let callback = Closure::<IntersectCallback>::new(move |entries: Vec<IntersectionObserverEntry>| {
+                    if entries.iter().any(|entry| entry.is_intersecting()) {
+                        on_intersect.set(true);
+                    }
+                });
+                let options = IntersectionObserverInit::new();
+                options.set_root_margin(LAZY_MARGIN);
+                match IntersectionObserver::new_with_options(callback.as_ref().unchecked_ref(), &options) {
+                    Ok(created) => {
+                        created.observe(&element);
+                        observer = Some((created, callback));
+                    }
+                    // no observer support, just load it
+                    Err(_) => visible.set(true),
+                }
+            }
+            move || {
+                if let Some((observer, _callback)) = observer {
+                    observer.disconnect();
+                }
+            }
+        });
+    }
+
+    let on_load = {
+        let loaded = loaded.clone();
+        Callback::from(move |_: Event| loaded.set(true))
+    };
+    // a broken image shouldn't leave the placeholder up forever
+    let on_error = {
+        let loaded = loaded.clone();
+        let onerror = props.onerror.clone();
+        Callback::from(move |event: Event| {
+            loaded.set(true);
+            if let Some(onerror) = &onerror {
+                onerror.emit(event);
+            }
+        })
+    };
+
+    let info = manifest.get(&props.src);
+    let lazy = props.loading == Loading::Lazy;
+    let placeholder = info.and_then(|info| info.placeholder.clone());
+
+    // blurred stand-in, kept until the real image has decoded so it never flashes empty
+    let placeholder_html = if lazy && !*loaded {
+        let size = info.map(|info| (info.width.to_string(), info.height.to_string()));
+        html! {
+            <img
+                ref={placeholder_ref}
+                src={placeholder}
+                width={size.as_ref().map(|(width, _)| width.clone())}
+                height={size.as_ref().map(|(_, height)| height.clone())}
+                alt=""
+                aria-hidden="true"
+                class={classes!(props.class.clone(), "blur-md")}
+                style={props.style.clone()}
+            />
+        }
+    } else {
+        html! {}
+    };
+    if !*visible {
+        return placeholder_html;
+    }
+
+    // the real image stays hidden behind the placeholder until it has loaded
+    // (no native loading="lazy" on top: browsers never fetch lazy images that are display: none)
+    let class = classes!(props.class.clone(), (lazy && !*loaded).then_some("hidden"));
+
+    let Some(info) = info else {
+        return html! {
+            <>
+                {placeholder_html}
+                <img
+                    src={props.src.clone()}
+                    alt={props.alt.clone()}
+                    class={class}
+                    style={props.style.clone()}
+                    onload={on_load}
+                    onclick={props.onclick.clone()}
+                    onerror={on_error.clone()}
+                />
+            </>
+        };
+    };
+
+    html! {
+        <>
+            {placeholder_html}
+            // display: contents so the img sizes against the caller's container like a bare <img> would
+            <picture class="contents">
+                { for Format::MODERN.iter().filter_map(|&format| {
+                    let srcset = info.srcset(format)?;
+                    Some(html! { <source type={format.mime()} srcset={srcset} sizes={props.sizes.clone()} /> })
+                })}
+                <img
+                    src={fallback_src(info, &props.src)}
+                    srcset={info.srcset(info.format)}
+                    sizes={props.sizes.clone()}
+                    width={info.width.to_string()}
+                    height={info.height.to_string()}
+                    alt={props.alt.clone()}
+                    class={class}
+                    style={props.style.clone()}
+                    onload={on_load}
+                    onclick={props.onclick.clone()}
+                    onerror={on_error.clone()}
+                />
+            </picture>
+        </>
+    }
+}
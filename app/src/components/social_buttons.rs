@@ -1,6 +1,8 @@
 // components/social_buttons.rs
 use yew::prelude::*;
 
+use crate::cv;
+
 #[derive(Properties, PartialEq)]
 pub struct SocialButtonProps {
     pub button_size: u8,
@@ -13,8 +15,8 @@ pub fn social_buttons(props: &SocialButtonProps) -> Html {
     // cv download handler
     let download_cv = Callback::from(|_| {
         if let Some(window) = web_sys::window() {
-            // direct link to cv file
-            let _ = window.open_with_url_and_target("/static/about/KR_CV.pdf", "_blank");
+            // generated from the about page's content on every build (src/bin/cv.rs)
+            let _ = window.open_with_url_and_target(cv::PDF_PATH, "_blank");
         }
     });
     
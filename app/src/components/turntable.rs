@@ -0,0 +1,182 @@
+// components/turntable.rs
+use gloo_net::http::Request;
+use wasm_bindgen::JsCast;
+use web_sys::{KeyboardEvent, PointerEvent};
+use yew::prelude::*;
+
+use crate::components::loading_bar::LoadingBar;
+use crate::renderer::metadata::TurntableManifest;
+use crate::renderer::support::FallbackReason;
+
+#[derive(Properties, PartialEq)]
+pub struct TurntableProps {
+    pub manifest_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub alt: String,
+    #[prop_or_default]
+    pub reason: Option<FallbackReason>,     // shown as a small note under the preview
+    #[prop_or_default]
+    pub on_enable_3d: Option<Callback<()>>, // switch to the live viewer anyway
+}
+
+#[derive(Clone, PartialEq)]
+enum ManifestState {
+    Loading,
+    Ready(TurntableManifest),
+    Failed(String),
+}
+
+async fn fetch_manifest(path: &str) -> Result<TurntableManifest, String> {
+    let response = Request::get(path).send().await.map_err(|e| format!("Network error: {}", e))?;
+    if !response.ok() {
+        return Err(format!("HTTP {} error", response.status()));
+    }
+    let json = response.text().await.map_err(|e| e.to_string())?;
+    TurntableManifest::parse(&json)
+}
+
+// pre-rendered sprite strip of the model, scrubbed by dragging or the arrow keys
+#[function_component(Turntable)]
+pub fn turntable(props: &TurntableProps) -> Html {
+    let manifest = use_state(|| ManifestState::Loading);
+    let image_failed = use_state(|| false);
+    let frame = use_state(|| 0i64);
+    let drag_start = use_mut_ref(|| None::<(f64, i64)>);   // pointer x and frame when the drag began
+
+    {
+        let manifest = manifest.clone();
+        use_effect_with(props.manifest_path.clone(), move |path| {
+            let path = path.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_manifest(&path).await {
+                    Ok(loaded) => manifest.set(ManifestState::Ready(loaded)),
+                    Err(err) => {
+                        tracing::error!("Failed to load turntable {}: {}", path, err);
+                        manifest.set(ManifestState::Failed(err));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let frames = match &*manifest {
+        ManifestState::Ready(manifest) => manifest.frames as i64,
+        _ => 1,
+    };
+    // one full drag across the preview turns the model all the way around
+    let pixels_per_frame = props.width as f64 / frames as f64;
+
+    let on_pointer_down = {
+        let drag_start = drag_start.clone();
+        let frame = frame.clone();
+        Callback::from(move |e: PointerEvent| {
+            if let Some(target) = e.target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) {
+                let _ = target.set_pointer_capture(e.pointer_id());
+            }
+            *drag_start.borrow_mut() = Some((e.client_x() as f64, *frame));
+        })
+    };
+
+    let on_pointer_move = {
+        let drag_start = drag_start.clone();
+        let frame = frame.clone();
+        Callback::from(move |e: PointerEvent| {
+            let Some((start_x, start_frame)) = *drag_start.borrow() else { return };
+            let offset = ((e.client_x() as f64 - start_x) / pixels_per_frame).round() as i64;
+            let next = (start_frame + offset).rem_euclid(frames);
+            if next != *frame {
+                frame.set(next);
+            }
+        })
+    };
+
+    let on_pointer_up = {
+        let drag_start = drag_start.clone();
+        Callback::from(move |_: PointerEvent| {
+            drag_start.borrow_mut().take();
+        })
+    };
+
+    let on_key_down = {
+        let frame = frame.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            let step = match e.key().as_str() {
+                "ArrowLeft" => -1,
+                "ArrowRight" => 1,
+                _ => return,
+            };
+            e.prevent_default();
+            frame.set((*frame + step).rem_euclid(frames));
+        })
+    };
+
+    let size_style = format!("width: {}px; height: {}px;", props.width, props.height);
+
+    html! {
+        <div class="relative" style={size_style.clone()}>
+            {match &*manifest {
+                ManifestState::Ready(manifest) if !*image_failed => {
+                    let on_error = {
+                        let image_failed = image_failed.clone();
+                        Callback::from(move |_: Event| image_failed.set(true))
+                    };
+                    html! {
+                        <div
+                            class="overflow-hidden cursor-grab focus:outline-2 focus:outline-red-600"
+                            style={format!("{} touch-action: none;", size_style)}
+                            tabindex="0"
+                            role="img"
+                            aria-label={format!("{} (drag or use the arrow keys to rotate)", props.alt)}
+                            onpointerdown={on_pointer_down}
+                            onpointermove={on_pointer_move}
+                            onpointerup={on_pointer_up.clone()}
+                            onpointercancel={on_pointer_up}
+                            onkeydown={on_key_down}
+                        >
+                            <img
+                                src={manifest.src.clone()}
+                                alt=""
+                                draggable="false"
+                                onerror={on_error}
+                                class="select-none pointer-events-none"
+                                style={format!(
+                                    "width: {}px; height: {}px; max-width: none; transform: translateX(-{}px); image-rendering: pixelated;",
+                                    props.width as u64 * manifest.frames as u64,
+                                    props.height,
+                                    props.width as i64 * *frame,
+                                )}
+                            />
+                        </div>
+                    }
+                }
+                ManifestState::Loading => html! {
+                    <div class="absolute inset-0 flex items-center justify-center">
+                        <LoadingBar label="LOADING PREVIEW" />
+                    </div>
+                },
+                _ => html! {
+                    <div class="absolute inset-0 flex items-center justify-center">
+                        <div class="text-gray-300 font-mono text-sm text-center">{"Preview unavailable"}</div>
+                    </div>
+                },
+            }}
+
+            // why the live viewer isn't running, with a way back to it when the device can handle it
+            if let Some(reason) = props.reason {
+                <div class="absolute top-2 right-2 flex flex-col items-end gap-2 font-mono text-xs">
+                    <span class="bg-[#1a1a1a]/80 text-gray-300 px-2 py-1 rounded">{reason.describe()}</span>
+                    if let (true, Some(on_enable_3d)) = (reason.can_opt_in(), props.on_enable_3d.clone()) {
+                        <button
+                            onclick={Callback::from(move |_: MouseEvent| on_enable_3d.emit(()))}
+                            class="bg-[#2b2b2b]/80 hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-1 px-3 rounded transition-all duration-200 cursor-pointer"
+                        >
+                            {"VIEW IN 3D"}
+                        </button>
+                    }
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,42 @@
+// components/volume_chart.rs
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct VolumeChartProps {
+    pub counts: Vec<(String, usize)>,   // (date, messages), oldest first
+}
+
+// messages per day as a row of red bars, the tallest day fills the height
+#[function_component(VolumeChart)]
+pub fn volume_chart(props: &VolumeChartProps) -> Html {
+    let peak = props.counts.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+    let total: usize = props.counts.iter().map(|(_, count)| count).sum();
+    let (Some((first, _)), Some((last, _))) = (props.counts.first(), props.counts.last()) else {
+        return html! {};
+    };
+
+    html! {
+        <figure class="p-3 bg-[#2b2b2b] border-2 border-[#0b0b0a] font-mono text-xs">
+            <figcaption class="flex justify-between mb-2">
+                <span class="text-gray-400">{format!("Messages, last {} days", props.counts.len())}</span>
+                <span class="text-red-600 font-bold">{total}</span>
+            </figcaption>
+            <div class="flex items-end gap-px h-24" role="img" aria-label={format!("{} messages between {} and {}", total, first, last)}>
+                { for props.counts.iter().map(|(date, count)| {
+                    let height = if *count == 0 { 2.0 } else { (*count as f64 / peak as f64 * 100.0).max(8.0) };
+                    html! {
+                        <div
+                            title={format!("{}: {}", date, count)}
+                            class={classes!("flex-1", if *count == 0 { "bg-gray-700" } else { "bg-red-600 hover:bg-red-400" })}
+                            style={format!("height: {:.0}%", height)}
+                        ></div>
+                    }
+                })}
+            </div>
+            <div class="flex justify-between mt-1 text-gray-500">
+                <span>{first}</span>
+                <span>{last}</span>
+            </div>
+        </figure>
+    }
+}
@@ -0,0 +1,107 @@
+// contact/attachments.rs
+// which files can ride along with a message: checked as they're picked, and again by whatever receives them
+use std::fmt;
+
+// the multipart field every file is sent under, text fields keep their own names
+pub const ATTACHMENT_FIELD: &str = "attachment";
+
+pub const MAX_ATTACHMENTS: usize = 5;
+pub const MAX_ATTACHMENT_SIZE: u64 = 10 * 1024 * 1024;
+pub const MAX_TOTAL_SIZE: u64 = 20 * 1024 * 1024;
+
+// by extension, browsers don't know a type for wads and pk3s
+pub const ALLOWED_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "pdf", "txt", "md", "zip", "wad", "pk3"];
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttachmentError {
+    Type { name: String },
+    TooBig { name: String },
+    TooMany,
+    TotalTooBig,
+}
+
+impl fmt::Display for AttachmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttachmentError::Type { name } => {
+                write!(f, "{} isn't a file type the form takes ({})", name, ALLOWED_EXTENSIONS.join(", "))
+            }
+            AttachmentError::TooBig { name } => write!(f, "{} is over {} MB", name, MAX_ATTACHMENT_SIZE / (1024 * 1024)),
+            AttachmentError::TooMany => write!(f, "At most {} files per message", MAX_ATTACHMENTS),
+            AttachmentError::TotalTooBig => write!(f, "All files together can't be over {} MB", MAX_TOTAL_SIZE / (1024 * 1024)),
+        }
+    }
+}
+
+fn extension(name: &str) -> Option<String> {
+    let (_, extension) = name.rsplit_once('.')?;
+    Some(extension.to_ascii_lowercase())
+}
+
+pub fn is_image(name: &str) -> bool {
+    extension(name).is_some_and(|extension| IMAGE_EXTENSIONS.contains(&extension.as_str()))
+}
+
+// for the file input's accept attribute
+pub fn accept() -> String {
+    ALLOWED_EXTENSIONS.iter().map(|extension| format!(".{}", extension)).collect::<Vec<_>>().join(",")
+}
+
+// whether a file of this name and size can join the ones already attached (their sizes)
+pub fn check(name: &str, size: u64, attached: &[u64]) -> Result<(), AttachmentError> {
+    if !extension(name).is_some_and(|extension| ALLOWED_EXTENSIONS.contains(&extension.as_str())) {
+        return Err(AttachmentError::Type { name: name.to_string() });
+    }
+    if size > MAX_ATTACHMENT_SIZE {
+        return Err(AttachmentError::TooBig { name: name.to_string() });
+    }
+    if attached.len() >= MAX_ATTACHMENTS {
+        return Err(AttachmentError::TooMany);
+    }
+    if attached.iter().sum::<u64>() + size > MAX_TOTAL_SIZE {
+        return Err(AttachmentError::TotalTooBig);
+    }
+    Ok(())
+}
+
+// a whole set at once, the way a receiver sees them: (file name, size)
+pub fn check_all(files: &[(String, u64)]) -> Result<(), AttachmentError> {
+    let mut attached = Vec::new();
+    for (name, size) in files {
+        check(name, *size, &attached)?;
+        attached.push(*size);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn takes_known_types_within_the_limits() {
+        assert_eq!(check("MAP07.WAD", 2 * MB, &[]), Ok(()));
+        assert_eq!(check("shot.PNG", MB, &[]), Ok(()));
+        assert_eq!(check("setup.exe", 1, &[]), Err(AttachmentError::Type { name: "setup.exe".to_string() }));
+        assert_eq!(check("README", 1, &[]), Err(AttachmentError::Type { name: "README".to_string() }));
+        assert_eq!(check("big.zip", MAX_ATTACHMENT_SIZE + 1, &[]), Err(AttachmentError::TooBig { name: "big.zip".to_string() }));
+        assert_eq!(check("one.txt", 1, &[1; MAX_ATTACHMENTS]), Err(AttachmentError::TooMany));
+        assert_eq!(check("last.pdf", 5 * MB, &[8 * MB, 8 * MB]), Err(AttachmentError::TotalTooBig));
+        assert_eq!(check_all(&[("a.pk3".to_string(), 9 * MB), ("b.pk3".to_string(), 9 * MB)]), Ok(()));
+        assert_eq!(
+            check_all(&[("a.pk3".to_string(), 9 * MB), ("b.pk3".to_string(), 9 * MB), ("c.pk3".to_string(), 9 * MB)]),
+            Err(AttachmentError::TotalTooBig)
+        );
+    }
+
+    #[test]
+    fn knows_what_to_preview() {
+        assert!(is_image("titlepic.webp"));
+        assert!(!is_image("brief.pdf"));
+        assert!(!is_image("png"));
+        assert!(accept().starts_with(".png,.jpg,"));
+    }
+}
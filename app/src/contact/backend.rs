@@ -0,0 +1,399 @@
+// contact/backend.rs
+// the services a contact message can go to: each turns a submission into a request and judges the response,
+// the page only does the sending so all of this stays testable off the browser
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::contact::error::ContactError;
+use crate::contact::spam::SPAM_CODE;
+use crate::contact::Submission;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BackendConfig {
+    AppsScript { url: String },     // google apps script web app writing to a sheet
+    Webhook {                       // any endpoint taking the submission as json
+        url: String,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        headers: BTreeMap<String, String>,
+    },
+    Formspree { url: String },      // formspree.io/f/<id> and the services copying its api
+    Server { url: String },         // the self-hosted server crate, usually "/api/contact"
+    Mailto { address: String },     // no service at all, opens the visitor's mail client
+}
+
+impl BackendConfig {
+    pub fn backend(&self) -> Box<dyn ContactBackend> {
+        match self.clone() {
+            BackendConfig::AppsScript { url } => Box::new(AppsScript { url }),
+            BackendConfig::Webhook { url, headers } => Box::new(Webhook { url, headers }),
+            BackendConfig::Formspree { url } => Box::new(Formspree { url }),
+            BackendConfig::Server { url } => Box::new(Server { url }),
+            BackendConfig::Mailto { address } => Box::new(Mailto { address }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outgoing {
+    Post {
+        url: String,
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+    Open(String),   // navigate to this url instead of fetching anything
+}
+
+// a multipart/form-data post: the text fields come from the backend, the page appends the files
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultipartPost {
+    pub url: String,
+    pub headers: Vec<(String, String)>,     // never Content-Type, the browser sets it with the boundary
+    pub fields: Vec<(String, String)>,
+}
+
+pub trait ContactBackend {
+    // for messages, e.g. "couldn't reach the webhook"
+    fn name(&self) -> &'static str;
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError>;
+    // status and body of the answer to a Post; the Err is shown to the visitor
+    fn response(&self, status: u16, body: &str) -> Result<(), ContactError>;
+    // the same message as form fields for sending attachments along, None when the service can't take files
+    fn multipart(&self, _submission: &Submission) -> Option<MultipartPost> {
+        None
+    }
+    // where to get a spam::FormToken when the form opens, for services that don't take form_seconds on trust
+    fn form_token_url(&self) -> Option<String> {
+        None
+    }
+}
+
+// {"code": "spam"} next to an error, how the receiving end says a message looked like a bot
+fn flagged_as_spam(reply: &Value) -> bool {
+    reply["code"].as_str().is_some_and(|code| code.eq_ignore_ascii_case(SPAM_CODE))
+}
+
+// the plain fields and the form timing, plus the recaptcha answer under the name google's widget gives it
+fn form_fields(submission: &Submission) -> Vec<(String, String)> {
+    let mut fields = vec![
+        ("name".to_string(), submission.name.clone()),
+        ("email".to_string(), submission.email.clone()),
+        ("subject".to_string(), submission.subject.clone()),
+        ("message".to_string(), submission.message.clone()),
+    ];
+    if !submission.recaptcha_response.is_empty() {
+        fields.push(("g-recaptcha-response".to_string(), submission.recaptcha_response.clone()));
+    }
+    if let Some(seconds) = submission.form_seconds {
+        fields.push(("form_seconds".to_string(), seconds.to_string()));
+    }
+    fields
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, ContactError> {
+    serde_json::to_string(value).map_err(|e| ContactError::Request(format!("Failed to serialize form data: {}", e)))
+}
+
+pub struct AppsScript {
+    pub url: String,
+}
+
+impl ContactBackend for AppsScript {
+    fn name(&self) -> &'static str {
+        "Google Apps Script"
+    }
+
+    // text/plain keeps it a simple request, apps script doesn't answer cors preflights
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError> {
+        Ok(Outgoing::Post {
+            url: self.url.clone(),
+            headers: vec![("Content-Type".to_string(), "text/plain;charset=utf-8".to_string())],
+            body: to_json(submission)?,
+        })
+    }
+
+    // {"result": "success"} or {"result": "error", "error": ...}; anything else that came back 200 went through
+    fn response(&self, status: u16, body: &str) -> Result<(), ContactError> {
+        if !(200..300).contains(&status) {
+            return Err(ContactError::http(status, body));
+        }
+        let Ok(reply) = serde_json::from_str::<Value>(body) else { return Ok(()) };
+        match reply["result"].as_str() {
+            Some("error") if flagged_as_spam(&reply) => Err(ContactError::Spam),
+            Some("error") => Err(ContactError::Rejected(reply["error"].as_str().unwrap_or("The script rejected the message").to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct Webhook {
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl ContactBackend for Webhook {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError> {
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        headers.extend(self.headers.iter().map(|(name, value)| (name.clone(), value.clone())));
+        Ok(Outgoing::Post { url: self.url.clone(), headers, body: to_json(submission)? })
+    }
+
+    fn response(&self, status: u16, body: &str) -> Result<(), ContactError> {
+        if (200..300).contains(&status) {
+            Ok(())
+        } else if serde_json::from_str::<Value>(body).is_ok_and(|reply| flagged_as_spam(&reply)) {
+            Err(ContactError::Spam)
+        } else {
+            Err(ContactError::http(status, body))
+        }
+    }
+
+    fn multipart(&self, submission: &Submission) -> Option<MultipartPost> {
+        let headers = self.headers.iter().filter(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        Some(MultipartPost {
+            url: self.url.clone(),
+            headers: headers.map(|(name, value)| (name.clone(), value.clone())).collect(),
+            fields: form_fields(submission),
+        })
+    }
+}
+
+pub struct Formspree {
+    pub url: String,
+}
+
+impl ContactBackend for Formspree {
+    fn name(&self) -> &'static str {
+        "Formspree"
+    }
+
+    // _subject and _replyto are formspree's names for the email it forwards
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError> {
+        let mut body = json!({
+            "name": submission.name,
+            "email": submission.email,
+            "_replyto": submission.email,
+            "_subject": submission.subject,
+            "message": submission.message,
+        });
+        if !submission.recaptcha_response.is_empty() {
+            body["g-recaptcha-response"] = json!(submission.recaptcha_response);
+        }
+        if let Some(seconds) = submission.form_seconds {
+            body["form_seconds"] = json!(seconds);
+        }
+        Ok(Outgoing::Post {
+            url: self.url.clone(),
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ],
+            body: to_json(&body)?,
+        })
+    }
+
+    // rejections come back as {"errors": [{"message": ...}]}
+    fn response(&self, status: u16, body: &str) -> Result<(), ContactError> {
+        if (200..300).contains(&status) {
+            return Ok(());
+        }
+        let errors = serde_json::from_str::<Value>(body).ok().and_then(|reply| reply["errors"].as_array().cloned()).unwrap_or_default();
+        if errors.iter().any(flagged_as_spam) {
+            return Err(ContactError::Spam);
+        }
+        let messages: Vec<String> = errors
+            .iter()
+            .filter_map(|error| error["message"].as_str().map(str::to_string))
+            .collect();
+        if messages.is_empty() {
+            Err(ContactError::http(status, body))
+        } else {
+            Err(ContactError::Http { status, message: messages.join(", ") })
+        }
+    }
+
+    // formspree takes files on the same endpoint when the form is posted as multipart
+    fn multipart(&self, submission: &Submission) -> Option<MultipartPost> {
+        let mut fields = form_fields(submission);
+        fields.push(("_replyto".to_string(), submission.email.clone()));
+        fields.push(("_subject".to_string(), submission.subject.clone()));
+        Some(MultipartPost {
+            url: self.url.clone(),
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+            fields,
+        })
+    }
+}
+
+// the server's multipart body: the submission as json in this field, the files under ATTACHMENT_FIELD
+pub const SUBMISSION_FIELD: &str = "submission";
+
+pub struct Server {
+    pub url: String,
+}
+
+impl ContactBackend for Server {
+    fn name(&self) -> &'static str {
+        "message server"
+    }
+
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError> {
+        Ok(Outgoing::Post {
+            url: self.url.clone(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: to_json(submission)?,
+        })
+    }
+
+    // validation and rate limit refusals come back as {"error": ...}
+    fn response(&self, status: u16, body: &str) -> Result<(), ContactError> {
+        if (200..300).contains(&status) {
+            return Ok(());
+        }
+        let reply = serde_json::from_str::<Value>(body).unwrap_or(Value::Null);
+        match reply["error"].as_str() {
+            _ if flagged_as_spam(&reply) => Err(ContactError::Spam),
+            Some(message) => Err(ContactError::Http { status, message: message.to_string() }),
+            None => Err(ContactError::http(status, body)),
+        }
+    }
+
+    // the whole submission in one field, so the server reads it the same as a json post
+    fn multipart(&self, submission: &Submission) -> Option<MultipartPost> {
+        Some(MultipartPost { url: self.url.clone(), headers: Vec::new(), fields: vec![(SUBMISSION_FIELD.to_string(), to_json(submission).ok()?)] })
+    }
+
+    // "/api/contact" -> "/api/contact/form"
+    fn form_token_url(&self) -> Option<String> {
+        Some(format!("{}/form", self.url.trim_end_matches('/')))
+    }
+}
+
+pub struct Mailto {
+    pub address: String,
+}
+
+impl ContactBackend for Mailto {
+    fn name(&self) -> &'static str {
+        "mail client"
+    }
+
+    fn request(&self, submission: &Submission) -> Result<Outgoing, ContactError> {
+        Ok(Outgoing::Open(mailto_url(&self.address, submission)))
+    }
+
+    // nothing to answer, the visitor sends it themselves
+    fn response(&self, _status: u16, _body: &str) -> Result<(), ContactError> {
+        Ok(())
+    }
+}
+
+// mailto:address?subject=...&body=... with the sender's details on top of the message
+pub fn mailto_url(address: &str, submission: &Submission) -> String {
+    let subject = if submission.subject.is_empty() { "Message from the website" } else { &submission.subject };
+    let body = format!("From: {} <{}>\n\n{}", submission.name, submission.email, submission.message);
+    format!("mailto:{}?subject={}&body={}", address, percent_encode(subject), percent_encode(&body))
+}
+
+// everything but the unreserved characters, as rfc 6068 wants for mailto fields
+pub(crate) fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission() -> Submission {
+        Submission {
+            name: "Doom Guy".to_string(),
+            email: "guy@example.com".to_string(),
+            subject: "Map 07 & you".to_string(),
+            message: "Dead simple.\nRight?".to_string(),
+            form_seconds: Some(12),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reads_config_variants() {
+        let config: BackendConfig = serde_json::from_str(r#"{"type": "webhook", "url": "http://localhost:8787/webhook", "headers": {"X-Key": "k"}}"#).unwrap();
+        let Ok(Outgoing::Post { url, headers, .. }) = config.backend().request(&submission()) else { panic!("not a post") };
+        assert_eq!(url, "http://localhost:8787/webhook");
+        assert!(headers.contains(&("X-Key".to_string(), "k".to_string())));
+
+        let config: BackendConfig = serde_json::from_str(r#"{"type": "apps_script", "url": "https://script.example/exec"}"#).unwrap();
+        assert_eq!(config, BackendConfig::AppsScript { url: "https://script.example/exec".to_string() });
+    }
+
+    #[test]
+    fn judges_responses_per_service() {
+        let script = AppsScript { url: String::new() };
+        assert_eq!(script.response(200, r#"{"result": "success"}"#), Ok(()));
+        assert_eq!(script.response(200, "<html>ok</html>"), Ok(()));
+        assert_eq!(script.response(200, r#"{"result": "error", "error": "quota"}"#), Err(ContactError::Rejected("quota".to_string())));
+        assert_eq!(script.response(500, "<html>oops</html>").unwrap_err().to_string(), "HTTP 500 error");
+
+        let formspree = Formspree { url: String::new() };
+        assert_eq!(formspree.response(200, r#"{"ok": true}"#), Ok(()));
+        let rejected = r#"{"errors": [{"code": "TYPE_EMAIL", "message": "should be an email"}]}"#;
+        assert_eq!(formspree.response(422, rejected), Err(ContactError::Http { status: 422, message: "should be an email".to_string() }));
+
+        let server = Server { url: String::new() };
+        assert_eq!(server.response(201, r#"{"ok": true, "id": 3}"#), Ok(()));
+        assert_eq!(server.response(429, r#"{"error": "too many messages"}"#), Err(ContactError::Http { status: 429, message: "too many messages".to_string() }));
+
+        // spam refusals read the same everywhere, whatever the service said
+        assert_eq!(server.response(403, r#"{"error": "no", "code": "spam"}"#), Err(ContactError::Spam));
+        assert_eq!(formspree.response(403, r#"{"errors": [{"code": "SPAM", "message": "no"}]}"#), Err(ContactError::Spam));
+        assert_eq!(script.response(200, r#"{"result": "error", "error": "no", "code": "spam"}"#), Err(ContactError::Spam));
+
+        // only the self-hosted server times the form itself
+        assert_eq!(Server { url: "/api/contact/".to_string() }.form_token_url().as_deref(), Some("/api/contact/form"));
+        assert_eq!(formspree.form_token_url(), None);
+    }
+
+    #[test]
+    fn sends_files_only_where_the_service_takes_them() {
+        let mut headers = BTreeMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert("X-Key".to_string(), "k".to_string());
+        let webhook = Webhook { url: "http://localhost:8787/webhook".to_string(), headers };
+        let post = webhook.multipart(&submission()).unwrap();
+        assert_eq!(post.headers, [("X-Key".to_string(), "k".to_string())]);
+        assert!(post.fields.contains(&("subject".to_string(), "Map 07 & you".to_string())));
+        assert!(post.fields.contains(&("form_seconds".to_string(), "12".to_string())));
+
+        let formspree = Formspree { url: String::new() }.multipart(&submission()).unwrap();
+        assert!(formspree.fields.contains(&("_replyto".to_string(), "guy@example.com".to_string())));
+        let server = Server { url: "/api/contact".to_string() }.multipart(&submission()).unwrap();
+        assert_eq!(server.fields.len(), 1);
+        assert_eq!(serde_json::from_str::<Submission>(&server.fields[0].1).unwrap(), submission());
+        assert_eq!(AppsScript { url: String::new() }.multipart(&submission()), None);
+        assert_eq!(Mailto { address: String::new() }.multipart(&submission()), None);
+    }
+
+    #[test]
+    fn builds_mailto_links() {
+        let url = mailto_url("me@example.com", &submission());
+        assert_eq!(
+            url,
+            "mailto:me@example.com?subject=Map%2007%20%26%20you\
+             &body=From%3A%20Doom%20Guy%20%3Cguy%40example.com%3E%0A%0ADead%20simple.%0ARight%3F"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+// contact/captcha.rs
+// which captcha the form shows, and a self-hosted hashcash-style proof of work as the alternative to google's:
+// the server hands out a random salt, the browser looks for a nonce whose sha-256 of "salt:nonce" starts with
+// enough zero bits, the server checks that with a single hash
+use serde::{Deserialize, Serialize};
+
+pub const CHALLENGE_API: &str = "/api/contact/challenge";
+// trunk builds src/bin/pow_worker.rs to these, next to index.html. nothing rewrites these paths the way it
+// does for the main bundle, so the worker's <link> in index.html pins its name and opts out of file hashing
+pub const WORKER_SCRIPT: &str = "/pow_worker.js";
+pub const WORKER_WASM: &str = "/pow_worker_bg.wasm";
+
+// progress is reported this often, a few times a second in the browser
+pub const PROGRESS_EVERY: u64 = 1 << 15;
+
+// only the backend can check either, so pick one it understands (the self-hosted server checks proofs of work)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CaptchaConfig {
+    Recaptcha { site_key: String },
+    ProofOfWork { challenge_url: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Challenge {
+    pub salt: String,
+    pub difficulty: u32,    // leading zero bits, every extra bit doubles the work
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    pub salt: String,
+    pub nonce: u64,
+}
+
+// what the worker posts back while solving
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkerMessage {
+    Progress { tried: u64 },
+    Solved { nonce: u64 },
+}
+
+pub fn leading_zero_bits(hash: &[u8; 32]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+pub fn check(proof: &Proof, difficulty: u32) -> bool {
+    leading_zero_bits(&sha256(format!("{}:{}", proof.salt, proof.nonce).as_bytes())) >= difficulty
+}
+
+// counts up from zero, calling progress with the number tried every PROGRESS_EVERY hashes
+pub fn solve(challenge: &Challenge, mut progress: impl FnMut(u64)) -> Proof {
+    let mut nonce = 0u64;
+    loop {
+        let proof = Proof { salt: challenge.salt.clone(), nonce };
+        if check(&proof, challenge.difficulty) {
+            return proof;
+        }
+        nonce += 1;
+        if nonce.is_multiple_of(PROGRESS_EVERY) {
+            progress(nonce);
+        }
+    }
+}
+
+// how far along a search that has tried this many nonces probably is, nobody knows the real answer upfront
+pub fn expected_progress(tried: u64, difficulty: u32) -> f64 {
+    1.0 - (-(tried as f64) / 2f64.powi(difficulty as i32)).exp()
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// fips 180-4, small enough to not pull in a crypto crate for the one hash both sides need
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+    // message, a 1 bit, zeros up to 56 mod 64, then the length in bits
+    let mut padded = data.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in padded.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+            (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+        }
+        for (word, add) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(add);
+        }
+    }
+
+    let mut hash = [0u8; 32];
+    for (bytes, word) in hash.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(hash: [u8; 32]) -> String {
+        hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn hashes_the_standard_vectors() {
+        assert_eq!(hex(sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hex(sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(
+            hex(sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        // padding spills into a second block
+        assert_eq!(hex(sha256(&[b'a'; 64])), "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb");
+    }
+
+    #[test]
+    fn solves_and_checks_proofs() {
+        assert_eq!(leading_zero_bits(&[0; 32]), 256);
+        let mut hash = [0xff; 32];
+        hash[0] = 0;
+        hash[1] = 0b0001_0000;
+        assert_eq!(leading_zero_bits(&hash), 11);
+
+        let challenge = Challenge { salt: "e1m1".to_string(), difficulty: 12 };
+        let mut reports = 0;
+        let proof = solve(&challenge, |_| reports += 1);
+        assert!(check(&proof, 12));
+        assert_eq!(reports as u64, proof.nonce / PROGRESS_EVERY);
+        // the first nonce that works, anything before fails
+        assert!((0..proof.nonce).all(|nonce| !check(&Proof { salt: challenge.salt.clone(), nonce }, 12)));
+
+        assert_eq!(expected_progress(0, 20), 0.0);
+        assert!((expected_progress(1 << 20, 20) - 0.632).abs() < 0.001);
+    }
+
+    #[test]
+    fn reads_captcha_configs() {
+        let config: CaptchaConfig = serde_json::from_str(r#"{"type": "proof_of_work", "challenge_url": "/api/contact/challenge"}"#).unwrap();
+        assert_eq!(config, CaptchaConfig::ProofOfWork { challenge_url: CHALLENGE_API.to_string() });
+        let message: WorkerMessage = serde_json::from_str(r#"{"type": "solved", "nonce": 42}"#).unwrap();
+        assert_eq!(message, WorkerMessage::Solved { nonce: 42 });
+    }
+}
@@ -0,0 +1,69 @@
+// contact/error.rs
+// everything that can stop a message on its way, worded for the visitor by Display
+use std::fmt;
+
+use crate::contact::spam;
+use crate::contact::validate::{Field, FieldError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContactError {
+    Invalid { field: Field, error: FieldError },
+    Request(String),                    // couldn't even build the request
+    Network(String),                    // no answer at all: offline, dns, refused connection
+    Cors { url: String },               // the service is up but doesn't let this site read its answers
+    Http { status: u16, message: String },
+    Captcha(String),
+    Rejected(String),                   // answered 200 and still said no, like apps script's {"result": "error"}
+    Spam,                               // looked like a bot, to the form or the service; the visitor isn't told why
+}
+
+impl ContactError {
+    // worth trying again later without the visitor changing anything
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ContactError::Network(_) => true,
+            ContactError::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    // "HTTP 500 error" from a status and a body, the body only when it's short text rather than an error page
+    pub fn http(status: u16, body: &str) -> ContactError {
+        let body = body.trim();
+        let message = if body.starts_with('<') { String::new() } else { body.chars().take(200).collect() };
+        ContactError::Http { status, message }
+    }
+}
+
+impl fmt::Display for ContactError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContactError::Invalid { field, error } => write!(f, "{} {}", field.label(), error),
+            ContactError::Request(err) => write!(f, "Failed to create request: {}", err),
+            ContactError::Network(err) => write!(f, "Network error: {}", err),
+            ContactError::Cors { url } => write!(f, "{} doesn't accept messages from this site (CORS)", url),
+            ContactError::Http { status, message } if message.is_empty() => write!(f, "HTTP {} error", status),
+            ContactError::Http { status, message } => write!(f, "HTTP {} error: {}", status, message),
+            ContactError::Captcha(err) => write!(f, "Captcha failed: {}", err),
+            ContactError::Rejected(err) => write!(f, "{}", err),
+            ContactError::Spam => write!(f, "{}", spam::REJECTION),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_errors_and_knows_which_to_retry() {
+        assert_eq!(ContactError::http(500, "<html>oops</html>").to_string(), "HTTP 500 error");
+        assert_eq!(ContactError::http(422, " bad email\n").to_string(), "HTTP 422 error: bad email");
+        assert!(ContactError::http(503, "").is_transient());
+        assert!(!ContactError::http(429, "").is_transient());
+        assert!(ContactError::Network("offline".to_string()).is_transient());
+        assert!(!ContactError::Cors { url: "https://example.com".to_string() }.is_transient());
+        assert!(!ContactError::Invalid { field: Field::Name, error: FieldError::Required }.is_transient());
+        assert!(!ContactError::Spam.is_transient());
+    }
+}
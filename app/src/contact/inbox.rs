@@ -0,0 +1,228 @@
+// contact/inbox.rs
+// received messages as the server's admin api hands them out, and what the admin page does with them
+use serde::{Deserialize, Serialize};
+
+use crate::contact::backend::percent_encode;
+
+pub const LOGIN_API: &str = "/api/admin/login";
+pub const LOGOUT_API: &str = "/api/admin/logout";
+pub const MESSAGES_API: &str = "/api/admin/messages";   // GET the list, PATCH /<id> with a MessageUpdate
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InboxMessage {
+    pub id: i64,
+    pub received: u64,      // unix seconds
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub message: String,
+    pub read: bool,
+    pub spam: bool,
+}
+
+// only the flags that are set change
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spam: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Login {
+    pub password: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub token: String,      // sent back as "Authorization: Bearer <token>"
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Folder {
+    #[default]
+    Inbox,      // everything but spam
+    Unread,
+    Spam,
+}
+
+impl Folder {
+    pub const ALL: [Folder; 3] = [Folder::Inbox, Folder::Unread, Folder::Spam];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Folder::Inbox => "Inbox",
+            Folder::Unread => "Unread",
+            Folder::Spam => "Spam",
+        }
+    }
+
+    pub fn contains(self, message: &InboxMessage) -> bool {
+        match self {
+            Folder::Inbox => !message.spam,
+            Folder::Unread => !message.spam && !message.read,
+            Folder::Spam => message.spam,
+        }
+    }
+}
+
+// every word of the query somewhere in the sender, subject or text, ignoring case
+pub fn matches(message: &InboxMessage, query: &str) -> bool {
+    let haystack = format!("{}\n{}\n{}\n{}", message.name, message.email, message.subject, message.message).to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| haystack.contains(word))
+}
+
+pub fn search<'a>(messages: &'a [InboxMessage], folder: Folder, query: &str) -> Vec<&'a InboxMessage> {
+    messages.iter().filter(|message| folder.contains(message) && matches(message, query)).collect()
+}
+
+// proleptic gregorian date of a unix day number (howard hinnant's days_from_civil, backwards)
+fn civil(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+// "2025-03-14", in utc
+pub fn date(received: u64) -> String {
+    let (year, month, day) = civil((received / SECONDS_PER_DAY) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// "2025-03-14 09:26"
+pub fn date_time(received: u64) -> String {
+    let seconds = received % SECONDS_PER_DAY;
+    format!("{} {:02}:{:02}", date(received), seconds / 3600, seconds % 3600 / 60)
+}
+
+// messages per utc day for the `days` days up to and including the one `now` falls on, oldest first
+pub fn daily_counts(messages: &[InboxMessage], now: u64, days: u64) -> Vec<(String, usize)> {
+    let today = now / SECONDS_PER_DAY;
+    let first = (today + 1).saturating_sub(days);
+    (first..=today)
+        .map(|day| {
+            let count = messages.iter().filter(|message| message.received / SECONDS_PER_DAY == day).count();
+            (date(day * SECONDS_PER_DAY), count)
+        })
+        .collect()
+}
+
+// rfc 4180: every field quoted, quotes doubled, crlf line ends. fields a spreadsheet would take for a
+// formula get a leading ' since name, subject and message are whatever the sender typed
+pub fn to_csv(messages: &[&InboxMessage]) -> String {
+    let quote = |field: &str| {
+        let escape = if field.starts_with(['=', '+', '-', '@']) { "'" } else { "" };
+        format!("\"{}{}\"", escape, field.replace('"', "\"\""))
+    };
+    let mut csv = String::from("id,received,name,email,subject,message,read,spam\r\n");
+    for message in messages {
+        let fields = [
+            message.id.to_string(),
+            date_time(message.received),
+            message.name.clone(),
+            message.email.clone(),
+            message.subject.clone(),
+            message.message.clone(),
+            message.read.to_string(),
+            message.spam.to_string(),
+        ];
+        csv.push_str(&fields.iter().map(|field| quote(field)).collect::<Vec<_>>().join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+// mailto: answer with "Re: " on the subject and the original quoted below
+pub fn reply_url(message: &InboxMessage) -> String {
+    let subject = match message.subject.trim() {
+        "" => "Re: your message".to_string(),
+        subject if subject.to_lowercase().starts_with("re:") => subject.to_string(),
+        subject => format!("Re: {}", subject),
+    };
+    let quoted: Vec<String> = message.message.lines().map(|line| format!("> {}", line)).collect();
+    let body = format!("\n\nOn {}, {} wrote:\n{}", date_time(message.received), message.name, quoted.join("\n"));
+    // the address is whatever the sender typed, so it's encoded too or a ? or & in it would add headers
+    let to: Vec<String> = message.email.split('@').map(percent_encode).collect();
+    format!("mailto:{}?subject={}&body={}", to.join("@"), percent_encode(&subject), percent_encode(&body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: i64, received: u64, subject: &str, read: bool, spam: bool) -> InboxMessage {
+        InboxMessage {
+            id,
+            received,
+            name: "Doom Guy".to_string(),
+            email: "guy@example.com".to_string(),
+            subject: subject.to_string(),
+            message: "Rip and \"tear\"".to_string(),
+            read,
+            spam,
+        }
+    }
+
+    #[test]
+    fn files_and_searches_messages() {
+        let messages = [message(1, 0, "Map commission", false, false), message(2, 0, "Cheap pills", false, true), message(3, 0, "Hello", true, false)];
+        let ids = |found: Vec<&InboxMessage>| found.iter().map(|message| message.id).collect::<Vec<_>>();
+        assert_eq!(ids(search(&messages, Folder::Inbox, "")), [1, 3]);
+        assert_eq!(ids(search(&messages, Folder::Unread, "")), [1]);
+        assert_eq!(ids(search(&messages, Folder::Spam, "")), [2]);
+        assert_eq!(ids(search(&messages, Folder::Inbox, "MAP guy")), [1]);
+        assert_eq!(ids(search(&messages, Folder::Inbox, "map hello")), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn dates_and_counts_per_day() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date_time(951_782_400 + 3_723), "2000-02-29 01:02");
+        assert_eq!(date(1_735_689_599), "2024-12-31");
+
+        let day = SECONDS_PER_DAY;
+        let messages = [message(1, 10 * day + 5, "", false, false), message(2, 10 * day + 7, "", false, false), message(3, 12 * day, "", false, false)];
+        let counts: Vec<usize> = daily_counts(&messages, 12 * day + 100, 4).into_iter().map(|(_, count)| count).collect();
+        assert_eq!(counts, [0, 2, 0, 1]);
+        assert_eq!(daily_counts(&messages, 12 * day, 1)[0].0, "1970-01-13");
+    }
+
+    #[test]
+    fn exports_csv_and_reply_links() {
+        let message = message(7, 0, "Hi, there", true, false);
+        assert_eq!(
+            to_csv(&[&message]),
+            "id,received,name,email,subject,message,read,spam\r\n\
+             \"7\",\"1970-01-01 00:00\",\"Doom Guy\",\"guy@example.com\",\"Hi, there\",\"Rip and \"\"tear\"\"\",\"true\",\"false\"\r\n"
+        );
+        assert!(reply_url(&message).starts_with("mailto:guy@example.com?subject=Re%3A%20Hi%2C%20there&body=%0A%0AOn%201970-01-01"));
+    }
+
+    #[test]
+    fn encodes_the_reply_address() {
+        let message = InboxMessage { email: "guy@example.com?cc=all@example.com&body=x".to_string(), ..message(7, 0, "", false, false) };
+        assert!(reply_url(&message).starts_with("mailto:guy@example.com%3Fcc%3Dall@example.com%26body%3Dx?subject="));
+    }
+
+    #[test]
+    fn defuses_spreadsheet_formulas() {
+        let message = InboxMessage {
+            name: "=HYPERLINK(\"http://evil.example\")".to_string(),
+            subject: "-2+3".to_string(),
+            message: "@SUM(A1)".to_string(),
+            ..message(7, 0, "", false, false)
+        };
+        let csv = to_csv(&[&message]);
+        assert!(csv.contains("\"'=HYPERLINK(\"\"http://evil.example\"\")\""));
+        assert!(csv.contains("\"'-2+3\",\"'@SUM(A1)\""));
+    }
+}
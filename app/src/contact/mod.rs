@@ -0,0 +1,73 @@
+// contact/mod.rs
+// what the contact form sends and where, shared by the page and the local mock server (src/bin/mock_contact.rs)
+pub mod attachments;
+pub mod backend;
+pub mod captcha;
+pub mod error;
+pub mod inbox;
+pub mod multipart;
+pub mod outbox;
+pub mod spam;
+pub mod validate;
+
+use serde::{Deserialize, Serialize};
+
+use backend::BackendConfig;
+use captcha::{CaptchaConfig, Proof};
+use spam::SpamRules;
+
+// deployed config, and a gitignored override the mock server writes for local testing
+pub const CONFIG_PATH: &str = "/static/contact/backend.json";
+pub const LOCAL_CONFIG_PATH: &str = "/static/contact/backend.local.json";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub message: String,
+    #[serde(rename = "g-recaptcha-response", default, skip_serializing_if = "String::is_empty")]
+    pub recaptcha_response: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_of_work: Option<Proof>,
+    // a field hidden from people, only bots fill it in
+    #[serde(rename = "website", default, skip_serializing_if = "String::is_empty")]
+    pub honeypot: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_seconds: Option<u64>,              // how long the form was open before sending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_token: Option<String>,             // from backends that time the form themselves, see spam::FormToken
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContactConfig {
+    pub backend: BackendConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captcha: Option<CaptchaConfig>,         // none for backends that don't check one, like the mock server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_email: Option<String>,         // offered as a mailto: link when the backend can't be reached
+    #[serde(default)]
+    pub spam: SpamRules,                        // what the form holds itself to, the receiving end has its own
+}
+
+impl Default for ContactConfig {
+    // what the site used before the backend was configurable
+    fn default() -> ContactConfig {
+        ContactConfig {
+            backend: BackendConfig::AppsScript {
+                url: "https://script.google.com/macros/s/AKfycbwLckDBcah084esScg4oIG0IvmgCb_KPfsjPS979BxWQj8fFVvP6Ia_AF2gbOUHgWgajw/exec".to_string(),
+            },
+            captcha: Some(CaptchaConfig::Recaptcha { site_key: "6LfHdcsrAAAAAA4ndXu6pT_KvO1sdOxdPIRX3q12".to_string() }),
+            fallback_email: None,
+            spam: SpamRules::default(),
+        }
+    }
+}
+
+impl ContactConfig {
+    // whether the outbox can send a queued message again on its own: a recaptcha token is good for one try
+    // and only the visitor can get another, a proof of work is fetched and solved again on every try
+    pub fn retries_unattended(&self) -> bool {
+        !matches!(self.captcha, Some(CaptchaConfig::Recaptcha { .. }))
+    }
+}
@@ -0,0 +1,123 @@
+// contact/multipart.rs
+// multipart/form-data (rfc 7578), the browser writes it for the form's attachments and the mock server reads it back
+#[derive(Clone, Debug, PartialEq)]
+pub struct Part {
+    pub name: String,
+    pub filename: Option<String>,
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Part {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.data).into_owned()
+    }
+}
+
+// the boundary parameter of a multipart/form-data content type
+pub fn boundary(content_type: &str) -> Option<String> {
+    let (kind, parameters) = content_type.split_once(';')?;
+    if !kind.trim().eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    parameters.split(';').find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        name.trim().eq_ignore_ascii_case("boundary").then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack.get(from..)?.windows(needle.len()).position(|window| window == needle).map(|at| at + from)
+}
+
+// name="..." out of a content-disposition header, quotes and all
+fn disposition_parameter(header: &str, parameter: &str) -> Option<String> {
+    header.split(';').skip(1).find_map(|part| {
+        let (name, value) = part.split_once('=')?;
+        name.trim().eq_ignore_ascii_case(parameter).then(|| value.trim().trim_matches('"').replace("\\\"", "\""))
+    })
+}
+
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, String> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut position = find(body, &delimiter, 0).ok_or("no boundary in the body")? + delimiter.len();
+    let mut parts = Vec::new();
+
+    loop {
+        // "--" after a delimiter ends the body
+        if body.get(position..position + 2) == Some(b"--") {
+            return Ok(parts);
+        }
+        let headers_start = find(body, b"\r\n", position).ok_or("truncated part")? + 2;
+        let headers_end = find(body, b"\r\n\r\n", headers_start).ok_or("part without a blank line after its headers")?;
+        let data_start = headers_end + 4;
+        let next = find(body, &[b"\r\n".as_slice(), &delimiter].concat(), data_start).ok_or("part without a closing boundary")?;
+
+        let headers = String::from_utf8_lossy(&body[headers_start..headers_end]);
+        let mut part = Part { name: String::new(), filename: None, content_type: None, data: body[data_start..next].to_vec() };
+        for header in headers.split("\r\n") {
+            let Some((name, value)) = header.split_once(':') else { continue };
+            if name.trim().eq_ignore_ascii_case("content-disposition") {
+                part.name = disposition_parameter(value, "name").unwrap_or_default();
+                part.filename = disposition_parameter(value, "filename");
+            } else if name.trim().eq_ignore_ascii_case("content-type") {
+                part.content_type = Some(value.trim().to_string());
+            }
+        }
+        if part.name.is_empty() {
+            return Err("part without a name".to_string());
+        }
+        parts.push(part);
+        position = next + 2 + delimiter.len();
+    }
+}
+
+// what a browser sends for a FormData, for tests and scripted submissions
+pub fn encode(parts: &[Part], boundary: &str) -> Vec<u8> {
+    let mut body = Vec::new();
+    for part in parts {
+        body.extend_from_slice(format!("--{}\r\nContent-Disposition: form-data; name=\"{}\"", boundary, part.name).as_bytes());
+        if let Some(filename) = &part.filename {
+            body.extend_from_slice(format!("; filename=\"{}\"", filename.replace('"', "\\\"")).as_bytes());
+        }
+        if let Some(content_type) = &part.content_type {
+            body.extend_from_slice(format!("\r\nContent-Type: {}", content_type).as_bytes());
+        }
+        body.extend_from_slice(b"\r\n\r\n");
+        body.extend_from_slice(&part.data);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_boundary() {
+        assert_eq!(boundary("multipart/form-data; boundary=----WebKitFormBoundaryx1"), Some("----WebKitFormBoundaryx1".to_string()));
+        assert_eq!(boundary("Multipart/Form-Data; charset=utf-8; boundary=\"a b\""), Some("a b".to_string()));
+        assert_eq!(boundary("application/json"), None);
+    }
+
+    #[test]
+    fn round_trips_fields_and_files() {
+        let parts = vec![
+            Part { name: "message".to_string(), filename: None, content_type: None, data: "line\r\nbreak".into() },
+            Part {
+                name: "attachment".to_string(),
+                filename: Some("MAP \"07\".wad".to_string()),
+                content_type: Some("application/octet-stream".to_string()),
+                data: vec![b'P', b'W', b'A', b'D', 0, 0xff, b'\r', b'\n', b'-', b'-'],
+            },
+        ];
+        let body = encode(&parts, "xyz");
+        assert_eq!(parse(&body, "xyz"), Ok(parts.clone()));
+        assert_eq!(parts[0].text(), "line\r\nbreak");
+
+        assert!(parse(b"--xyz\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nno end", "xyz").is_err());
+        assert!(parse(b"nothing here", "xyz").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+// contact/outbox.rs
+// messages that couldn't be sent yet, kept in localStorage with the form's draft until the backend answers
+use serde::{Deserialize, Serialize};
+
+use crate::contact::Submission;
+
+pub const DRAFT_KEY: &str = "contact_draft";
+pub const OUTBOX_KEY: &str = "contact_outbox";
+
+// first retry after 5 seconds, doubling up to 10 minutes
+const FIRST_RETRY_MS: f64 = 5_000.0;
+const MAX_RETRY_MS: f64 = 10.0 * 60.0 * 1000.0;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum QueueStatus {
+    Waiting,
+    NeedsCaptcha,       // due, but the captcha needs the visitor again (recaptcha) before it can go
+    Sending,
+    Rejected(String),   // the backend answered no, retrying won't change that
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    pub id: u64,
+    pub submission: Submission,
+    pub queued: f64,        // js milliseconds, like everything else here
+    pub attempts: u32,
+    pub next_try: f64,
+    pub last_error: Option<String>,
+    pub status: QueueStatus,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Outbox {
+    pub messages: Vec<QueuedMessage>,
+}
+
+pub fn backoff(attempts: u32) -> f64 {
+    (FIRST_RETRY_MS * 2f64.powi(attempts.saturating_sub(1).min(16) as i32)).min(MAX_RETRY_MS)
+}
+
+impl Outbox {
+    // captcha answers are single use and long expired by the retry, so they aren't kept
+    pub fn push(&mut self, submission: Submission, now: f64, error: Option<String>) -> u64 {
+        let id = self.messages.iter().map(|message| message.id + 1).max().unwrap_or(1);
+        let attempts = u32::from(error.is_some());
+        self.messages.push(QueuedMessage {
+            id,
+            submission: Submission { recaptcha_response: String::new(), proof_of_work: None, ..submission },
+            queued: now,
+            attempts,
+            next_try: now + if attempts == 0 { 0.0 } else { backoff(attempts) },
+            last_error: error,
+            status: QueueStatus::Waiting,
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<QueuedMessage> {
+        let index = self.messages.iter().position(|message| message.id == id)?;
+        Some(self.messages.remove(index))
+    }
+
+    // the waiting messages whose retry is due, marked as sending
+    pub fn take_due(&mut self, now: f64) -> Vec<QueuedMessage> {
+        self.messages
+            .iter_mut()
+            .filter(|message| message.status == QueueStatus::Waiting && message.next_try <= now)
+            .map(|message| {
+                message.status = QueueStatus::Sending;
+                message.clone()
+            })
+            .collect()
+    }
+
+    pub fn needs_captcha(&mut self, id: u64) {
+        if let Some(message) = self.messages.iter_mut().find(|message| message.id == id) {
+            message.status = QueueStatus::NeedsCaptcha;
+        }
+    }
+
+    // one waiting on the captcha, marked as sending now the visitor has answered it
+    pub fn take_answered(&mut self, id: u64) -> Option<QueuedMessage> {
+        let message = self.messages.iter_mut().find(|message| message.id == id && message.status == QueueStatus::NeedsCaptcha)?;
+        message.status = QueueStatus::Sending;
+        Some(message.clone())
+    }
+
+    pub fn retry_later(&mut self, id: u64, now: f64, error: String) {
+        if let Some(message) = self.messages.iter_mut().find(|message| message.id == id) {
+            message.attempts += 1;
+            message.next_try = now + backoff(message.attempts);
+            message.last_error = Some(error);
+            message.status = QueueStatus::Waiting;
+        }
+    }
+
+    pub fn reject(&mut self, id: u64, error: String) {
+        if let Some(message) = self.messages.iter_mut().find(|message| message.id == id) {
+            message.status = QueueStatus::Rejected(error);
+        }
+    }
+
+    // back online, no point waiting out the backoff
+    pub fn wake(&mut self, now: f64) {
+        for message in self.messages.iter_mut().filter(|message| message.status == QueueStatus::Waiting) {
+            message.next_try = now;
+        }
+    }
+
+    // a page closed mid-send never heard back, so those go again
+    pub fn interrupted(&mut self) {
+        for message in self.messages.iter_mut().filter(|message| message.status == QueueStatus::Sending) {
+            message.status = QueueStatus::Waiting;
+        }
+    }
+
+    pub fn next_try(&self) -> Option<f64> {
+        self.messages
+            .iter()
+            .filter(|message| message.status == QueueStatus::Waiting)
+            .map(|message| message.next_try)
+            .min_by(f64::total_cmp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(message: &str) -> Submission {
+        Submission {
+            name: "Doom Guy".to_string(),
+            email: "guy@example.com".to_string(),
+            message: message.to_string(),
+            recaptcha_response: "token".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn backs_off_exponentially_up_to_a_cap() {
+        assert_eq!(backoff(1), 5_000.0);
+        assert_eq!(backoff(2), 10_000.0);
+        assert_eq!(backoff(4), 40_000.0);
+        assert_eq!(backoff(20), MAX_RETRY_MS);
+        assert_eq!(backoff(u32::MAX), MAX_RETRY_MS);
+    }
+
+    #[test]
+    fn retries_waiting_messages_when_due() {
+        let mut outbox = Outbox::default();
+        let offline = outbox.push(submission("queued offline"), 0.0, None);
+        let failed = outbox.push(submission("failed once"), 0.0, Some("Network error".to_string()));
+        assert_ne!(offline, failed);
+        assert_eq!(outbox.messages[0].submission.recaptcha_response, "");
+        assert_eq!(outbox.next_try(), Some(0.0));
+
+        let due = outbox.take_due(1_000.0);
+        assert_eq!(due.iter().map(|message| message.id).collect::<Vec<_>>(), [offline]);
+        assert!(outbox.take_due(1_000.0).is_empty(), "sending twice");
+
+        outbox.retry_later(offline, 1_000.0, "still offline".to_string());
+        assert_eq!(outbox.messages[0].attempts, 1);
+        assert_eq!(outbox.next_try(), Some(5_000.0));
+
+        outbox.wake(2_000.0);
+        assert_eq!(outbox.take_due(2_000.0).len(), 2);
+        outbox.interrupted();
+        assert_eq!(outbox.next_try(), Some(2_000.0));
+
+        // a recaptcha site can't resend on its own, the visitor has to answer it again first
+        assert!(outbox.take_answered(failed).is_none());
+        outbox.take_due(2_000.0);
+        outbox.needs_captcha(failed);
+        assert_eq!(outbox.messages[1].status, QueueStatus::NeedsCaptcha);
+        assert_eq!(outbox.take_answered(failed).map(|message| message.status), Some(QueueStatus::Sending));
+
+        outbox.reject(failed, "bad email".to_string());
+        assert_eq!(outbox.messages[1].status, QueueStatus::Rejected("bad email".to_string()));
+        assert_eq!(outbox.remove(offline).map(|message| message.submission.message), Some("queued offline".to_string()));
+        assert_eq!(outbox.next_try(), None);
+        assert_eq!(outbox.remove(offline), None);
+    }
+}
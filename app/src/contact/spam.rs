@@ -0,0 +1,160 @@
+// contact/spam.rs
+// cheap tells of a bot on top of the captcha: checked by the form before sending, and again by whatever
+// receives the message since a bot doesn't need the form to post
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::contact::Submission;
+
+// what the visitor sees, deliberately not saying which rule it was
+pub const REJECTION: &str = "Your message couldn't be sent right now. Please try again in a little while, or email me directly.";
+// servers tag spam refusals with this code so the form shows REJECTION instead of an http error
+pub const SPAM_CODE: &str = "spam";
+
+// what a server that measures the time to submit itself hands the form when it's opened, see
+// ContactBackend::form_token_url; the form sends it back as Submission::form_token
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FormToken {
+    pub token: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpamRules {
+    pub min_seconds: u64,           // from opening the form to sending, people don't type that fast
+    pub max_links: usize,
+    pub max_messages: usize,        // per window: per session in the browser, per address on a server
+    pub window_seconds: u64,
+}
+
+impl Default for SpamRules {
+    fn default() -> SpamRules {
+        SpamRules { min_seconds: 3, max_links: 3, max_messages: 3, window_seconds: 600 }
+    }
+}
+
+// why a message was refused, for logs only
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpamReason {
+    Honeypot,
+    TooFast { seconds: u64 },
+    NoFormToken,                    // missing, expired or never issued
+    TooManyLinks { count: usize },
+    RateLimited,
+}
+
+impl fmt::Display for SpamReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpamReason::Honeypot => write!(f, "filled in the hidden field"),
+            SpamReason::TooFast { seconds } => write!(f, "sent {}s after opening the form", seconds),
+            SpamReason::NoFormToken => write!(f, "no form token of ours"),
+            SpamReason::TooManyLinks { count } => write!(f, "{} links", count),
+            SpamReason::RateLimited => write!(f, "too many messages"),
+        }
+    }
+}
+
+// words that are, or start, a link: http(s) urls, bare www. hosts and bbcode
+pub fn count_links(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| {
+            let word = word.to_ascii_lowercase();
+            word.contains("http://") || word.contains("https://") || word.starts_with("www.") || word.contains("[url")
+        })
+        .count()
+}
+
+impl SpamRules {
+    // everything about the message itself; the timing is check_time's since only the receiving end knows
+    // whether to believe form_seconds, and the rate limit needs to know who's sending, see check_rate
+    pub fn check(&self, submission: &Submission) -> Result<(), SpamReason> {
+        if !submission.honeypot.trim().is_empty() {
+            return Err(SpamReason::Honeypot);
+        }
+        let count = [&submission.name, &submission.subject, &submission.message].iter().map(|text| count_links(text)).sum();
+        if count > self.max_links {
+            return Err(SpamReason::TooManyLinks { count });
+        }
+        Ok(())
+    }
+
+    // seconds from opening the form to sending; none means the post didn't come from the form
+    pub fn check_time(&self, seconds: Option<u64>) -> Result<(), SpamReason> {
+        let seconds = seconds.unwrap_or(0);
+        if seconds < self.min_seconds {
+            return Err(SpamReason::TooFast { seconds });
+        }
+        Ok(())
+    }
+
+    // sent holds the unix seconds of the same sender's earlier messages; counts this one when it's allowed
+    pub fn check_rate(&self, sent: &mut Vec<u64>, now: u64) -> Result<(), SpamReason> {
+        sent.retain(|&at| now.saturating_sub(at) < self.window_seconds);
+        if sent.len() >= self.max_messages {
+            return Err(SpamReason::RateLimited);
+        }
+        sent.push(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(message: &str, form_seconds: u64) -> Submission {
+        Submission {
+            name: "Doom Guy".to_string(),
+            email: "guy@example.com".to_string(),
+            message: message.to_string(),
+            form_seconds: Some(form_seconds),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_links() {
+        assert_eq!(count_links("see https://example.com and www.example.org/x"), 2);
+        assert_eq!(count_links("(HTTP://A.B) [url=x]cheap[/url]"), 2);
+        assert_eq!(count_links("I use www and http daily, e.g. github.com"), 0);
+    }
+
+    #[test]
+    fn catches_bots_and_lets_people_through() {
+        let rules = SpamRules::default();
+        assert_eq!(rules.check(&submission("Loved MAP07, see https://example.com", 40)), Ok(()));
+
+        let filled = Submission { honeypot: "http://spam.example".to_string(), ..submission("hi", 40) };
+        assert_eq!(rules.check(&filled), Err(SpamReason::Honeypot));
+        assert_eq!(rules.check_time(Some(40)), Ok(()));
+        assert_eq!(rules.check_time(Some(1)), Err(SpamReason::TooFast { seconds: 1 }));
+        assert_eq!(rules.check_time(None), Err(SpamReason::TooFast { seconds: 0 }));
+        let links = submission("https://a.example https://b.example www.c.example https://d.example", 40);
+        assert_eq!(rules.check(&links), Err(SpamReason::TooManyLinks { count: 4 }));
+
+        let lenient = SpamRules { min_seconds: 0, max_links: 10, ..rules };
+        assert_eq!(lenient.check(&submission("https://a.example https://b.example www.c.example https://d.example", 0)), Ok(()));
+        assert_eq!(lenient.check_time(None), Ok(()));
+    }
+
+    #[test]
+    fn limits_the_rate_over_a_sliding_window() {
+        let rules = SpamRules { max_messages: 2, window_seconds: 60, ..SpamRules::default() };
+        let mut sent = Vec::new();
+        assert_eq!(rules.check_rate(&mut sent, 1000), Ok(()));
+        assert_eq!(rules.check_rate(&mut sent, 1010), Ok(()));
+        assert_eq!(rules.check_rate(&mut sent, 1020), Err(SpamReason::RateLimited));
+        assert_eq!(sent, vec![1000, 1010]);
+        // the first one has left the window
+        assert_eq!(rules.check_rate(&mut sent, 1060), Ok(()));
+        assert_eq!(sent, vec![1010, 1060]);
+    }
+
+    #[test]
+    fn reads_partial_rules() {
+        let rules: SpamRules = serde_json::from_str(r#"{"max_links": 0}"#).unwrap();
+        assert_eq!(rules, SpamRules { max_links: 0, ..SpamRules::default() });
+    }
+}
@@ -0,0 +1,190 @@
+// contact/validate.rs
+// what makes a submission acceptable, shared by the form (live, per field) and the server (all at once)
+use std::fmt;
+
+use crate::contact::error::ContactError;
+use crate::contact::Submission;
+
+pub const MAX_NAME: usize = 100;
+pub const MAX_EMAIL: usize = 254;
+pub const MAX_SUBJECT: usize = 200;
+pub const MAX_MESSAGE: usize = 5000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    Name,
+    Email,
+    Subject,
+    Message,
+}
+
+impl Field {
+    pub const ALL: [Field; 4] = [Field::Name, Field::Email, Field::Subject, Field::Message];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::Name => "Name",
+            Field::Email => "Email",
+            Field::Subject => "Subject",
+            Field::Message => "Message",
+        }
+    }
+
+    pub fn max(self) -> usize {
+        match self {
+            Field::Name => MAX_NAME,
+            Field::Email => MAX_EMAIL,
+            Field::Subject => MAX_SUBJECT,
+            Field::Message => MAX_MESSAGE,
+        }
+    }
+
+    pub fn required(self) -> bool {
+        self != Field::Subject
+    }
+
+    pub fn value(self, submission: &Submission) -> &str {
+        match self {
+            Field::Name => &submission.name,
+            Field::Email => &submission.email,
+            Field::Subject => &submission.subject,
+            Field::Message => &submission.message,
+        }
+    }
+
+    pub fn set(self, submission: &mut Submission, value: String) {
+        match self {
+            Field::Name => submission.name = value,
+            Field::Email => submission.email = value,
+            Field::Subject => submission.subject = value,
+            Field::Message => submission.message = value,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldError {
+    Required,
+    TooLong { max: usize },
+    InvalidEmail,
+    Disallowed(&'static str),   // what was found, e.g. "line breaks"
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::Required => write!(f, "is required"),
+            FieldError::TooLong { max } => write!(f, "is longer than {} characters", max),
+            FieldError::InvalidEmail => write!(f, "isn't a valid address"),
+            FieldError::Disallowed(what) => write!(f, "can't contain {}", what),
+        }
+    }
+}
+
+// what the counters show, and what the limits count: characters of the trimmed text
+pub fn length(text: &str) -> usize {
+    text.trim().chars().count()
+}
+
+pub fn check_field(field: Field, text: &str) -> Result<(), FieldError> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return if field.required() { Err(FieldError::Required) } else { Ok(()) };
+    }
+    if length(text) > field.max() {
+        return Err(FieldError::TooLong { max: field.max() });
+    }
+    // a line break in a one-line field ends up in a mail header somewhere down the line
+    if field != Field::Message && trimmed.contains(['\r', '\n']) {
+        return Err(FieldError::Disallowed("line breaks"));
+    }
+    if trimmed.chars().any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')) {
+        return Err(FieldError::Disallowed("control characters"));
+    }
+    if field == Field::Email && !plausible_email(trimmed) {
+        return Err(FieldError::InvalidEmail);
+    }
+    Ok(())
+}
+
+// every field that's wrong, in form order
+pub fn field_errors(submission: &Submission) -> Vec<(Field, FieldError)> {
+    Field::ALL
+        .into_iter()
+        .filter_map(|field| check_field(field, field.value(submission)).err().map(|error| (field, error)))
+        .collect()
+}
+
+pub fn validate(submission: &Submission) -> Result<(), ContactError> {
+    match field_errors(submission).into_iter().next() {
+        Some((field, error)) => Err(ContactError::Invalid { field, error }),
+        None => Ok(()),
+    }
+}
+
+// one @, something before it, a dotted domain after it, no spaces and nothing that would split it into
+// more addresses or headers once it's in a mailto: link or a To: line
+pub fn plausible_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else { return false };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.split('.').count() >= 2
+        && domain.split('.').all(|part| !part.is_empty())
+        && !email.chars().any(|c| c.is_whitespace() || matches!(c, '?' | '&' | '#' | '<' | '>' | ',' | ';'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(email: &str, message: &str) -> Submission {
+        Submission { name: "Doom Guy".to_string(), email: email.to_string(), message: message.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn checks_required_fields_and_limits() {
+        assert_eq!(validate(&submission("guy@example.com", "hi")), Ok(()));
+        assert_eq!(
+            validate(&submission("guy@example.com", "  ")),
+            Err(ContactError::Invalid { field: Field::Message, error: FieldError::Required })
+        );
+        assert_eq!(
+            check_field(Field::Message, &"a".repeat(MAX_MESSAGE + 1)),
+            Err(FieldError::TooLong { max: MAX_MESSAGE })
+        );
+        assert_eq!(check_field(Field::Message, &format!("  {}  ", "a".repeat(MAX_MESSAGE))), Ok(()));
+        assert_eq!(check_field(Field::Subject, ""), Ok(()));
+        for email in ["guy", "guy@", "@example.com", "guy@example", "guy@example..com", "g uy@example.com", "a@b@c.com"] {
+            assert_eq!(check_field(Field::Email, email), Err(FieldError::InvalidEmail), "{}", email);
+        }
+        let smuggled = ["guy@example.com?cc=all@example.com", "guy@example.com&body=x", "guy@example.com#x", "<guy@example.com>"];
+        for email in smuggled.into_iter().chain(["guy@example.com,all@example.com", "a;b@example.com"]) {
+            assert_eq!(check_field(Field::Email, email), Err(FieldError::InvalidEmail), "{}", email);
+        }
+        assert_eq!(check_field(Field::Email, "doom.guy+uac@mars-base.example.com"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_header_breaking_content() {
+        assert_eq!(check_field(Field::Subject, "hi\r\nBcc: everyone@example.com"), Err(FieldError::Disallowed("line breaks")));
+        assert_eq!(check_field(Field::Name, "Doom\u{0}Guy"), Err(FieldError::Disallowed("control characters")));
+        assert_eq!(check_field(Field::Message, "line one\nline two\n\tindented"), Ok(()));
+        assert_eq!(check_field(Field::Message, "bell\u{7}"), Err(FieldError::Disallowed("control characters")));
+    }
+
+    #[test]
+    fn lists_every_field_error_in_order() {
+        let empty = Submission::default();
+        let fields: Vec<Field> = field_errors(&empty).into_iter().map(|(field, _)| field).collect();
+        assert_eq!(fields, [Field::Name, Field::Email, Field::Message]);
+        assert_eq!(
+            ContactError::Invalid { field: Field::Email, error: FieldError::InvalidEmail }.to_string(),
+            "Email isn't a valid address"
+        );
+
+        let mut submission = empty;
+        Field::Subject.set(&mut submission, "Map 07".to_string());
+        assert_eq!(Field::Subject.value(&submission), "Map 07");
+        assert_eq!(length("  Ünïcode  "), 7);
+    }
+}
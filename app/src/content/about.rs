@@ -0,0 +1,116 @@
+// content/about.rs
+use crate::content::{ExperienceItem, Link, Profile, Skill};
+
+pub fn experience() -> Vec<ExperienceItem> {
+    vec![
+        ExperienceItem {
+            title: "Teaching Assistant".to_string(),
+            institution: "University of Gothenburg".to_string(),
+            date: "2024 - 2025".to_string(),
+            description: Some(vec![
+                "TA for Software Architecture, Requirements Engineering and Systems Development.".to_string(),
+                "Led TA meetings and workshops with students, provided in-person and remote guidance.".to_string(),
+                "Provided support and feedback to professors concerning assignments and course materials.".to_string(),
+                "Graded student assignments and exams.".to_string()]),
+            icon: "/static/about/education/U_GOTH.png".to_string(),
+        },
+        ExperienceItem {
+            title: "Compliance Officer".to_string(),
+            institution: "Huawei Technologies S.A.".to_string(),
+            date: "2019 - 2022".to_string(),
+            description: Some(vec![
+                "Legal compliance assessment and risk analysis for all areas of company operations in Athens and Cyprus offices (specialization in Data Protection (GDPR) and Cybersecurity)".to_string(),
+                "Legal support to regional offices in Albania, Northern Macedonia and Bulgaria.".to_string(),
+                "Led training sessions for all regional offices on sensitive areas (personal data protection, cybersecurity, anti-bribery).".to_string(),]),
+            icon: "/static/about/experience/HUAWEI.png".to_string(),
+        },
+        ExperienceItem {
+            title: "Intern at Academy of European Public Law".to_string(),
+            institution: "European Public Law Organization (EPLO)".to_string(),
+            date: "2019".to_string(),
+            description: Some(vec![
+                "Assisted in the design, organization and implementation of European and Public Law university programs and summer exchange programs".to_string(),
+                "Assisted in the administration of the Department of Education.".to_string(),
+                "Drafted reports on EPLO activities and conducted research for various other departments.".to_string(),
+                ]),
+            icon: "/static/about/experience/EPLO.png".to_string(),
+        },
+        ExperienceItem {
+            title: "Intern at Greek National Desk".to_string(),
+            institution: "Eurojust".to_string(),
+            date: "2019 - 2020".to_string(),
+            description: Some(vec![
+                "Assisted in administrative functions of the Greek national desk.".to_string(),
+                "Composed external and internal communications towards national authorities and national desks.".to_string(),
+                "Legal case file and database management.".to_string(),
+                    ]),
+            icon: "/static/about/experience/EUROJUST.png".to_string(),
+        },
+    ]
+}
+
+pub fn education() -> Vec<ExperienceItem> {
+    vec![
+        ExperienceItem {
+            title: "Software Engineering & Management (BSc)".to_string(),
+            institution: "University of Gothenburg, SE".to_string(),
+            date: "2022 - 2025".to_string(),
+            description: None,
+            icon: "/static/about/education/U_GOTH.png".to_string(),
+        },
+        ExperienceItem {
+            title: "European Law (LLM)".to_string(),
+            institution: "Leiden University, NL".to_string(),
+            date: "2015 - 2016".to_string(),
+            description: None,
+            icon: "/static/about/education/U_LEID.png".to_string(),
+        },
+        ExperienceItem {
+            title: "Law (LLB)".to_string(),
+            institution: "University of Reading, UK".to_string(),
+            date: "2012 - 2015".to_string(),
+            description: None,
+            icon: "/static/about/education/U_READ.png".to_string(),
+        },
+    ]
+}
+
+pub fn skills() -> Vec<Skill> {
+    let skill = |name: &str, icon: &str, color: &str| Skill {
+        name: name.to_string(),
+        icon: format!("/static/about/technologies/{}", icon),
+        color: color.to_string(),
+    };
+    vec![
+        skill("Python", "PYTHON.svg", "bg-orange-500"),
+        skill("Java", "JAVA.svg", "bg-blue-500"),
+        skill("C++", "CPP.svg", "bg-blue-600"),
+        skill("Cmake", "CMAKE.svg", "bg-yellow-600"),
+        skill("Typescript", "TS.svg", "bg-cyan-500"),
+        skill("SQL", "SQL.svg", "bg-purple-500"),
+        skill("Docker", "DOCKER.svg", "bg-yellow-600"),
+        skill("Yew", "YEW.svg", "bg-blue-700"),
+        skill("Vue", "VUE.svg", "bg-yellow-500"),
+        skill("React", "REACT.svg", "bg-yellow-600"),
+        skill("Svelte", "SVELTE.svg", "bg-blue-800"),
+        skill("Postman", "POSTMAN.svg", "bg-yellow-600"),
+        skill("TensorFlow", "TENSORFLOW.svg", "bg-yellow-600"),
+        skill("Arduino", "ARDUINO.svg", "bg-yellow-600"),
+    ]
+}
+
+// the professional half of the about page's introduction
+pub fn profile() -> Profile {
+    Profile {
+        name: "Konstantinos Rokanas".to_string(),
+        summary: vec![
+            "I'm a recent software engineering graduate eager to begin a career in tech.".to_string(),
+            "I want to build intuitive software solutions that make your jobs and lives easier, saving you time and effort. Currently learning Rust, Solidity smart contracts and agentic AI implementations.".to_string(),
+            "Formerly a legal professional with a focus on personal data and cybersecurity policy compliance and experience both in international organizations and in the private sector.".to_string(),
+        ],
+        links: vec![
+            Link { label: "GitHub".to_string(), url: "https://github.com/rokanas".to_string() },
+            Link { label: "LinkedIn".to_string(), url: "https://www.linkedin.com/in/konstantinos-rokanas-1ab1a113a/".to_string() },
+        ],
+    }
+}
@@ -0,0 +1,91 @@
+// content/doom_projects.rs
+use crate::content::DoomProject;
+use crate::media::Media;
+
+pub fn doom_projects() -> Vec<DoomProject> {
+    vec![
+        DoomProject {
+            title: "Cathedral of Charybdis".to_string(),
+            description: "A dark and atmospheric map. All are swallowed by the shadow of the cathedral. Can you resist the evil cult of Charybdis?".to_string(),
+            image_src: "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_1.png".to_string(),
+            image_alt: Some("Cathedral of Charybdis".to_string()),
+            gallery: vec![
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_2.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_3.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_4.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_5.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_6.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_7.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_8.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_9.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_10.png"),
+                Media::image("/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_11.png"),
+            ],
+        },
+        DoomProject {
+            title: "Jammy".to_string(),
+            description: "A gimmicky challenge map involving a lot of scripted terrain transformation and light slaughter. Inspired by Doom64 MAP19. Push through and don't stand still!".to_string(),
+            image_src: "/static/doom_projects/jammy/jammy_1.png".to_string(),
+            image_alt: Some("Jammy".to_string()),
+            gallery: vec![
+                Media::image("/static/doom_projects/jammy/jammy_2.png"),
+                Media::image("/static/doom_projects/jammy/jammy_3.png"),
+                Media::image("/static/doom_projects/jammy/jammy_4.png"),
+                Media::image("/static/doom_projects/jammy/jammy_5.png"),
+                Media::image("/static/doom_projects/jammy/jammy_6.png"),
+            ],
+
+        },
+        DoomProject {
+            title: "Whispers of Change".to_string(),
+            description: "A short and atmospheric map with story elements and light puzzles. Co-authored by Erik Lindstrand and made in 1 day for Chalmers March GameJam 2024.".to_string(),
+            image_src: "/static/doom_projects/whispers_of_change/whispers_of_change_1.png".to_string(),
+            image_alt: Some("Whispers of Change".to_string()),
+            gallery: vec![
+                Media::image("/static/doom_projects/whispers_of_change/whispers_of_change_2.png"),
+                Media::image("/static/doom_projects/whispers_of_change/whispers_of_change_3.png"),
+                Media::image("/static/doom_projects/whispers_of_change/whispers_of_change_4.png"),
+                Media::image("/static/doom_projects/whispers_of_change/whispers_of_change_5.png"),
+            ],
+        },
+        DoomProject {
+            title: "SWEDEN".to_string(),
+            description: "An adventure map that has nothing to do with Sweden. Explore the demonic presence aroused in the ruins by human interference.".to_string(),
+            image_src: "/static/doom_projects/sweden/sweden_1.png".to_string(),
+            image_alt: Some("SWEDEN".to_string()),
+            gallery: vec![
+                Media::image("/static/doom_projects/sweden/sweden_2.png"),
+                Media::image("/static/doom_projects/sweden/sweden_3.png"),
+                Media::image("/static/doom_projects/sweden/sweden_4.png"),
+                Media::image("/static/doom_projects/sweden/sweden_5.png"),
+                Media::image("/static/doom_projects/sweden/sweden_6.png"),
+                Media::image("/static/doom_projects/sweden/sweden_7.png"),
+                Media::image("/static/doom_projects/sweden/sweden_8.png"),
+            ],
+            
+        },
+        DoomProject {
+            title: "ΣΣΑΣ".to_string(),
+            description: "A map that is definitely not inspired by a real military base. Discover the hellish secrets buried beneath military inefficiency and bureaucracy!".to_string(),
+            image_src: "/static/doom_projects/ssas/ssas_1.png".to_string(),
+            image_alt: Some("ΣΣΑΣ".to_string()),
+            gallery: vec![
+                Media::image("/static/doom_projects/ssas/ssas_2.png"),
+                Media::image("/static/doom_projects/ssas/ssas_3.png"),
+                Media::image("/static/doom_projects/ssas/ssas_4.png"),
+                Media::image("/static/doom_projects/ssas/ssas_5.png"),
+                Media::image("/static/doom_projects/ssas/ssas_6.png"),
+                Media::image("/static/doom_projects/ssas/ssas_7.png"),
+                Media::image("/static/doom_projects/ssas/ssas_8.png"),
+                Media::image("/static/doom_projects/ssas/ssas_9.png"),
+                Media::image("/static/doom_projects/ssas/ssas_10.png"),
+                Media::image("/static/doom_projects/ssas/ssas_11.png"),
+                Media::image("/static/doom_projects/ssas/ssas_12.png"),
+                Media::image("/static/doom_projects/ssas/ssas_13.png"),
+                Media::image("/static/doom_projects/ssas/ssas_14.png"),
+                Media::image("/static/doom_projects/ssas/ssas_15.png"),
+                Media::image("/static/doom_projects/ssas/ssas_16.png"),
+            ],
+        },
+    ]
+}
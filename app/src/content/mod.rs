@@ -0,0 +1,90 @@
+// content/mod.rs
+// the site's projects, maps and cv entries: built into the app, and served from a database by the server crate
+pub mod about;
+pub mod doom_projects;
+pub mod projects;
+
+use serde::{Deserialize, Serialize};
+
+use crate::media::Media;
+
+// json endpoints of the server crate, same origin (trunk serve proxies them in development)
+pub const PROJECTS_API: &str = "/api/projects";
+pub const DOOM_PROJECTS_API: &str = "/api/doom-projects";
+pub const EXPERIENCE_API: &str = "/api/experience";
+pub const EDUCATION_API: &str = "/api/education";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    pub color: String,                  // css class e.g. bg-blue-500"
+    #[serde(default)]
+    pub text_color: Option<String>,     // optional text color override
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    pub title: String,
+    pub tags: Vec<Tag>,
+    pub description: String,
+    pub image_src: String,
+    #[serde(default)]
+    pub image_alt: Option<String>,
+    #[serde(default)]
+    pub thumbnail_src: Option<String>,      // palette-mapped card image from doom_thumbnails.json
+    pub github_url: String,
+    #[serde(default)]
+    pub detailed_description: Option<String>,
+    #[serde(default)]
+    pub technologies_used: Vec<String>,
+    #[serde(default)]
+    pub key_features: Vec<String>,
+    #[serde(default)]
+    pub wiki_url: Option<String>,
+    #[serde(default)]
+    pub gallery: Vec<Media>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DoomProject {
+    pub title: String,
+    pub description: String,
+    pub image_src: String,
+    #[serde(default)]
+    pub image_alt: Option<String>,
+    #[serde(default)]
+    pub gallery: Vec<Media>,
+}
+
+// education and experience entries on the about page
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExperienceItem {
+    pub title: String,
+    pub institution: String,
+    pub date: String,
+    #[serde(default)]
+    pub description: Option<Vec<String>>,
+    pub icon: String,
+}
+
+// a technology on the about page and in the cv
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    pub icon: String,
+    pub color: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    pub label: String,
+    pub url: String,
+}
+
+// the cv's header: who, in a few sentences, and where else to look
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub summary: Vec<String>,
+    pub links: Vec<Link>,
+}
@@ -0,0 +1,175 @@
+// cv.rs
+// the cv as a pdf, laid out from the same content the about page and the printable #/cv view show,
+// written by src/bin/cv.rs on every trunk build so the download never drifts from the site
+use crate::content::{self, ExperienceItem, Profile, Skill};
+use crate::pdf::{self, Color, Document, Font};
+
+// where the build puts it, the about page's cv button links here
+pub const PDF_PATH: &str = "/static/derived/KR_CV.pdf";
+
+const MARGIN: f64 = 40.0;
+const RED: Color = (0.863, 0.149, 0.149);       // red-600, the site's accent
+const TEXT: Color = (0.1, 0.1, 0.1);
+const MUTED: Color = (0.4, 0.4, 0.4);
+const LEADING: f64 = 1.35;                      // line height per point of font size
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cv {
+    pub profile: Profile,
+    pub experience: Vec<ExperienceItem>,
+    pub education: Vec<ExperienceItem>,
+    pub skills: Vec<Skill>,
+}
+
+impl Cv {
+    // the content compiled into the app
+    pub fn built_in() -> Cv {
+        Cv {
+            profile: content::about::profile(),
+            experience: content::about::experience(),
+            education: content::about::education(),
+            skills: content::about::skills(),
+        }
+    }
+}
+
+// draws top to bottom, starting a new page whenever the next block wouldn't fit
+struct Writer {
+    document: Document,
+    y: f64,
+}
+
+impl Writer {
+    fn width(&self) -> f64 {
+        self.document.size().0 - 2.0 * MARGIN
+    }
+
+    fn top(&self) -> f64 {
+        self.document.size().1 - MARGIN
+    }
+
+    fn reserve(&mut self, height: f64) {
+        // a block taller than a page starts on a fresh one and breaks line by line from there
+        if self.y - height < MARGIN && self.y < self.top() {
+            self.document.add_page();
+            self.y = self.top();
+        }
+    }
+
+    fn lines(&self, text: &str, indent: f64, font: Font, size: f64) -> Vec<String> {
+        pdf::wrap(text, font, size, self.width() - indent)
+    }
+
+    // wrapped text, the first baseline one line below the current position
+    fn paragraph(&mut self, text: &str, indent: f64, font: Font, size: f64, color: Color) {
+        for line in self.lines(text, indent, font, size) {
+            self.reserve(size * LEADING);
+            self.y -= size * LEADING;
+            self.document.text(MARGIN + indent, self.y, font, size, color, &line);
+        }
+    }
+
+    fn section(&mut self, title: &str) {
+        self.reserve(60.0);     // the heading and at least the start of what's under it
+        self.y -= 18.0;
+        self.paragraph(title, 0.0, Font::Bold, 13.0, RED);
+        self.y -= 4.0;
+        self.document.line((MARGIN, self.y), (MARGIN + self.width(), self.y), 1.0, RED);
+        self.y -= 2.0;
+    }
+
+    fn entry_height(&self, item: &ExperienceItem) -> f64 {
+        let title = self.lines(&item.title, 0.0, Font::Bold, 11.0).len() as f64 * 11.0 * LEADING;
+        let bullets: usize = item.description.iter().flatten().map(|point| self.lines(point, 14.0, Font::Regular, 10.0).len()).sum();
+        10.0 + title + 10.0 * LEADING * (1 + bullets) as f64
+    }
+
+    // title with the dates on the right, institution under it, then the description as bullets
+    fn entry(&mut self, item: &ExperienceItem) {
+        self.reserve(self.entry_height(item));
+        self.y -= 8.0;
+        let date_width = Font::Regular.width(&item.date, 10.0);
+        let title_lines = pdf::wrap(&item.title, Font::Bold, 11.0, self.width() - date_width - 12.0);
+        for (index, line) in title_lines.iter().enumerate() {
+            self.y -= 11.0 * LEADING;
+            self.document.text(MARGIN, self.y, Font::Bold, 11.0, TEXT, line);
+            if index == 0 {
+                self.document.text(MARGIN + self.width() - date_width, self.y, Font::Regular, 10.0, MUTED, &item.date);
+            }
+        }
+        self.paragraph(&item.institution, 0.0, Font::Regular, 10.0, MUTED);
+        for point in item.description.iter().flatten() {
+            let first_line = self.y - 10.0 * LEADING;
+            self.paragraph(point, 14.0, Font::Regular, 10.0, TEXT);
+            // the bullet goes wherever the point's first line ended up, which is a new page if it broke there
+            let y = if self.y > first_line { self.top() - 10.0 * LEADING } else { first_line };
+            self.document.text(MARGIN + 4.0, y, Font::Regular, 10.0, RED, "\u{2022}");
+        }
+    }
+}
+
+pub fn layout(cv: &Cv) -> Document {
+    let mut writer = Writer { document: Document::new(&format!("{} \u{2013} CV", cv.profile.name), pdf::A4), y: 0.0 };
+    writer.y = writer.top();
+
+    writer.paragraph(&cv.profile.name, 0.0, Font::Bold, 24.0, TEXT);
+    writer.y -= 2.0;
+    for link in &cv.profile.links {
+        let text = format!("{}: {}", link.label, link.url);
+        writer.paragraph(&text, 0.0, Font::Regular, 9.0, RED);
+        writer.document.link(MARGIN, writer.y - 2.0, Font::Regular.width(&text, 9.0), 11.0, &link.url);
+    }
+    writer.y -= 6.0;
+    for paragraph in &cv.profile.summary {
+        writer.paragraph(paragraph, 0.0, Font::Regular, 10.0, TEXT);
+        writer.y -= 4.0;
+    }
+
+    writer.section("Experience");
+    for item in &cv.experience {
+        writer.entry(item);
+    }
+    writer.section("Education");
+    for item in &cv.education {
+        writer.entry(item);
+    }
+    writer.section("Technologies");
+    let skills: Vec<&str> = cv.skills.iter().map(|skill| skill.name.as_str()).collect();
+    writer.paragraph(&skills.join(", "), 0.0, Font::Regular, 10.0, TEXT);
+
+    writer.document
+}
+
+pub fn render(cv: &Cv) -> Vec<u8> {
+    layout(cv).to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_the_built_in_content() {
+        let cv = Cv::built_in();
+        let text = String::from_utf8_lossy(&render(&cv)).into_owned();
+        assert!(text.contains("(Konstantinos Rokanas) Tj"));
+        for item in cv.experience.iter().chain(&cv.education) {
+            let title = item.title.replace('(', "\\(").replace(')', "\\)");
+            assert!(text.contains(&format!("({}) Tj", title)), "{} is missing", item.title);
+        }
+        assert!(text.contains("/URI (https://github.com/rokanas)"));
+        assert!(text.contains("TensorFlow"));
+    }
+
+    #[test]
+    fn breaks_long_content_onto_more_pages() {
+        let mut cv = Cv::built_in();
+        let experience = cv.experience.clone();
+        for _ in 0..5 {
+            cv.experience.extend(experience.clone());
+        }
+        let document = layout(&cv);
+        assert!(document.page_count() > 1);
+        assert!(String::from_utf8_lossy(&document.to_bytes()).contains(&format!("/Count {}", document.page_count())));
+    }
+}
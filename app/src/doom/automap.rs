@@ -0,0 +1,229 @@
+// doom/automap.rs
+// top-down geometry for the automap viewer, classified the way the engine colours automap lines
+use serde::{Deserialize, Serialize};
+
+use crate::doom::map::{Linedef, Map};
+use crate::doom::things::{self, Key};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    Wall,           // one-sided, or a secret line pretending to be one
+    FloorStep,      // two-sided with a floor height change
+    CeilingStep,    // two-sided with only a ceiling height change
+    Teleporter,
+    KeyDoor(Key),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThingKind {
+    Player,
+    Monster,
+    Key(Key),
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutomapLine {
+    pub from: [f64; 2],
+    pub to: [f64; 2],
+    pub kind: LineKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutomapThing {
+    pub x: f64,
+    pub y: f64,
+    pub angle: f64,
+    pub kind: ThingKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AutomapMap {
+    pub name: String,
+    pub min: [f64; 2],
+    pub max: [f64; 2],
+    pub lines: Vec<AutomapLine>,
+    pub things: Vec<AutomapThing>,
+}
+
+pub fn automap(map: &Map) -> AutomapMap {
+    let lines: Vec<AutomapLine> = map
+        .linedefs
+        .iter()
+        .filter_map(|line| {
+            let kind = line_kind(map, line)?;
+            let (from, to) = (map.vertices[line.start], map.vertices[line.end]);
+            Some(AutomapLine { from: [from.x, from.y], to: [to.x, to.y], kind })
+        })
+        .collect();
+
+    let things = map
+        .things
+        .iter()
+        .filter(|thing| thing.single_player)
+        .map(|thing| AutomapThing {
+            x: thing.x,
+            y: thing.y,
+            angle: thing.angle,
+            kind: if things::is_player_start(thing.doomednum) {
+                ThingKind::Player
+            } else if things::is_monster(thing.doomednum) || thing.doomednum == things::LOST_SOUL {
+                ThingKind::Monster
+            } else if let Some(key) = things::key_color(thing.doomednum) {
+                ThingKind::Key(key)
+            } else {
+                ThingKind::Other
+            },
+        })
+        .collect();
+
+    // bounds of the drawn lines, so hidden geometry doesn't pad the view
+    let points = lines.iter().flat_map(|line| [line.from, line.to]);
+    let (min, max) = points.fold(([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]), |(min, max), [x, y]| {
+        ([min[0].min(x), min[1].min(y)], [max[0].max(x), max[1].max(y)])
+    });
+    let (min, max) = if lines.is_empty() { ([0.0; 2], [0.0; 2]) } else { (min, max) };
+
+    AutomapMap { name: map.name.clone(), min, max, lines, things }
+}
+
+// none for lines the automap never draws: flagged hidden, or two-sided without a height change
+fn line_kind(map: &Map, line: &Linedef) -> Option<LineKind> {
+    if line.hidden {
+        return None;
+    }
+    let sector = |side: Option<usize>| side.map(|side| &map.sectors[map.sidedefs[side].sector]);
+    let (Some(front), Some(back)) = (sector(line.front), sector(line.back)) else {
+        return Some(LineKind::Wall);
+    };
+    if line.secret {
+        return Some(LineKind::Wall);
+    }
+
+    let hexen = map.hexen_specials();
+    if let Some(key) = if hexen { hexen_door_key(line) } else { door_key(line.special) } {
+        Some(LineKind::KeyDoor(key))
+    } else if is_teleporter(line.special, hexen) {
+        Some(LineKind::Teleporter)
+    } else if front.floor_height != back.floor_height {
+        Some(LineKind::FloorStep)
+    } else if front.ceiling_height != back.ceiling_height {
+        Some(LineKind::CeilingStep)
+    } else {
+        None
+    }
+}
+
+// vanilla locked door specials, then boom's generalized locked doors (0x3800..0x3c00)
+fn door_key(special: i32) -> Option<Key> {
+    match special {
+        26 | 32 | 99 | 133 => Some(Key::Blue),
+        27 | 34 | 136 | 137 => Some(Key::Yellow),
+        28 | 33 | 134 | 135 => Some(Key::Red),
+        0x3800..=0x3bff => match (special & 0x01c0) >> 6 {
+            1 | 4 => Some(Key::Red),
+            2 | 5 => Some(Key::Blue),
+            3 | 6 => Some(Key::Yellow),
+            _ => None,  // any key or all keys
+        },
+        _ => None,
+    }
+}
+
+// Door_LockedRaise with a zdoom lock number in its fourth argument
+fn hexen_door_key(line: &Linedef) -> Option<Key> {
+    if line.special != 13 {
+        return None;
+    }
+    match line.args[3] {
+        1 | 4 | 129 => Some(Key::Red),
+        2 | 5 | 130 => Some(Key::Blue),
+        3 | 6 | 131 => Some(Key::Yellow),
+        _ => None,
+    }
+}
+
+fn is_teleporter(special: i32, hexen: bool) -> bool {
+    if hexen {
+        matches!(special, 70 | 71)
+    } else {
+        matches!(special, 39 | 97 | 125 | 126)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doom::wad::tests::build;
+    use crate::doom::wad::Wad;
+
+    fn parse(textmap: &str) -> AutomapMap {
+        let bytes = build(b"PWAD", &[("MAP01", vec![]), ("TEXTMAP", textmap.as_bytes().to_vec()), ("ENDMAP", vec![])]);
+        let wad = Wad::parse(&bytes).unwrap();
+        automap(&Map::parse(&wad.maps()[0]).unwrap())
+    }
+
+    #[test]
+    fn classifies_lines_like_the_engine() {
+        let map = parse(r#"
+            namespace = "doom";
+            vertex { x = 0; y = 0; }
+            vertex { x = 64; y = 0; }
+            vertex { x = 64; y = 128; }
+            sector { heightfloor = 0; heightceiling = 128; }
+            sector { heightfloor = 24; heightceiling = 128; }
+            sector { heightfloor = 0; heightceiling = 96; }
+            sidedef { sector = 0; }
+            sidedef { sector = 1; }
+            sidedef { sector = 2; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 1; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 2; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 0; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 1; secret = true; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 1; dontdraw = true; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 0; special = 28; }
+            linedef { v1 = 1; v2 = 2; sidefront = 0; sideback = 0; special = 14464; }
+            linedef { v1 = 1; v2 = 2; sidefront = 0; sideback = 0; special = 97; }
+            thing { x = 32; y = 32; type = 1; single = true; }
+            thing { x = 32; y = 32; type = 3001; single = true; }
+            thing { x = 32; y = 32; type = 39; single = true; }
+            thing { x = 32; y = 32; type = 2001; single = true; }
+            thing { x = 32; y = 32; type = 3001; coop = true; }
+        "#);
+
+        let kinds: Vec<LineKind> = map.lines.iter().map(|line| line.kind).collect();
+        assert_eq!(kinds, [
+            LineKind::Wall,
+            LineKind::FloorStep,
+            LineKind::CeilingStep,
+            LineKind::Wall,
+            LineKind::KeyDoor(Key::Red),
+            LineKind::KeyDoor(Key::Blue),  // generalized: blue card
+            LineKind::Teleporter,
+        ]);
+        assert_eq!((map.min, map.max), ([0.0, 0.0], [64.0, 128.0]));
+
+        let things: Vec<ThingKind> = map.things.iter().map(|thing| thing.kind).collect();
+        assert_eq!(things, [ThingKind::Player, ThingKind::Monster, ThingKind::Key(Key::Yellow), ThingKind::Other]);
+    }
+
+    #[test]
+    fn reads_zdoom_locked_doors() {
+        let map = parse(r#"
+            namespace = "zdoom";
+            vertex { x = 0; y = 0; }
+            vertex { x = 64; y = 0; }
+            sector { heightfloor = 0; heightceiling = 128; }
+            sidedef { sector = 0; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 0; special = 13; arg3 = 130; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 0; special = 28; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; sideback = 0; special = 70; }
+        "#);
+
+        let kinds: Vec<LineKind> = map.lines.iter().map(|line| line.kind).collect();
+        assert_eq!(kinds, [LineKind::KeyDoor(Key::Blue), LineKind::Teleporter]);
+    }
+}
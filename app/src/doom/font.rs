@@ -0,0 +1,283 @@
+// doom/font.rs
+// bitmap fonts built from per-character patches (STCFN033..) or a zdoom font lump (BIGFONT), packed into one
+// atlas row per colour
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::doom::graphics::{Palette, Picture};
+use crate::doom::wad::{read_u16, WadError};
+
+// text colour translations, applied like zdoom's: glyph brightness picks a shade along the ramp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextColor {
+    #[default]
+    Red,
+    Gold,
+    Gray,
+}
+
+impl TextColor {
+    pub const ALL: [TextColor; 3] = [TextColor::Red, TextColor::Gold, TextColor::Gray];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TextColor::Red => "red",
+            TextColor::Gold => "gold",
+            TextColor::Gray => "gray",
+        }
+    }
+
+    // darkest and brightest shade
+    fn ramp(self) -> [[u8; 3]; 2] {
+        match self {
+            TextColor::Red => [[0x4b, 0x00, 0x00], [0xff, 0x5c, 0x5c]],
+            TextColor::Gold => [[0x73, 0x43, 0x23], [0xff, 0xeb, 0x8b]],
+            TextColor::Gray => [[0x27, 0x27, 0x27], [0xdf, 0xdf, 0xdf]],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Glyph {
+    pub x: u32,         // left edge in the atlas
+    pub width: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FontMetrics {
+    pub height: u32,        // every glyph is this tall in the atlas, aligned on a shared baseline
+    pub atlas_width: u32,
+    pub space: u32,         // advance for spaces and characters the font doesn't have
+    pub glyphs: BTreeMap<char, Glyph>,
+}
+
+// where each character of a string lands, none for gaps
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Placement {
+    pub x: u32,
+    pub glyph: Option<Glyph>,
+}
+
+impl FontMetrics {
+    // doom's fonts are uppercase only, so lowercase falls back to it
+    pub fn glyph(&self, c: char) -> Option<Glyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&c.to_ascii_uppercase())).copied()
+    }
+
+    pub fn layout(&self, text: &str) -> (Vec<Placement>, u32) {
+        let mut x = 0;
+        let placements = text
+            .chars()
+            .map(|c| {
+                let glyph = if c == ' ' { None } else { self.glyph(c) };
+                let placement = Placement { x, glyph };
+                x += glyph.map_or(self.space, |glyph| glyph.width);
+                placement
+            })
+            .collect();
+        (placements, x)
+    }
+}
+
+pub struct Atlas {
+    pub metrics: FontMetrics,
+    pub picture: Picture,
+}
+
+// glyphs side by side; patch top offsets are honoured so punctuation sits where the engine draws it
+pub fn build_atlas(glyphs: &[(char, Picture)], space: u32) -> Atlas {
+    let top = glyphs.iter().map(|(_, glyph)| -glyph.top_offset).min().unwrap_or(0);
+    let height = glyphs.iter().map(|(_, glyph)| -glyph.top_offset - top + glyph.height as i32).max().unwrap_or(0) as usize;
+    let width: usize = glyphs.iter().map(|(_, glyph)| glyph.width).sum();
+
+    let mut pixels = vec![None; width * height];
+    let mut metrics = FontMetrics { height: height as u32, atlas_width: width as u32, space, glyphs: BTreeMap::new() };
+    let mut x = 0;
+    for (c, glyph) in glyphs {
+        let y = (-glyph.top_offset - top) as usize;
+        for row in 0..glyph.height {
+            let source = &glyph.pixels[row * glyph.width..(row + 1) * glyph.width];
+            let start = (y + row) * width + x;
+            pixels[start..start + glyph.width].copy_from_slice(source);
+        }
+        metrics.glyphs.insert(*c, Glyph { x: x as u32, width: glyph.width as u32 });
+        x += glyph.width;
+    }
+
+    Atlas { metrics, picture: Picture { width, height, left_offset: 0, top_offset: 0, pixels } }
+}
+
+// a FON1/FON2 lump: every character in one lump, indexed into the font's own palette (0 is transparent)
+pub struct FontLump {
+    pub glyphs: Vec<(char, Picture)>,
+    pub palette: Palette,
+}
+
+// FON1: "FON1", width, height, then 256 characters of width x height; its colours are shades of grey.
+// FON2: "FON2", height, first and last character, constant width flag, shading type, palette size - 1,
+// flags (bit 0: a global kerning i16 follows), one u16 width (constant) or one per character, the rgb palette,
+// then each character with a width. glyph pixels are byterun1 packed in both
+pub fn from_font_lump(name: &str, data: &[u8]) -> Result<FontLump, WadError> {
+    let bad = || WadError::BadGraphic(name.to_string());
+    let u16_at = |offset: usize| data.get(offset..offset + 2).map(|_| read_u16(data, offset) as usize).ok_or_else(bad);
+    let u8_at = |offset: usize| data.get(offset).copied().ok_or_else(bad);
+
+    let (widths, height, first, palette, mut offset) = match data.get(..4) {
+        Some(b"FON1") => {
+            let (width, height) = (u16_at(4)?, u16_at(6)?);
+            (vec![width; 256], height, 0, Palette(std::array::from_fn(|i| [i as u8; 3])), 8)
+        }
+        Some(b"FON2") => {
+            let (height, first, last) = (u16_at(4)?, u8_at(6)?, u8_at(7)?);
+            let count = (last as usize + 1).checked_sub(first as usize).ok_or_else(bad)?;
+            let colors = u8_at(10)? as usize + 1;
+            let mut offset = if u8_at(11)? & 1 != 0 { 14 } else { 12 };
+            let widths = if u8_at(8)? != 0 {
+                offset += 2;
+                vec![u16_at(offset - 2)?; count]
+            } else {
+                offset += count * 2;
+                (0..count).map(|i| u16_at(offset - count * 2 + i * 2)).collect::<Result<_, _>>()?
+            };
+            let rgb = data.get(offset..offset + colors * 3).ok_or_else(bad)?;
+            let palette = Palette(std::array::from_fn(|i| rgb.get(i * 3..i * 3 + 3).map_or([0; 3], |c| [c[0], c[1], c[2]])));
+            (widths, height, first, palette, offset + colors * 3)
+        }
+        _ => return Err(WadError::UnsupportedFormat(format!("{} is not a FON1 or FON2 font", name))),
+    };
+
+    let mut glyphs = Vec::new();
+    for (i, &width) in widths.iter().enumerate() {
+        if width == 0 {
+            continue;
+        }
+        let (pixels, used) = unpack_byterun1(data.get(offset..).unwrap_or_default(), width * height).ok_or_else(bad)?;
+        offset += used;
+        let c = char::from(first + i as u8);
+        if c != ' ' && pixels.iter().any(|&index| index != 0) {
+            let pixels = pixels.into_iter().map(|index| (index != 0).then_some(index)).collect();
+            glyphs.push((c, Picture { width, height, left_offset: 0, top_offset: 0, pixels }));
+        }
+    }
+    Ok(FontLump { glyphs, palette })
+}
+
+// n >= 0: the next n + 1 bytes as they are, n < 0: the next byte -n + 1 times, -128 is a no-op.
+// the unpacked bytes and how many packed bytes they took
+fn unpack_byterun1(data: &[u8], size: usize) -> Option<(Vec<u8>, usize)> {
+    let (mut out, mut read) = (Vec::with_capacity(size), 0);
+    while out.len() < size {
+        let code = *data.get(read)? as i8;
+        read += 1;
+        match code {
+            -128 => {}
+            0.. => {
+                let count = code as usize + 1;
+                out.extend_from_slice(data.get(read..read + count)?);
+                read += count;
+            }
+            _ => {
+                out.extend(std::iter::repeat_n(*data.get(read)?, (1 - code as isize) as usize));
+                read += 1;
+            }
+        }
+    }
+    out.truncate(size);
+    Some((out, read))
+}
+
+// rgba8 of the atlas in a text colour, with brightness stretched over the glyphs' own range
+pub fn translate(picture: &Picture, palette: &Palette, color: TextColor) -> Vec<u8> {
+    let luminance = |index: u8| {
+        let [r, g, b] = palette.0[index as usize];
+        0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64
+    };
+    let (min, max) = picture
+        .pixels
+        .iter()
+        .flatten()
+        .map(|&index| luminance(index))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), l| (min.min(l), max.max(l)));
+    let [dark, bright] = color.ramp();
+
+    picture
+        .pixels
+        .iter()
+        .flat_map(|pixel| match pixel {
+            Some(index) => {
+                let t = if max > min { (luminance(*index) - min) / (max - min) } else { 1.0 };
+                let shade = |i: usize| (dark[i] as f64 + (bright[i] as f64 - dark[i] as f64) * t).round() as u8;
+                [shade(0), shade(1), shade(2), 255]
+            }
+            None => [0; 4],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(width: usize, height: usize, top_offset: i32, index: u8) -> Picture {
+        Picture { width, height, left_offset: 0, top_offset, pixels: vec![Some(index); width * height] }
+    }
+
+    #[test]
+    fn packs_glyphs_on_a_baseline() {
+        // 'A' 3x7, ',' 2x2 drawn 6 rows lower (top offset -6)
+        let atlas = build_atlas(&[('A', glyph(3, 7, 0, 1)), (',', glyph(2, 2, -6, 2))], 4);
+
+        assert_eq!((atlas.metrics.height, atlas.metrics.atlas_width), (8, 5));
+        assert_eq!(atlas.metrics.glyphs[&','], Glyph { x: 3, width: 2 });
+        assert_eq!(atlas.picture.pixels[3], None);                  // comma column, top row
+        assert_eq!(atlas.picture.pixels[6 * 5 + 3], Some(2));       // comma starts on row 6
+        assert_eq!(atlas.picture.pixels[7 * 5], None);              // 'A' ends on row 6
+    }
+
+    #[test]
+    fn lays_out_text_with_fallbacks() {
+        let atlas = build_atlas(&[('A', glyph(3, 7, 0, 1)), ('B', glyph(4, 7, 0, 1))], 2);
+        let (placements, width) = atlas.metrics.layout("ab A~");
+
+        assert_eq!(width, 3 + 4 + 2 + 3 + 2);
+        assert_eq!(placements.iter().map(|p| p.x).collect::<Vec<_>>(), [0, 3, 7, 9, 12]);
+        assert_eq!(placements[1].glyph, Some(Glyph { x: 3, width: 4 }));
+        assert_eq!(placements[2].glyph, None);
+        assert_eq!(placements[4].glyph, None);   // no glyph for '~'
+    }
+
+    #[test]
+    fn reads_a_fon2_lump() {
+        // 3 tall, 'A' and 'B' (2 wide and 0 wide, so no data), 2 colours, kerning -1
+        let mut lump = b"FON2".to_vec();
+        lump.extend_from_slice(&[3, 0, b'A', b'B', 0, 0, 1, 1]);
+        lump.extend_from_slice(&(-1i16).to_le_bytes());
+        lump.extend_from_slice(&[2, 0, 0, 0]);
+        lump.extend_from_slice(&[0, 0, 0, 200, 10, 10]);
+        // a literal run of 2, then 1 repeated 4 times
+        lump.extend_from_slice(&[1, 0, 1, -3i8 as u8, 1]);
+
+        let font = from_font_lump("BIGFONT", &lump).unwrap();
+        assert_eq!(font.palette.0[1], [200, 10, 10]);
+        assert_eq!(font.glyphs.len(), 1);
+        let (c, glyph) = &font.glyphs[0];
+        assert_eq!((*c, glyph.width, glyph.height), ('A', 2, 3));
+        assert_eq!(glyph.pixels, [None, Some(1), Some(1), Some(1), Some(1), Some(1)]);
+
+        assert!(from_font_lump("BIGFONT", &lump[..lump.len() - 1]).is_err());
+        assert!(matches!(from_font_lump("BIGFONT", b"FON3"), Err(WadError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn translates_brightness_onto_the_ramp() {
+        let mut palette = Palette([[0; 3]; 256]);
+        palette.0[1] = [40, 40, 40];
+        palette.0[2] = [200, 200, 200];
+        let picture = Picture { width: 3, height: 1, left_offset: 0, top_offset: 0, pixels: vec![Some(1), Some(2), None] };
+
+        let rgba = translate(&picture, &palette, TextColor::Gray);
+        assert_eq!(rgba, [0x27, 0x27, 0x27, 255, 0xdf, 0xdf, 0xdf, 255, 0, 0, 0, 0]);
+    }
+}
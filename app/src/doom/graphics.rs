@@ -0,0 +1,185 @@
+// doom/graphics.rs
+// paletted pictures: column based patches (STBAR, M_DOOM, wall patches) and raw flats, coloured through PLAYPAL/COLORMAP
+use crate::doom::wad::{read_i16, read_u16, read_u32, WadError};
+
+const PALETTE_SIZE: usize = 256 * 3;
+const COLORMAP_SIZE: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette(pub [[u8; 3]; 256]);
+
+impl Palette {
+    // PLAYPAL holds 14 palettes: 0 normal, 1-8 pain red, 9-12 pickup gold, 13 radiation suit green
+    pub fn from_playpal(data: &[u8], index: usize) -> Result<Palette, WadError> {
+        let start = index * PALETTE_SIZE;
+        let bytes = data.get(start..start + PALETTE_SIZE).ok_or_else(|| WadError::BadLumpSize {
+            lump: "PLAYPAL".to_string(),
+            size: data.len(),
+            record: PALETTE_SIZE,
+        })?;
+        Ok(Palette(std::array::from_fn(|i| [bytes[i * 3], bytes[i * 3 + 1], bytes[i * 3 + 2]])))
+    }
+}
+
+// COLORMAP: 32 light levels (0 brightest) plus the invulnerability map, each remapping palette indices
+#[derive(Clone, Debug, PartialEq)]
+pub struct Colormap(pub Vec<[u8; COLORMAP_SIZE]>);
+
+impl Colormap {
+    pub fn from_lump(data: &[u8]) -> Result<Colormap, WadError> {
+        if data.is_empty() || !data.len().is_multiple_of(COLORMAP_SIZE) {
+            return Err(WadError::BadLumpSize { lump: "COLORMAP".to_string(), size: data.len(), record: COLORMAP_SIZE });
+        }
+        Ok(Colormap(data.chunks_exact(COLORMAP_SIZE).map(|map| map.try_into().unwrap_or([0; COLORMAP_SIZE])).collect()))
+    }
+
+    pub fn level(&self, level: usize) -> Option<&[u8; COLORMAP_SIZE]> {
+        self.0.get(level)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    pub width: usize,
+    pub height: usize,
+    pub left_offset: i32,
+    pub top_offset: i32,
+    pub pixels: Vec<Option<u8>>,    // row major palette indices, none where the patch is transparent
+}
+
+impl Picture {
+    // header (width, height, offsets), one u32 offset per column, then posts of opaque runs per column
+    pub fn from_patch(name: &str, data: &[u8]) -> Result<Picture, WadError> {
+        let bad = || WadError::BadGraphic(name.to_string());
+        if data.len() < 8 {
+            return Err(bad());
+        }
+        let (width, height) = (read_u16(data, 0) as usize, read_u16(data, 2) as usize);
+        if width == 0 || height == 0 || data.len() < 8 + width * 4 {
+            return Err(bad());
+        }
+
+        let mut pixels = vec![None; width * height];
+        for x in 0..width {
+            let mut offset = read_u32(data, 8 + x * 4) as usize;
+            let mut top = -1i32;
+            loop {
+                let &delta = data.get(offset).ok_or_else(bad)?;
+                if delta == 0xff {
+                    break;
+                }
+                // tall patches: a delta not above the previous one is relative to it
+                top = if delta as i32 <= top { top + delta as i32 } else { delta as i32 };
+                let &length = data.get(offset + 1).ok_or_else(bad)?;
+                let run = data.get(offset + 3..offset + 3 + length as usize).ok_or_else(bad)?;
+                for (i, &index) in run.iter().enumerate() {
+                    let y = top as usize + i;
+                    if y < height {
+                        pixels[y * width + x] = Some(index);
+                    }
+                }
+                offset += length as usize + 4;  // delta, length, padding, data, padding
+            }
+        }
+
+        Ok(Picture {
+            width,
+            height,
+            left_offset: read_i16(data, 4) as i32,
+            top_offset: read_i16(data, 6) as i32,
+            pixels,
+        })
+    }
+
+    // flats are raw square images, 64x64 in vanilla and larger in some source ports
+    pub fn from_flat(name: &str, data: &[u8]) -> Result<Picture, WadError> {
+        let size = (data.len() as f64).sqrt() as usize;
+        if size == 0 || size * size != data.len() {
+            return Err(WadError::BadGraphic(name.to_string()));
+        }
+        Ok(Picture { width: size, height: size, left_offset: 0, top_offset: 0, pixels: data.iter().map(|&i| Some(i)).collect() })
+    }
+
+    // rgba8, optionally darkened (or otherwise remapped) through a colormap level first
+    pub fn to_rgba(&self, palette: &Palette, colormap: Option<&[u8; COLORMAP_SIZE]>) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| match pixel {
+                Some(index) => {
+                    let index = colormap.map_or(*index, |map| map[*index as usize]);
+                    let [r, g, b] = palette.0[index as usize];
+                    [r, g, b, 255]
+                }
+                None => [0; 4],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3x4 patch: column 0 a full post, column 1 empty, column 2 two posts (rows 0 and 2-3)
+    fn patch() -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [3u16, 4] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [-1i16, 2] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let columns: [&[u8]; 3] = [&[0, 4, 0, 1, 2, 3, 4, 0, 0xff], &[0xff], &[0, 1, 0, 5, 0, 2, 2, 0, 6, 7, 0, 0xff]];
+        let mut offset = 8 + columns.len() * 4;
+        for column in columns {
+            data.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += column.len();
+        }
+        for column in columns {
+            data.extend_from_slice(column);
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_patch_columns() {
+        let picture = Picture::from_patch("TEST", &patch()).unwrap();
+        assert_eq!((picture.width, picture.height, picture.left_offset, picture.top_offset), (3, 4, -1, 2));
+        assert_eq!(picture.pixels, [
+            Some(1), None, Some(5),
+            Some(2), None, None,
+            Some(3), None, Some(6),
+            Some(4), None, Some(7),
+        ]);
+    }
+
+    #[test]
+    fn rejects_truncated_patches() {
+        let data = patch();
+        assert_eq!(Picture::from_patch("TEST", &data[..12]), Err(WadError::BadGraphic("TEST".to_string())));
+        assert_eq!(Picture::from_patch("TEST", &data[..data.len() - 3]), Err(WadError::BadGraphic("TEST".to_string())));
+        assert!(Picture::from_flat("FLAT", &[0; 4095]).is_err());
+    }
+
+    #[test]
+    fn colours_through_palette_and_colormap() {
+        let playpal: Vec<u8> = (0..PALETTE_SIZE * 2).map(|i| (i % 251) as u8).collect();
+        let palette = Palette::from_playpal(&playpal, 0).unwrap();
+        assert_eq!(palette.0[1], [3, 4, 5]);
+        assert_eq!(Palette::from_playpal(&playpal, 1).unwrap().0[0], [15, 16, 17]);   // 768 % 251
+        assert!(Palette::from_playpal(&playpal, 2).is_err());
+
+        // level 1 shifts every index up by one
+        let mut lump: Vec<u8> = (0..=255).collect();
+        lump.extend((0..=255u8).map(|i| i.wrapping_add(1)));
+        let colormap = Colormap::from_lump(&lump).unwrap();
+
+        let flat = Picture::from_flat("FLAT", &[1; 4096]).unwrap();
+        assert_eq!((flat.width, flat.height), (64, 64));
+        assert_eq!(flat.to_rgba(&palette, None)[..4], [3, 4, 5, 255]);
+        assert_eq!(flat.to_rgba(&palette, colormap.level(1))[..4], [6, 7, 8, 255]);
+
+        let patch = Picture::from_patch("TEST", &patch()).unwrap();
+        assert_eq!(patch.to_rgba(&palette, None)[4..8], [0, 0, 0, 0]);
+    }
+}
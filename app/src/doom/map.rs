@@ -0,0 +1,319 @@
+// doom/map.rs
+// one level in a common shape, read from binary (vanilla/boom) or udmf lumps
+use serde::{Deserialize, Serialize};
+
+use crate::doom::udmf::{self, Block};
+use crate::doom::wad::{read_i16, read_name, read_u16, Lump, MapLumps, WadError};
+
+const NO_SIDEDEF: u16 = 0xffff;
+
+// binary linedef flags
+const ML_TWO_SIDED: u16 = 0x0004;
+const ML_SECRET: u16 = 0x0020;      // drawn as one-sided on the automap
+const ML_DONT_DRAW: u16 = 0x0080;   // never shown on the automap
+const ML_MAPPED: u16 = 0x0100;      // shown from the start
+
+// binary thing flags
+const MTF_EASY: u16 = 0x0001;       // skills 1 and 2
+const MTF_NORMAL: u16 = 0x0002;     // skill 3
+const MTF_HARD: u16 = 0x0004;       // skills 4 and 5
+const MTF_MULTIPLAYER: u16 = 0x0010;
+const MTF_NOT_DEATHMATCH: u16 = 0x0020;    // boom
+const MTF_NOT_COOP: u16 = 0x0040;          // boom
+const MTF_FRIENDLY: u16 = 0x0080;          // mbf
+
+const VANILLA_MAX_LINE_SPECIAL: i32 = 141;
+const VANILLA_MAX_SECTOR_SPECIAL: i32 = 17;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MapFormat {
+    Vanilla,
+    Boom,   // same binary layout as vanilla, told apart by the specials and flags it uses
+    Udmf,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vertex {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Thing {
+    pub x: f64,
+    pub y: f64,
+    pub angle: f64,         // degrees, 0 = east
+    pub doomednum: i32,
+    pub skills: [bool; 5],  // present on ITYTD, HNTR, HMP, UV, NM
+    pub single_player: bool,
+    pub coop: bool,
+    pub deathmatch: bool,
+    pub friendly: bool,     // mbf helper, fights for the player and isn't on the kill tally
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Linedef {
+    pub start: usize,
+    pub end: usize,
+    pub front: Option<usize>,
+    pub back: Option<usize>,
+    pub special: i32,
+    pub tag: i32,
+    pub args: [i32; 5],     // udmf action arguments, zero in binary maps
+    pub two_sided: bool,
+    pub secret: bool,
+    pub hidden: bool,
+    pub mapped: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sidedef {
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub upper: String,
+    pub lower: String,
+    pub middle: String,
+    pub sector: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sector {
+    pub floor_height: f64,
+    pub ceiling_height: f64,
+    pub floor_texture: String,
+    pub ceiling_texture: String,
+    pub light: i32,
+    pub special: i32,
+    pub tag: i32,
+    pub secret: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Map {
+    pub name: String,
+    pub format: MapFormat,
+    pub namespace: String,  // udmf only
+    pub things: Vec<Thing>,
+    pub linedefs: Vec<Linedef>,
+    pub sidedefs: Vec<Sidedef>,
+    pub sectors: Vec<Sector>,
+    pub vertices: Vec<Vertex>,
+}
+
+impl Map {
+    pub fn parse(lumps: &MapLumps) -> Result<Map, WadError> {
+        let mut map = match lumps.get("TEXTMAP") {
+            Some(textmap) => parse_udmf(&String::from_utf8_lossy(&textmap.data))?,
+            None if lumps.get("BEHAVIOR").is_some() => return Err(WadError::UnsupportedFormat("Hexen".to_string())),
+            None => parse_binary(lumps)?,
+        };
+        map.name = lumps.name.clone();
+        map.validate()?;
+        Ok(map)
+    }
+
+    // zdoom style namespaces number line and sector specials like hexen
+    pub fn hexen_specials(&self) -> bool {
+        self.format == MapFormat::Udmf && hexen_namespace(&self.namespace)
+    }
+
+    // every index points at something that exists, so consumers can index without checks
+    fn validate(&self) -> Result<(), WadError> {
+        for line in &self.linedefs {
+            for vertex in [line.start, line.end] {
+                if vertex >= self.vertices.len() {
+                    return Err(WadError::BadReference { lump: "LINEDEFS".to_string(), index: vertex });
+                }
+            }
+            for side in [line.front, line.back].into_iter().flatten() {
+                if side >= self.sidedefs.len() {
+                    return Err(WadError::BadReference { lump: "LINEDEFS".to_string(), index: side });
+                }
+            }
+        }
+        for side in &self.sidedefs {
+            if side.sector >= self.sectors.len() {
+                return Err(WadError::BadReference { lump: "SIDEDEFS".to_string(), index: side.sector });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hexen_namespace(namespace: &str) -> bool {
+    !matches!(namespace, "doom" | "heretic")
+}
+
+// vanilla sector special 9, or the secret bit of boom's generalized sector types
+fn is_secret_special(special: i32) -> bool {
+    special == 9 || special & 0x80 != 0
+}
+
+fn records<'a>(lumps: &MapLumps<'a>, name: &str, size: usize) -> Result<std::slice::ChunksExact<'a, u8>, WadError> {
+    let data: &'a [u8] = lumps.get(name).map(|lump: &'a Lump| lump.data.as_slice()).unwrap_or(&[]);
+    if !data.len().is_multiple_of(size) {
+        return Err(WadError::BadLumpSize { lump: name.to_string(), size: data.len(), record: size });
+    }
+    Ok(data.chunks_exact(size))
+}
+
+fn parse_binary(lumps: &MapLumps) -> Result<Map, WadError> {
+    let mut boom = false;
+    let vertices = records(lumps, "VERTEXES", 4)?
+        .map(|r| Vertex { x: read_i16(r, 0) as f64, y: read_i16(r, 2) as f64 })
+        .collect();
+
+    let things = records(lumps, "THINGS", 10)?
+        .map(|r| {
+            let flags = read_u16(r, 8);
+            boom |= flags & (MTF_NOT_DEATHMATCH | MTF_NOT_COOP | MTF_FRIENDLY) != 0;
+            Thing {
+                x: read_i16(r, 0) as f64,
+                y: read_i16(r, 2) as f64,
+                angle: read_i16(r, 4) as f64,
+                doomednum: read_i16(r, 6) as i32,
+                skills: [
+                    flags & MTF_EASY != 0,
+                    flags & MTF_EASY != 0,
+                    flags & MTF_NORMAL != 0,
+                    flags & MTF_HARD != 0,
+                    flags & MTF_HARD != 0,
+                ],
+                single_player: flags & MTF_MULTIPLAYER == 0,
+                coop: flags & MTF_NOT_COOP == 0,
+                deathmatch: flags & MTF_NOT_DEATHMATCH == 0,
+                friendly: flags & MTF_FRIENDLY != 0,
+            }
+        })
+        .collect();
+
+    let side = |index: u16| (index != NO_SIDEDEF).then_some(index as usize);
+    let linedefs = records(lumps, "LINEDEFS", 14)?
+        .map(|r| {
+            let flags = read_u16(r, 4);
+            boom |= read_u16(r, 6) as i32 > VANILLA_MAX_LINE_SPECIAL;
+            Linedef {
+                start: read_u16(r, 0) as usize,
+                end: read_u16(r, 2) as usize,
+                special: read_u16(r, 6) as i32,
+                tag: read_i16(r, 8) as i32,
+                args: [0; 5],
+                front: side(read_u16(r, 10)),
+                back: side(read_u16(r, 12)),
+                two_sided: flags & ML_TWO_SIDED != 0,
+                secret: flags & ML_SECRET != 0,
+                hidden: flags & ML_DONT_DRAW != 0,
+                mapped: flags & ML_MAPPED != 0,
+            }
+        })
+        .collect();
+
+    let sidedefs = records(lumps, "SIDEDEFS", 30)?
+        .map(|r| Sidedef {
+            offset_x: read_i16(r, 0) as f64,
+            offset_y: read_i16(r, 2) as f64,
+            upper: read_name(&r[4..12]),
+            lower: read_name(&r[12..20]),
+            middle: read_name(&r[20..28]),
+            sector: read_u16(r, 28) as usize,
+        })
+        .collect();
+
+    let sectors = records(lumps, "SECTORS", 26)?
+        .map(|r| {
+            let special = read_i16(r, 22) as i32;
+            boom |= special > VANILLA_MAX_SECTOR_SPECIAL;
+            Sector {
+                floor_height: read_i16(r, 0) as f64,
+                ceiling_height: read_i16(r, 2) as f64,
+                floor_texture: read_name(&r[4..12]),
+                ceiling_texture: read_name(&r[12..20]),
+                light: read_i16(r, 20) as i32,
+                special,
+                tag: read_i16(r, 24) as i32,
+                secret: is_secret_special(special),
+            }
+        })
+        .collect();
+
+    let format = if boom { MapFormat::Boom } else { MapFormat::Vanilla };
+    Ok(Map { name: String::new(), format, namespace: String::new(), things, linedefs, sidedefs, sectors, vertices })
+}
+
+fn parse_udmf(source: &str) -> Result<Map, WadError> {
+    let textmap = udmf::parse(source)?;
+    // in hexen numbering 1024 is the sector secret flag
+    let hexen_specials = hexen_namespace(&textmap.namespace);
+    let blocks = |kind: &'static str| textmap.blocks.iter().filter(move |block| block.kind == kind);
+    let index = |block: &Block, key: &str| usize::try_from(block.int(key, -1)).ok();
+
+    let vertices = blocks("vertex").map(|b| Vertex { x: b.float("x", 0.0), y: b.float("y", 0.0) }).collect();
+
+    let things = blocks("thing")
+        .map(|b| Thing {
+            x: b.float("x", 0.0),
+            y: b.float("y", 0.0),
+            angle: b.float("angle", 0.0),
+            doomednum: b.int("type", 0) as i32,
+            skills: std::array::from_fn(|skill| b.flag(&format!("skill{}", skill + 1))),
+            single_player: b.flag("single"),
+            coop: b.flag("coop"),
+            deathmatch: b.flag("dm"),
+            friendly: b.flag("friend"),
+        })
+        .collect();
+
+    let linedefs = blocks("linedef")
+        .map(|b| {
+            Ok(Linedef {
+                start: index(b, "v1").ok_or_else(|| missing("linedef", "v1"))?,
+                end: index(b, "v2").ok_or_else(|| missing("linedef", "v2"))?,
+                front: index(b, "sidefront"),
+                back: index(b, "sideback"),
+                special: b.int("special", 0) as i32,
+                tag: b.int("id", b.int("arg0", 0)) as i32,
+                args: std::array::from_fn(|arg| b.int(&format!("arg{}", arg), 0) as i32),
+                two_sided: b.flag("twosided"),
+                secret: b.flag("secret"),
+                hidden: b.flag("dontdraw"),
+                mapped: b.flag("mapped"),
+            })
+        })
+        .collect::<Result<_, WadError>>()?;
+
+    let sidedefs = blocks("sidedef")
+        .map(|b| {
+            Ok(Sidedef {
+                offset_x: b.float("offsetx", 0.0),
+                offset_y: b.float("offsety", 0.0),
+                upper: b.string("texturetop").unwrap_or("-").to_ascii_uppercase(),
+                lower: b.string("texturebottom").unwrap_or("-").to_ascii_uppercase(),
+                middle: b.string("texturemiddle").unwrap_or("-").to_ascii_uppercase(),
+                sector: index(b, "sector").ok_or_else(|| missing("sidedef", "sector"))?,
+            })
+        })
+        .collect::<Result<_, WadError>>()?;
+
+    let sectors = blocks("sector")
+        .map(|b| {
+            let special = b.int("special", 0) as i32;
+            let secret = b.flag("secret") || if hexen_specials { special & 1024 != 0 } else { is_secret_special(special) };
+            Sector {
+                floor_height: b.float("heightfloor", 0.0),
+                ceiling_height: b.float("heightceiling", 0.0),
+                floor_texture: b.string("texturefloor").unwrap_or("-").to_ascii_uppercase(),
+                ceiling_texture: b.string("textureceiling").unwrap_or("-").to_ascii_uppercase(),
+                light: b.int("lightlevel", 160) as i32,
+                special,
+                tag: b.int("id", 0) as i32,
+                secret,
+            }
+        })
+        .collect();
+
+    Ok(Map { name: String::new(), format: MapFormat::Udmf, namespace: textmap.namespace, things, linedefs, sidedefs, sectors, vertices })
+}
+
+fn missing(block: &str, field: &str) -> WadError {
+    WadError::Udmf { line: 0, message: format!("{} without {}", block, field) }
+}
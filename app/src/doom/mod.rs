@@ -0,0 +1,10 @@
+// doom/mod.rs
+pub mod automap;
+pub mod font;
+pub mod graphics;
+pub mod map;
+pub mod quantize;
+pub mod stats;
+pub mod things;
+pub mod udmf;
+pub mod wad;
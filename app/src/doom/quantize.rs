@@ -0,0 +1,132 @@
+// doom/quantize.rs
+// remaps truecolour images onto PLAYPAL so screenshots sit in the same 256 colours as the rest of the site
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::doom::graphics::{Palette, Picture};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dither {
+    #[default]
+    None,
+    FloydSteinberg,     // error diffusion, smoothest gradients
+    Bayer,              // 4x4 ordered, the crosshatch look of 90s renders
+}
+
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+const BAYER_SPREAD: f32 = 48.0;     // roughly the gap between neighbouring PLAYPAL ramp shades
+
+// nearest palette entry per colour, memoised since screenshots repeat colours a lot
+struct Nearest<'a> {
+    palette: &'a Palette,
+    cache: HashMap<[u8; 3], u8>,
+}
+
+impl<'a> Nearest<'a> {
+    fn new(palette: &'a Palette) -> Nearest<'a> {
+        Nearest { palette, cache: HashMap::new() }
+    }
+
+    fn index(&mut self, color: [f32; 3]) -> u8 {
+        let key = color.map(|c| c.round().clamp(0.0, 255.0) as u8);
+        *self.cache.entry(key).or_insert_with(|| {
+            // weighted like the eye: green differences matter most, blue least
+            let distance = |entry: &[u8; 3]| {
+                let d = |i: usize| entry[i] as i32 - key[i] as i32;
+                2 * d(0) * d(0) + 4 * d(1) * d(1) + 3 * d(2) * d(2)
+            };
+            (0..=255u8).min_by_key(|&i| distance(&self.palette.0[i as usize])).unwrap_or(0)
+        })
+    }
+}
+
+// rgba8 in, palette indices out; pixels under half alpha become transparent
+pub fn quantize(rgba: &[u8], width: usize, height: usize, palette: &Palette, dither: Dither) -> Picture {
+    let mut nearest = Nearest::new(palette);
+    let mut color: Vec<[f32; 3]> = rgba.chunks_exact(4).map(|p| [p[0] as f32, p[1] as f32, p[2] as f32]).collect();
+    let opaque = |i: usize| rgba[i * 4 + 3] >= 128;
+    let mut pixels = vec![None; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if !opaque(i) {
+                continue;
+            }
+            let wanted = match dither {
+                Dither::Bayer => {
+                    let offset = (BAYER[y % 4][x % 4] as f32 + 0.5) / 16.0 - 0.5;
+                    color[i].map(|c| c + offset * BAYER_SPREAD)
+                }
+                _ => color[i],
+            };
+            let index = nearest.index(wanted);
+            pixels[i] = Some(index);
+
+            if dither == Dither::FloydSteinberg {
+                let got = palette.0[index as usize];
+                let error: [f32; 3] = std::array::from_fn(|c| color[i][c] - got[c] as f32);
+                for (dx, dy, weight) in [(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)] {
+                    let (nx, ny) = (x as isize + dx, y + dy);
+                    if nx < 0 || nx as usize >= width || ny >= height {
+                        continue;
+                    }
+                    let n = ny * width + nx as usize;
+                    for c in 0..3 {
+                        color[n][c] += error[c] * weight / 16.0;
+                    }
+                }
+            }
+        }
+    }
+
+    Picture { width, height, left_offset: 0, top_offset: 0, pixels }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // black, white and pure red, everything else a far off blue
+    fn palette() -> Palette {
+        let mut palette = Palette([[0, 0, 255]; 256]);
+        palette.0[0] = [0, 0, 0];
+        palette.0[1] = [255, 255, 255];
+        palette.0[2] = [255, 0, 0];
+        palette
+    }
+
+    fn solid(width: usize, height: usize, rgba: [u8; 4]) -> Vec<u8> {
+        rgba.repeat(width * height)
+    }
+
+    #[test]
+    fn maps_to_the_nearest_entry() {
+        let mut rgba = solid(3, 1, [250, 10, 20, 255]);
+        rgba[4..8].copy_from_slice(&[240, 240, 230, 255]);
+        rgba[8..12].copy_from_slice(&[240, 240, 230, 0]);
+
+        let picture = quantize(&rgba, 3, 1, &palette(), Dither::None);
+        assert_eq!(picture.pixels, [Some(2), Some(1), None]);
+    }
+
+    #[test]
+    fn diffuses_mid_grey_into_a_mix() {
+        let (width, height) = (16, 16);
+        let rgba = solid(width, height, [128, 128, 128, 255]);
+
+        for dither in [Dither::FloydSteinberg, Dither::Bayer] {
+            let picture = quantize(&rgba, width, height, &palette(), dither);
+            let white = picture.pixels.iter().filter(|&&p| p == Some(1)).count();
+            let black = picture.pixels.iter().filter(|&&p| p == Some(0)).count();
+            assert_eq!(white + black, width * height, "{:?}", dither);
+            assert!((white as i32 - black as i32).abs() <= 16, "{:?}: {} white, {} black", dither, white, black);
+        }
+
+        // without dithering a flat colour stays flat
+        let picture = quantize(&rgba, width, height, &palette(), Dither::None);
+        assert!(picture.pixels.windows(2).all(|pair| pair[0] == pair[1]));
+    }
+}
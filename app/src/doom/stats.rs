@@ -0,0 +1,233 @@
+// doom/stats.rs
+// per map numbers for the doom project panels: kills/items per skill, secrets, size and line count
+use serde::{Deserialize, Serialize};
+
+use crate::doom::map::{Map, MapFormat};
+use crate::doom::things;
+use crate::doom::wad::{Wad, WadError};
+
+pub const SKILLS: [&str; 5] = ["ITYTD", "HNTR", "HMP", "UV", "NM"];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SkillStats {
+    pub monsters: u32,
+    pub items: u32,     // counted pickups, like the intermission screen
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MapStats {
+    pub name: String,
+    pub format: MapFormat,
+    pub skills: [SkillStats; 5],    // single player, indexed like SKILLS
+    pub secrets: u32,
+    pub width: u32,                 // map units
+    pub height: u32,
+    pub lines: u32,
+    pub sectors: u32,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WadStats {
+    pub file: String,
+    pub maps: Vec<MapStats>,
+}
+
+pub fn map_stats(map: &Map) -> MapStats {
+    let mut skills = [SkillStats::default(); 5];
+    for thing in map.things.iter().filter(|thing| thing.single_player) {
+        let monster = things::is_monster(thing.doomednum) && !thing.friendly;
+        let item = things::is_counted_item(thing.doomednum);
+        for (stats, _) in skills.iter_mut().zip(thing.skills).filter(|(_, present)| *present) {
+            stats.monsters += monster as u32;
+            stats.items += item as u32;
+        }
+    }
+
+    let (min, max) = map.vertices.iter().fold(
+        ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+        |(min, max), v| ([min[0].min(v.x), min[1].min(v.y)], [max[0].max(v.x), max[1].max(v.y)]),
+    );
+    let size = |axis: usize| if map.vertices.is_empty() { 0 } else { (max[axis] - min[axis]).round() as u32 };
+
+    MapStats {
+        name: map.name.clone(),
+        format: map.format,
+        skills,
+        secrets: map.sectors.iter().filter(|sector| sector.secret).count() as u32,
+        width: size(0),
+        height: size(1),
+        lines: map.linedefs.len() as u32,
+        sectors: map.sectors.len() as u32,
+    }
+}
+
+pub fn wad_stats(file: &str, wad: &Wad) -> Result<WadStats, WadError> {
+    let maps = wad
+        .maps()
+        .iter()
+        .map(|lumps| Map::parse(lumps).map(|map| map_stats(&map)))
+        .collect::<Result<_, _>>()?;
+    Ok(WadStats { file: file.to_string(), maps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doom::wad::tests::build;
+
+    fn thing(x: i16, y: i16, doomednum: i16, flags: u16) -> Vec<u8> {
+        [x.to_le_bytes(), y.to_le_bytes(), 0i16.to_le_bytes(), doomednum.to_le_bytes(), flags.to_le_bytes()].concat()
+    }
+
+    fn vertex(x: i16, y: i16) -> Vec<u8> {
+        [x.to_le_bytes(), y.to_le_bytes()].concat()
+    }
+
+    fn linedef(start: u16, end: u16, flags: u16, special: u16, front: u16, back: u16) -> Vec<u8> {
+        [start, end, flags, special, 0, front, back].iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn sidedef(sector: u16) -> Vec<u8> {
+        let mut side = vec![0; 4];
+        for texture in [b"-\0\0\0\0\0\0\0", b"-\0\0\0\0\0\0\0", b"STARTAN3"] {
+            side.extend_from_slice(texture);
+        }
+        side.extend_from_slice(&sector.to_le_bytes());
+        side
+    }
+
+    fn sector(special: i16) -> Vec<u8> {
+        let mut sector = [0i16.to_le_bytes(), 128i16.to_le_bytes()].concat();
+        sector.extend_from_slice(b"FLOOR4_8CEIL3_5\0");
+        sector.extend_from_slice(&160i16.to_le_bytes());
+        sector.extend_from_slice(&special.to_le_bytes());
+        sector.extend_from_slice(&0i16.to_le_bytes());
+        sector
+    }
+
+    // a square room split in two by a two-sided line, the right half a secret
+    fn binary_map(name: &'static str, things: Vec<Vec<u8>>, secret_special: i16) -> Vec<(&'static str, Vec<u8>)> {
+        vec![
+            (name, vec![]),
+            ("THINGS", things.concat()),
+            ("LINEDEFS", [
+                linedef(0, 1, 1, 0, 0, 0xffff),
+                linedef(1, 2, 1, 0, 1, 0xffff),
+                linedef(2, 3, 1, 0, 2, 0xffff),
+                linedef(3, 0, 1, 0, 3, 0xffff),
+                linedef(4, 5, 4, 0, 4, 5),
+            ].concat()),
+            ("SIDEDEFS", [sidedef(0), sidedef(1), sidedef(1), sidedef(0), sidedef(0), sidedef(1)].concat()),
+            ("VERTEXES", [
+                vertex(0, 0), vertex(512, 0), vertex(512, 256), vertex(0, 256), vertex(256, 0), vertex(256, 256),
+            ].concat()),
+            ("SECTORS", [sector(0), sector(secret_special)].concat()),
+        ]
+    }
+
+    #[test]
+    fn counts_vanilla_map_per_skill() {
+        let bytes = build(b"PWAD", &binary_map("MAP01", vec![
+            thing(32, 32, 1, 7),            // player start, not counted
+            thing(64, 64, 3001, 7),         // imp on all skills
+            thing(96, 64, 3004, 4),         // zombieman on uv/nm only
+            thing(128, 64, 9, 2 | 4),       // shotgun guy on hmp and up
+            thing(160, 64, 3005, 7 | 16),   // multiplayer-only cacodemon
+            thing(192, 64, 2014, 1),        // health bonus on itytd/hntr
+            thing(224, 64, 2001, 7),        // shotgun isn't a counted item
+            thing(256, 64, 2025, 7),        // neither is the radiation suit
+            thing(288, 64, 3006, 7),        // nor are lost souls a kill
+        ], 9));
+        let stats = wad_stats("test.wad", &Wad::parse(&bytes).unwrap()).unwrap();
+
+        assert_eq!(stats.maps.len(), 1);
+        let map = &stats.maps[0];
+        assert_eq!(map.name, "MAP01");
+        assert_eq!(map.format, MapFormat::Vanilla);
+        assert_eq!(map.skills.map(|s| s.monsters), [1, 1, 2, 3, 3]);
+        assert_eq!(map.skills.map(|s| s.items), [1, 1, 0, 0, 0]);
+        assert_eq!(map.secrets, 1);
+        assert_eq!((map.width, map.height), (512, 256));
+        assert_eq!(map.lines, 5);
+        assert_eq!(map.sectors, 2);
+    }
+
+    #[test]
+    fn detects_boom_maps_and_generalized_secrets() {
+        // generalized sector type: light bits 1 (blink) + secret bit
+        let bytes = build(b"PWAD", &binary_map("E1M1", vec![
+            thing(64, 64, 3001, 7 | 32),
+            thing(96, 64, 3002, 7 | 0x80),  // mbf friendly demon, on the player's side
+        ], 0x80 | 1));
+        let map = &wad_stats("boom.wad", &Wad::parse(&bytes).unwrap()).unwrap().maps[0];
+
+        assert_eq!(map.format, MapFormat::Boom);
+        assert_eq!(map.secrets, 1);
+        assert_eq!(map.skills[3].monsters, 1);
+    }
+
+    #[test]
+    fn rejects_truncated_and_dangling_lumps() {
+        let mut lumps = binary_map("MAP01", vec![], 0);
+        lumps[1].1 = vec![0; 7];
+        let bytes = build(b"PWAD", &lumps);
+        assert_eq!(
+            wad_stats("bad.wad", &Wad::parse(&bytes).unwrap()),
+            Err(WadError::BadLumpSize { lump: "THINGS".to_string(), size: 7, record: 10 })
+        );
+
+        let mut lumps = binary_map("MAP01", vec![], 0);
+        lumps[2].1 = linedef(0, 9, 1, 0, 0, 0xffff);
+        let bytes = build(b"PWAD", &lumps);
+        assert_eq!(
+            wad_stats("bad.wad", &Wad::parse(&bytes).unwrap()),
+            Err(WadError::BadReference { lump: "LINEDEFS".to_string(), index: 9 })
+        );
+    }
+
+    #[test]
+    fn reads_udmf_maps() {
+        let textmap = r#"
+            namespace = "zdoom";
+            vertex { x = 0.0; y = 0.0; }
+            vertex { x = 1024.0; y = 0.0; }
+            vertex { x = 1024.0; y = 512.0; }
+            sector { heightfloor = 0; heightceiling = 128; texturefloor = "FLAT1"; textureceiling = "F_SKY1"; }
+            sector { heightfloor = 0; heightceiling = 128; texturefloor = "FLAT1"; textureceiling = "F_SKY1"; secret = true; }
+            sector { heightfloor = 0; heightceiling = 128; texturefloor = "FLAT1"; textureceiling = "F_SKY1"; special = 1024; }
+            sidedef { sector = 0; texturemiddle = "STONE2"; }
+            linedef { v1 = 0; v2 = 1; sidefront = 0; blocking = true; }
+            linedef { v1 = 1; v2 = 2; sidefront = 0; secret = true; }
+            thing { x = 64.0; y = 64.0; type = 3003; skill4 = true; skill5 = true; single = true; }
+            thing { x = 64.0; y = 64.0; type = 83; skill1 = true; skill2 = true; skill3 = true; single = true; }
+            thing { x = 64.0; y = 64.0; type = 3001; skill1 = true; skill4 = true; coop = true; }
+            thing { x = 64.0; y = 64.0; type = 69; skill4 = true; single = true; friend = true; }
+        "#;
+        let bytes = build(b"PWAD", &[("MAP07", vec![]), ("TEXTMAP", textmap.as_bytes().to_vec()), ("ENDMAP", vec![])]);
+        let wad = Wad::parse(&bytes).unwrap();
+        let map = Map::parse(&wad.maps()[0]).unwrap();
+
+        assert_eq!(map.format, MapFormat::Udmf);
+        assert!(map.linedefs[1].secret);
+        assert_eq!(map.linedefs[0].back, None);
+        assert_eq!(map.sidedefs[0].middle, "STONE2");
+
+        let stats = map_stats(&map);
+        assert_eq!(stats.name, "MAP07");
+        assert_eq!(stats.skills.map(|s| s.monsters), [0, 0, 0, 1, 1]);
+        assert_eq!(stats.skills.map(|s| s.items), [1, 1, 1, 0, 0]);
+        assert_eq!(stats.secrets, 2);
+        assert_eq!((stats.width, stats.height, stats.lines), (1024, 512, 2));
+    }
+
+    #[test]
+    fn refuses_hexen_maps() {
+        let mut lumps = binary_map("MAP01", vec![], 0);
+        lumps.push(("BEHAVIOR", vec![0; 16]));
+        let bytes = build(b"PWAD", &lumps);
+        assert_eq!(
+            wad_stats("hexen.wad", &Wad::parse(&bytes).unwrap()),
+            Err(WadError::UnsupportedFormat("Hexen".to_string()))
+        );
+    }
+}
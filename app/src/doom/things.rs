@@ -0,0 +1,69 @@
+// doom/things.rs
+// doom 2 editor numbers (doomednums) for the thing categories the site cares about
+use serde::{Deserialize, Serialize};
+
+// a monster all the same, just not one the kill tally counts
+pub const LOST_SOUL: i32 = 3006;
+
+// monsters counted on the intermission kill tally (MF_COUNTKILL in info.c; the lost soul doesn't have it)
+pub fn is_monster(doomednum: i32) -> bool {
+    matches!(
+        doomednum,
+        3004    // zombieman
+        | 9     // shotgun guy
+        | 65    // heavy weapon dude
+        | 3001  // imp
+        | 3002  // demon
+        | 58    // spectre
+        | 3005  // cacodemon
+        | 69    // hell knight
+        | 3003  // baron of hell
+        | 68    // arachnotron
+        | 71    // pain elemental
+        | 66    // revenant
+        | 67    // mancubus
+        | 64    // arch-vile
+        | 7     // spider mastermind
+        | 16    // cyberdemon
+        | 84    // wolfenstein ss
+        | 72    // commander keen
+    )
+}
+
+// pickups counted on the intermission item tally (MF_COUNTITEM in info.c; the radiation suit doesn't have it)
+pub fn is_counted_item(doomednum: i32) -> bool {
+    matches!(
+        doomednum,
+        2014    // health bonus
+        | 2015  // armor bonus
+        | 2013  // soulsphere
+        | 83    // megasphere
+        | 2022  // invulnerability
+        | 2023  // berserk
+        | 2024  // partial invisibility
+        | 2026  // computer area map
+        | 2045  // light amplification visor
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Key {
+    Blue,
+    Yellow,
+    Red,
+}
+
+// keycards and skull keys, which open the same doors in vanilla
+pub fn key_color(doomednum: i32) -> Option<Key> {
+    match doomednum {
+        5 | 40 => Some(Key::Blue),
+        6 | 39 => Some(Key::Yellow),
+        13 | 38 => Some(Key::Red),
+        _ => None,
+    }
+}
+
+pub fn is_player_start(doomednum: i32) -> bool {
+    matches!(doomednum, 1..=4)
+}
@@ -0,0 +1,296 @@
+// doom/udmf.rs
+// generic TEXTMAP reader: global assignments plus a list of typed blocks (thing, linedef, ...), keys lowercased
+use std::collections::HashMap;
+
+use crate::doom::wad::WadError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+}
+
+impl Value {
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+    pub kind: String,
+    pub fields: HashMap<String, Value>,
+}
+
+impl Block {
+    pub fn float(&self, key: &str, default: f64) -> f64 {
+        self.fields.get(key).and_then(Value::as_f64).unwrap_or(default)
+    }
+
+    pub fn int(&self, key: &str, default: i64) -> i64 {
+        self.fields.get(key).and_then(Value::as_i64).unwrap_or(default)
+    }
+
+    pub fn flag(&self, key: &str) -> bool {
+        self.fields.get(key).and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    pub fn string(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).and_then(Value::as_str)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextMap {
+    pub namespace: String,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    Value(Value),
+    Symbol(char),
+}
+
+pub fn parse(source: &str) -> Result<TextMap, WadError> {
+    let tokens = tokenize(source)?;
+    let mut map = TextMap::default();
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some((line, token)) = iter.next() {
+        let Token::Identifier(name) = token else {
+            return Err(error(line, "expected a block or assignment"));
+        };
+        let name = name.to_ascii_lowercase();
+
+        match iter.next() {
+            Some((_, Token::Symbol('='))) => {
+                let value = expect_value(&mut iter, line)?;
+                expect_symbol(&mut iter, ';', line)?;
+                if name == "namespace" {
+                    map.namespace = value.as_str().unwrap_or_default().to_ascii_lowercase();
+                }
+            }
+            Some((_, Token::Symbol('{'))) => {
+                let mut block = Block { kind: name, fields: HashMap::new() };
+                loop {
+                    match iter.next() {
+                        Some((_, Token::Symbol('}'))) => break,
+                        Some((line, Token::Identifier(key))) => {
+                            expect_symbol(&mut iter, '=', line)?;
+                            let value = expect_value(&mut iter, line)?;
+                            expect_symbol(&mut iter, ';', line)?;
+                            block.fields.insert(key.to_ascii_lowercase(), value);
+                        }
+                        Some((line, _)) => return Err(error(line, "expected a field name or '}'")),
+                        None => return Err(error(line, "unterminated block")),
+                    }
+                }
+                map.blocks.push(block);
+            }
+            _ => return Err(error(line, "expected '=' or '{'")),
+        }
+    }
+
+    Ok(map)
+}
+
+fn error(line: usize, message: &str) -> WadError {
+    WadError::Udmf { line, message: message.to_string() }
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<(usize, Token)>>;
+
+fn expect_symbol(iter: &mut Tokens, symbol: char, line: usize) -> Result<(), WadError> {
+    match iter.next() {
+        Some((_, Token::Symbol(found))) if found == symbol => Ok(()),
+        Some((line, _)) => Err(error(line, &format!("expected '{}'", symbol))),
+        None => Err(error(line, &format!("expected '{}' before end of file", symbol))),
+    }
+}
+
+fn expect_value(iter: &mut Tokens, line: usize) -> Result<Value, WadError> {
+    match iter.next() {
+        Some((_, Token::Value(value))) => Ok(value),
+        // true/false are keywords, anything else bare is invalid
+        Some((line, Token::Identifier(word))) => match word.to_ascii_lowercase().as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(error(line, &format!("unexpected identifier '{}'", word))),
+        },
+        Some((line, _)) => Err(error(line, "expected a value")),
+        None => Err(error(line, "expected a value before end of file")),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, WadError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut line = 1;
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '\n' => {
+                line += 1;
+                chars.next();
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '/' => {
+                chars.next();
+                match chars.next() {
+                    Some('/') => {
+                        while chars.peek().is_some_and(|&c| c != '\n') {
+                            chars.next();
+                        }
+                    }
+                    Some('*') => {
+                        let mut previous = ' ';
+                        loop {
+                            match chars.next() {
+                                Some('/') if previous == '*' => break,
+                                Some(c) => {
+                                    if c == '\n' {
+                                        line += 1;
+                                    }
+                                    previous = c;
+                                }
+                                None => return Err(error(line, "unterminated comment")),
+                            }
+                        }
+                    }
+                    _ => return Err(error(line, "unexpected '/'")),
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            if let Some(escaped) = chars.next() {
+                                text.push(escaped);
+                            }
+                        }
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            text.push(c);
+                        }
+                        None => return Err(error(line, "unterminated string")),
+                    }
+                }
+                tokens.push((line, Token::Value(Value::String(text))));
+            }
+            '=' | ';' | '{' | '}' => {
+                chars.next();
+                tokens.push((line, Token::Symbol(c)));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let mut text = String::new();
+                while chars.peek().is_some_and(|&c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')) {
+                    text.extend(chars.next());
+                }
+                let value = parse_number(&text).ok_or_else(|| error(line, &format!("invalid number '{}'", text)))?;
+                tokens.push((line, Token::Value(value)));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut text = String::new();
+                while chars.peek().is_some_and(|&c| c.is_ascii_alphanumeric() || c == '_') {
+                    text.extend(chars.next());
+                }
+                tokens.push((line, Token::Identifier(text)));
+            }
+            other => return Err(error(line, &format!("unexpected character '{}'", other))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_number(text: &str) -> Option<Value> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    if let Some(hex) = digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        let value = i64::from_str_radix(hex, 16).ok()?;
+        return Some(Value::Int(if negative { -value } else { value }));
+    }
+    if let Ok(value) = text.parse::<i64>() {
+        return Some(Value::Int(value));
+    }
+    text.parse::<f64>().ok().map(Value::Float)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_blocks_and_values() {
+        let map = parse(r#"
+            // exported by ultimate doom builder
+            namespace = "ZDoom";
+            /* multi
+               line */
+            thing { x = -32.5; y = 0x10; type = 3001; skill3 = true; comment = "say \"hi\""; }
+            Vertex{X=1;Y=-2;}
+        "#).unwrap();
+
+        assert_eq!(map.namespace, "zdoom");
+        assert_eq!(map.blocks.len(), 2);
+        let thing = &map.blocks[0];
+        assert_eq!(thing.kind, "thing");
+        assert_eq!(thing.float("x", 0.0), -32.5);
+        assert_eq!(thing.int("y", 0), 16);
+        assert_eq!(thing.int("type", 0), 3001);
+        assert!(thing.flag("skill3"));
+        assert!(!thing.flag("skill4"));
+        assert_eq!(thing.string("comment"), Some("say \"hi\""));
+        assert_eq!(map.blocks[1].kind, "vertex");
+        assert_eq!(map.blocks[1].float("y", 0.0), -2.0);
+    }
+
+    #[test]
+    fn reports_line_of_errors() {
+        assert_eq!(
+            parse("namespace = \"doom\";\n\nthing { x = ; }"),
+            Err(WadError::Udmf { line: 3, message: "expected a value".to_string() })
+        );
+        assert!(matches!(parse("thing { x = 1;"), Err(WadError::Udmf { line: 1, .. })));
+        assert!(matches!(parse("thing { x = bogus; }"), Err(WadError::Udmf { .. })));
+    }
+}
@@ -0,0 +1,293 @@
+// doom/wad.rs
+// wad container: 12 byte header, lump data and a directory of 16 byte entries
+use std::fmt;
+
+const HEADER_SIZE: usize = 12;
+const DIRECTORY_ENTRY_SIZE: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WadKind {
+    Iwad,   // full game data
+    Pwad,   // patch wad (custom maps)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lump {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wad {
+    pub kind: WadKind,
+    pub lumps: Vec<Lump>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WadError {
+    TooShort,
+    BadMagic([u8; 4]),
+    DirectoryOutOfBounds,
+    LumpOutOfBounds(String),
+    BadLumpSize { lump: String, size: usize, record: usize },
+    BadReference { lump: String, index: usize },
+    Udmf { line: usize, message: String },
+    UnsupportedFormat(String),
+    BadGraphic(String),
+    MissingLump(String),
+}
+
+impl fmt::Display for WadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WadError::TooShort => write!(f, "file is too short to be a wad"),
+            WadError::BadMagic(magic) => write!(f, "not a wad (magic {:?})", String::from_utf8_lossy(magic)),
+            WadError::DirectoryOutOfBounds => write!(f, "lump directory points outside the file"),
+            WadError::LumpOutOfBounds(name) => write!(f, "lump {} points outside the file", name),
+            WadError::BadLumpSize { lump, size, record } => {
+                write!(f, "lump {} is {} bytes, not a multiple of {}", lump, size, record)
+            }
+            WadError::BadReference { lump, index } => write!(f, "{} references missing index {}", lump, index),
+            WadError::Udmf { line, message } => write!(f, "TEXTMAP line {}: {}", line, message),
+            WadError::UnsupportedFormat(format) => write!(f, "{} maps are not supported", format),
+            WadError::BadGraphic(name) => write!(f, "lump {} is not a valid picture", name),
+            WadError::MissingLump(name) => write!(f, "lump {} not found", name),
+        }
+    }
+}
+
+impl std::error::Error for WadError {}
+
+// lumps that can follow a map marker in binary (vanilla/boom) maps
+const MAP_LUMPS: [&str; 11] = [
+    "THINGS", "LINEDEFS", "SIDEDEFS", "VERTEXES", "SEGS", "SSECTORS", "NODES", "SECTORS", "REJECT", "BLOCKMAP", "BEHAVIOR",
+];
+
+// lumps of one map, found between its marker and the next non-map lump
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapLumps<'a> {
+    pub name: String,
+    pub lumps: Vec<&'a Lump>,
+}
+
+impl<'a> MapLumps<'a> {
+    pub fn get(&self, name: &str) -> Option<&'a Lump> {
+        self.lumps.iter().copied().find(|lump| lump.name == name)
+    }
+}
+
+impl Wad {
+    pub fn parse(bytes: &[u8]) -> Result<Wad, WadError> {
+        if bytes.len() < HEADER_SIZE {
+            return Err(WadError::TooShort);
+        }
+
+        let magic: [u8; 4] = bytes[0..4].try_into().unwrap_or_default();
+        let kind = match &magic {
+            b"IWAD" => WadKind::Iwad,
+            b"PWAD" => WadKind::Pwad,
+            _ => return Err(WadError::BadMagic(magic)),
+        };
+        let count = read_u32(bytes, 4) as usize;
+        let directory = read_u32(bytes, 8) as usize;
+
+        let directory_end = count
+            .checked_mul(DIRECTORY_ENTRY_SIZE)
+            .and_then(|size| size.checked_add(directory))
+            .filter(|&end| end <= bytes.len())
+            .ok_or(WadError::DirectoryOutOfBounds)?;
+
+        let lumps = bytes[directory..directory_end]
+            .chunks_exact(DIRECTORY_ENTRY_SIZE)
+            .map(|entry| {
+                let offset = read_u32(entry, 0) as usize;
+                let size = read_u32(entry, 4) as usize;
+                let name = read_name(&entry[8..16]);
+                let data = offset
+                    .checked_add(size)
+                    .and_then(|end| bytes.get(offset..end))
+                    .ok_or_else(|| WadError::LumpOutOfBounds(name.clone()))?;
+                Ok(Lump { name, data: data.to_vec() })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Wad { kind, lumps })
+    }
+
+    // last lump with this name wins, like the engine's lookup
+    pub fn lump(&self, name: &str) -> Option<&Lump> {
+        self.lumps.iter().rev().find(|lump| lump.name == name)
+    }
+
+    // flats live between F_START/F_END (or the FF_ pwad markers), where names may repeat patch names
+    pub fn flat(&self, name: &str) -> Option<&Lump> {
+        let mut inside = false;
+        let mut found = None;
+        for lump in &self.lumps {
+            match lump.name.as_str() {
+                "F_START" | "FF_START" => inside = true,
+                "F_END" | "FF_END" => inside = false,
+                _ if inside && lump.name == name => found = Some(lump),
+                _ => {}
+            }
+        }
+        found
+    }
+
+    // map markers are recognised by what follows them rather than by name, so any map name works
+    pub fn maps(&self) -> Vec<MapLumps<'_>> {
+        let mut maps = Vec::new();
+        let mut index = 0;
+
+        while index < self.lumps.len() {
+            let marker = &self.lumps[index];
+            let next = self.lumps.get(index + 1).map(|lump| lump.name.as_str());
+
+            if next == Some("TEXTMAP") {
+                // udmf: everything up to ENDMAP belongs to the map
+                let end = self.lumps[index + 1..]
+                    .iter()
+                    .position(|lump| lump.name == "ENDMAP")
+                    .map_or(self.lumps.len(), |offset| index + 1 + offset);
+                maps.push(MapLumps { name: marker.name.clone(), lumps: self.lumps[index + 1..end].iter().collect() });
+                index = end + 1;
+            } else if next == Some("THINGS") || next == Some("LINEDEFS") {
+                let lumps: Vec<&Lump> = self.lumps[index + 1..]
+                    .iter()
+                    .take_while(|lump| MAP_LUMPS.contains(&lump.name.as_str()))
+                    .collect();
+                index += 1 + lumps.len();
+                maps.push(MapLumps { name: marker.name.clone(), lumps });
+            } else {
+                index += 1;
+            }
+        }
+        maps
+    }
+}
+
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+pub(crate) fn read_i16(bytes: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+// 8 byte name, nul padded, case-insensitive in the engine
+pub(crate) fn read_name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_ascii_uppercase()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // assemble a wad in memory: header, lump data, then the directory
+    pub fn build(kind: &[u8; 4], lumps: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut directory = Vec::new();
+        for (name, lump) in lumps {
+            directory.extend_from_slice(&((HEADER_SIZE + data.len()) as u32).to_le_bytes());
+            directory.extend_from_slice(&(lump.len() as u32).to_le_bytes());
+            let mut padded = [0u8; 8];
+            padded[..name.len()].copy_from_slice(name.as_bytes());
+            directory.extend_from_slice(&padded);
+            data.extend_from_slice(lump);
+        }
+
+        let mut wad = kind.to_vec();
+        wad.extend_from_slice(&(lumps.len() as u32).to_le_bytes());
+        wad.extend_from_slice(&((HEADER_SIZE + data.len()) as u32).to_le_bytes());
+        wad.extend(data);
+        wad.extend(directory);
+        wad
+    }
+
+    #[test]
+    fn parses_header_and_directory() {
+        let bytes = build(b"PWAD", &[("PLAYPAL", vec![1, 2, 3]), ("empty", vec![])]);
+        let wad = Wad::parse(&bytes).unwrap();
+
+        assert_eq!(wad.kind, WadKind::Pwad);
+        assert_eq!(wad.lumps.len(), 2);
+        assert_eq!(wad.lumps[0], Lump { name: "PLAYPAL".to_string(), data: vec![1, 2, 3] });
+        assert_eq!(wad.lumps[1].name, "EMPTY");
+        assert!(wad.lumps[1].data.is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_files() {
+        assert_eq!(Wad::parse(b"PWAD"), Err(WadError::TooShort));
+        assert_eq!(Wad::parse(b"ZWAD\0\0\0\0\0\0\0\0"), Err(WadError::BadMagic(*b"ZWAD")));
+
+        // directory claims one entry past the end of the file
+        let mut bytes = build(b"IWAD", &[]);
+        bytes[4] = 1;
+        assert_eq!(Wad::parse(&bytes), Err(WadError::DirectoryOutOfBounds));
+
+        // lump size larger than the file
+        let mut bytes = build(b"IWAD", &[("THINGS", vec![0; 4])]);
+        let entry = bytes.len() - DIRECTORY_ENTRY_SIZE;
+        bytes[entry + 4] = 200;
+        assert_eq!(Wad::parse(&bytes), Err(WadError::LumpOutOfBounds("THINGS".to_string())));
+    }
+
+    #[test]
+    fn later_lumps_override_earlier_ones() {
+        let bytes = build(b"PWAD", &[("COLORMAP", vec![1]), ("COLORMAP", vec![2])]);
+        assert_eq!(Wad::parse(&bytes).unwrap().lump("COLORMAP").unwrap().data, vec![2]);
+    }
+
+    #[test]
+    fn finds_flats_between_markers() {
+        let bytes = build(b"PWAD", &[
+            ("FLOOR4_8", vec![1]),
+            ("F_START", vec![]),
+            ("FLOOR4_8", vec![2]),
+            ("F_END", vec![]),
+            ("FF_START", vec![]),
+            ("FLOOR4_8", vec![3]),
+            ("FF_END", vec![]),
+        ]);
+        let wad = Wad::parse(&bytes).unwrap();
+        assert_eq!(wad.flat("FLOOR4_8").unwrap().data, vec![3]);
+        assert_eq!(wad.lump("FLOOR4_8").unwrap().data, vec![3]);
+        assert!(wad.flat("STBAR").is_none());
+    }
+
+    #[test]
+    fn finds_binary_and_udmf_maps() {
+        let bytes = build(b"PWAD", &[
+            ("MAPINFO", vec![]),
+            ("MAP01", vec![]),
+            ("THINGS", vec![]),
+            ("LINEDEFS", vec![]),
+            ("SIDEDEFS", vec![]),
+            ("VERTEXES", vec![]),
+            ("SECTORS", vec![]),
+            ("E1M1", vec![]),
+            ("THINGS", vec![]),
+            ("DEHACKED", vec![]),
+            ("MAP02", vec![]),
+            ("TEXTMAP", b"namespace = \"doom\";".to_vec()),
+            ("ZNODES", vec![]),
+            ("ENDMAP", vec![]),
+            ("P_START", vec![]),
+        ]);
+        let wad = Wad::parse(&bytes).unwrap();
+        let maps = wad.maps();
+
+        let names: Vec<&str> = maps.iter().map(|map| map.name.as_str()).collect();
+        assert_eq!(names, ["MAP01", "E1M1", "MAP02"]);
+        assert_eq!(maps[0].lumps.len(), 5);
+        assert_eq!(maps[1].lumps.len(), 1);
+        assert!(maps[2].get("TEXTMAP").is_some());
+        assert!(maps[2].get("ZNODES").is_some());
+    }
+}
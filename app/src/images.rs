@@ -0,0 +1,151 @@
+// images.rs
+// resized and re-encoded copies of the content images, written by src/bin/images.rs and picked by ResponsiveImage
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+pub const MANIFEST_PATH: &str = "/static/derived/images.json";
+pub const DERIVED_DIR: &str = "/static/derived";
+
+// low quality placeholder width, stretched and blurred in the page
+pub const PLACEHOLDER_WIDTH: u32 = 16;
+
+// widths generated below the original's, which is always kept as the largest variant
+pub const WIDTHS: [u32; 3] = [480, 960, 1600];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    Avif,
+    Webp,
+    Png,
+    Jpeg,
+}
+
+impl Format {
+    // modern formats, best first, offered as <source> elements before the fallback
+    pub const MODERN: [Format; 2] = [Format::Avif, Format::Webp];
+
+    pub fn from_path(path: &str) -> Option<Format> {
+        let (_, extension) = path.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "avif" => Some(Format::Avif),
+            "webp" => Some(Format::Webp),
+            "png" => Some(Format::Png),
+            "jpg" | "jpeg" => Some(Format::Jpeg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Avif => "avif",
+            Format::Webp => "webp",
+            Format::Png => "png",
+            Format::Jpeg => "jpg",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            Format::Avif => "image/avif",
+            Format::Webp => "image/webp",
+            Format::Png => "image/png",
+            Format::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub src: String,
+    pub width: u32,
+    pub height: u32,
+    pub format: Format,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImageInfo {
+    pub width: u32,     // of the original
+    pub height: u32,
+    pub format: Format,
+    pub variants: Vec<Variant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,    // tiny png data uri, shown blurred while the image loads
+}
+
+// original site path -> its derivatives
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageManifest(pub BTreeMap<String, ImageInfo>);
+
+impl ImageManifest {
+    pub fn get(&self, src: &str) -> Option<&ImageInfo> {
+        self.0.get(src)
+    }
+}
+
+impl ImageInfo {
+    // "a-480.webp 480w, a-960.webp 960w", none when that format wasn't generated
+    pub fn srcset(&self, format: Format) -> Option<String> {
+        let mut variants: Vec<&Variant> = self.variants.iter().filter(|variant| variant.format == format).collect();
+        variants.sort_by_key(|variant| variant.width);
+        (!variants.is_empty()).then(|| {
+            variants.iter().map(|variant| format!("{} {}w", variant.src, variant.width)).collect::<Vec<_>>().join(", ")
+        })
+    }
+
+    // smallest variant at least this wide in the original's format, for the plain src of the <img>
+    pub fn fallback(&self, min_width: u32) -> Option<&Variant> {
+        let mut variants: Vec<&Variant> = self.variants.iter().filter(|variant| variant.format == self.format).collect();
+        variants.sort_by_key(|variant| variant.width);
+        variants.iter().find(|variant| variant.width >= min_width).or(variants.last()).copied()
+    }
+}
+
+// /static/doom_projects/jammy/jammy_1.png at 480 wide as webp -> /static/derived/doom_projects/jammy/jammy_1-480.webp
+pub fn derived_path(src: &str, width: u32, format: Format) -> String {
+    let relative = src.trim_start_matches('/').trim_start_matches("static/");
+    let stem = relative.rsplit_once('.').map_or(relative, |(stem, _)| stem);
+    format!("{}/{}-{}.{}", DERIVED_DIR, stem, width, format.extension())
+}
+
+// widths to generate for an original this wide
+pub fn widths_for(original: u32) -> Vec<u32> {
+    let mut widths: Vec<u32> = WIDTHS.iter().copied().filter(|&width| width < original).collect();
+    widths.push(original);
+    widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> ImageInfo {
+        let variant = |width, format| Variant { src: derived_path("/static/a/b.png", width, format), width, height: width / 2, format };
+        ImageInfo {
+            width: 1200,
+            height: 600,
+            format: Format::Png,
+            variants: vec![variant(1200, Format::Png), variant(480, Format::Png), variant(480, Format::Webp), variant(1200, Format::Webp)],
+            placeholder: None,
+        }
+    }
+
+    #[test]
+    fn names_derivatives_after_the_original() {
+        assert_eq!(derived_path("/static/a/b.png", 480, Format::Webp), "/static/derived/a/b-480.webp");
+        assert_eq!(derived_path("static/a/b.c.jpeg", 960, Format::Jpeg), "/static/derived/a/b.c-960.jpg");
+        assert_eq!(Format::from_path("/static/a/B.JPG"), Some(Format::Jpeg));
+        assert_eq!(widths_for(1200), [480, 960, 1200]);
+        assert_eq!(widths_for(300), [300]);
+    }
+
+    #[test]
+    fn builds_srcsets_per_format() {
+        let info = info();
+        assert_eq!(info.srcset(Format::Webp).as_deref(), Some("/static/derived/a/b-480.webp 480w, /static/derived/a/b-1200.webp 1200w"));
+        assert_eq!(info.srcset(Format::Avif), None);
+        assert_eq!(info.fallback(400).map(|variant| variant.width), Some(480));
+        assert_eq!(info.fallback(2000).map(|variant| variant.width), Some(1200));
+    }
+}
@@ -0,0 +1,10 @@
+// lib.rs
+// platform independent code shared by the web app (main.rs) and the build tools in src/bin/
+pub mod contact;
+pub mod content;
+pub mod cv;
+pub mod doom;
+pub mod images;
+pub mod media;
+pub mod pdf;
+pub mod renderer;
@@ -1,8 +1,10 @@
+mod api;
 mod app;
 mod router;
 mod pages;
 mod components;
 
+use ::app::{contact, content, cv, doom, images, media, renderer};     // library half of the crate (lib.rs), shared with the build tools
 use app::App;
 use tracing_subscriber::{filter::Targets, prelude::*};
 use tracing_web::MakeWebConsoleWriter;
@@ -0,0 +1,94 @@
+// media.rs
+// gallery entries: screenshots, gameplay videos and youtube embeds, each with its own caption and credit
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MediaKind {
+    Image { src: String },      // anything an <img> shows, gifs included
+    Video { src: String },      // local mp4/webm
+    #[serde(rename = "youtube")]
+    YouTube { id: String },     // embedded from youtube-nocookie.com, only once clicked
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    #[serde(flatten)]
+    pub kind: MediaKind,
+    #[serde(default)]
+    pub alt: Option<String>,        // the gallery numbers the title when missing
+    #[serde(default)]
+    pub caption: Option<String>,
+    #[serde(default)]
+    pub credit: Option<String>,
+    #[serde(default)]
+    pub poster: Option<String>,     // still frame for videos and embeds, also their thumbnail
+}
+
+impl Media {
+    fn new(kind: MediaKind) -> Media {
+        Media { kind, alt: None, caption: None, credit: None, poster: None }
+    }
+
+    pub fn image(src: &str) -> Media {
+        Media::new(MediaKind::Image { src: src.to_string() })
+    }
+
+    pub fn video(src: &str) -> Media {
+        Media::new(MediaKind::Video { src: src.to_string() })
+    }
+
+    pub fn youtube(id: &str) -> Media {
+        Media::new(MediaKind::YouTube { id: id.to_string() })
+    }
+
+    pub fn alt(mut self, alt: &str) -> Media {
+        self.alt = Some(alt.to_string());
+        self
+    }
+
+    pub fn caption(mut self, caption: &str) -> Media {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    pub fn credit(mut self, credit: &str) -> Media {
+        self.credit = Some(credit.to_string());
+        self
+    }
+
+    pub fn poster(mut self, poster: &str) -> Media {
+        self.poster = Some(poster.to_string());
+        self
+    }
+
+    // image shown in thumbnails and worth preloading: the picture itself, or the poster of a video
+    pub fn still(&self) -> Option<&str> {
+        match &self.kind {
+            MediaKind::Image { src } => Some(src),
+            MediaKind::Video { .. } | MediaKind::YouTube { .. } => self.poster.as_deref(),
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        matches!(self.kind, MediaKind::Image { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn videos_show_their_poster() {
+        assert_eq!(Media::image("/a.png").still(), Some("/a.png"));
+        assert_eq!(Media::youtube("abc").still(), None);
+        assert_eq!(Media::video("/a.webm").poster("/a.png").caption("E1M1").still(), Some("/a.png"));
+    }
+
+    #[test]
+    fn reads_tagged_json() {
+        let media: Media = serde_json::from_str(r#"{"type": "youtube", "id": "abc", "credit": "someone"}"#).unwrap();
+        assert_eq!(media, Media::youtube("abc").credit("someone"));
+    }
+}
@@ -4,22 +4,18 @@ use web_sys::window;
 
 use crate::components::social_buttons::{SocialButtons};
 use crate::components::heading::{Heading};
+use crate::components::doom_text::{DoomFont, DoomText};
 use crate::components::education::{Education};
 use crate::components::experience::{Experience};
 use crate::components::technologies::{Technologies};
+use crate::content;
 
-// struct to hold education and experience data
-#[derive(Clone, PartialEq)]
-pub struct ExperienceItem {
-    pub title: String,
-    pub institution: String,
-    pub date: String,
-    pub description: Option<Vec<String>>,
-    pub icon: String,
-}
+// in front of the profile summary's paragraphs, in order
+const SUMMARY_ICONS: [&str; 3] = ["🎓", "🛠", "⚖️"];
 
 #[function_component(About)]
 pub fn about() -> Html {
+    let profile = content::about::profile();
 
     use_effect_with((), |_| {
         // scroll to top when component mounts
@@ -60,21 +56,16 @@ pub fn about() -> Html {
                 // description
                 <div class="flex-1 text-center lg:text-left">
                     <div class="text-center mb-10 mt-10">
-                        <img 
-                            src="/static/about/KONSTANTINOS_ROKANAS_1.png" 
-                            alt="Konstantinos Rokanas"
-                            class="w-auto h-auto mx-auto"
-                        />
+                        <h1>
+                            <DoomText text={profile.name.clone()} font={DoomFont::Big} scale={3} />
+                        </h1>
                     </div>
-                            <p class="text-gray-300 text-lg lg:text-lg leading-relaxed">
-                                {"🎓 I'm a recent software engineering graduate eager to begin a career in tech."}
-                            </p>
-                            <p class="text-gray-300 text-lg lg:text-lg leading-relaxed mt-5">
-                                {"🛠 I want to build intuitive software solutions that make your jobs and lives easier, saving you time and effort. Currently learning Rust, Solidity smart contracts and agentic AI implementations."}
-                            </p>
-                            <p class="text-gray-300 text-lg lg:text-lg leading-relaxed mt-5">
-                                {"⚖️ Formerly a legal professional with a focus on personal data and cybersecurity policy compliance and experience both in international organizations and in the private sector."}
-                            </p>
+                            // the professional half is shared with the cv (content/about.rs), the rest is just for here
+                            { for profile.summary.iter().enumerate().map(|(index, paragraph)| html! {
+                                <p class={classes!("text-gray-300", "text-lg", "lg:text-lg", "leading-relaxed", (index > 0).then_some("mt-5"))}>
+                                    {SUMMARY_ICONS.get(index).map(|icon| format!("{} {}", icon, paragraph)).unwrap_or_else(|| paragraph.clone())}
+                                </p>
+                            })}
                             <p class="text-gray-300 text-lg lg:text-lg leading-relaxed mt-5">
                                 {"⛧ I'm also a lifelong "} <strong>{"Doom"}</strong> {" enthusiast, mapmaker and content creator."}
                             </p>
@@ -89,8 +80,7 @@ pub fn about() -> Html {
                 <div class="mb-16">
                     // heading
                     <Heading 
-                        src="/static/about/EXPERIENCE_1.png" 
-                        alt="Experience"
+                        text="Experience"
                         sub_heading=""             
                     />
                     // component
@@ -101,8 +91,7 @@ pub fn about() -> Html {
                 <div class="mb-16">
                     // heading
                     <Heading 
-                        src="/static/about/EDUCATION_1.png" 
-                        alt="Education"
+                        text="Education"
                         sub_heading=""             
                     />
                     // component
@@ -113,8 +102,7 @@ pub fn about() -> Html {
                 <div class="mb-16">
                     // heading
                     <Heading 
-                        src="/static/about/TECHNOLOGIES_1.png" 
-                        alt="Technologies"
+                        text="Technologies"
                         sub_heading=""             
                     />
                     <Technologies/>
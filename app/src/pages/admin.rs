@@ -0,0 +1,386 @@
+// pages/admin.rs
+use gloo::storage::{SessionStorage, Storage};
+use gloo_net::http::{Request, RequestBuilder, Response};
+use web_sys::{window, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::components::heading::Heading;
+use crate::components::volume_chart::VolumeChart;
+use crate::contact::inbox::{
+    self, Folder, InboxMessage, Login, MessageUpdate, Session, LOGIN_API, LOGOUT_API, MESSAGES_API,
+};
+
+// per tab, so closing the browser logs out
+const TOKEN_KEY: &str = "admin_token";
+const CHART_DAYS: u64 = 30;
+
+const CARD: &str = "rounded-lg border-3 border-red-600 p-6 sm:p-8";
+const CARD_STYLE: &str = "background:linear-gradient(135deg,#1a1a1a 0%,#2a2a2a 50%,#1a1a1a 100%);";
+const INPUT: &str = "w-full px-4 py-3 bg-[#1a1a1a] border border-gray-600 rounded-lg text-white placeholder-gray-400 \
+                     focus:border-red-600 focus:ring-1 focus:ring-red-600 focus:outline-none transition-colors";
+const BUTTON: &str = "px-4 py-2 bg-red-600 hover:bg-red-700 disabled:bg-gray-600 text-white font-semibold rounded-lg \
+                      transition-colors duration-200 cursor-pointer disabled:cursor-not-allowed";
+const OUTLINE_BUTTON: &str = "px-3 py-1.5 border border-red-600 text-red-600 hover:bg-red-600/30 rounded-lg \
+                              transition-colors duration-200 cursor-pointer text-sm";
+
+enum ApiError {
+    LoggedOut,      // session expired or the server restarted, back to the login form
+    Failed(String),
+}
+
+// the server's {"error": ...} when it sent one
+async fn send(request: Result<Request, gloo_net::Error>) -> Result<Response, ApiError> {
+    let response = request
+        .map_err(|e| ApiError::Failed(format!("Failed to create request: {}", e)))?
+        .send()
+        .await
+        .map_err(|e| ApiError::Failed(format!("Network error: {}", e)))?;
+    match response.status() {
+        401 => Err(ApiError::LoggedOut),
+        status if !response.ok() => {
+            let body = response.json::<serde_json::Value>().await.ok();
+            let message = body.as_ref().and_then(|body| body["error"].as_str()).map(str::to_string);
+            Err(ApiError::Failed(message.unwrap_or_else(|| format!("HTTP {} error", status))))
+        }
+        _ => Ok(response),
+    }
+}
+
+fn authorized(request: RequestBuilder, token: &str) -> RequestBuilder {
+    request.header("Authorization", &format!("Bearer {}", token))
+}
+
+async fn login(password: String) -> Result<String, String> {
+    match send(Request::post(LOGIN_API).json(&Login { password })).await {
+        Ok(response) => response.json::<Session>().await.map(|session| session.token).map_err(|e| e.to_string()),
+        Err(ApiError::LoggedOut) => Err("Wrong password.".to_string()),
+        Err(ApiError::Failed(err)) => Err(err),
+    }
+}
+
+async fn fetch_messages(token: &str) -> Result<Vec<InboxMessage>, ApiError> {
+    let response = send(authorized(Request::get(MESSAGES_API), token).build()).await?;
+    response.json().await.map_err(|e| ApiError::Failed(e.to_string()))
+}
+
+async fn update_message(token: &str, id: i64, update: &MessageUpdate) -> Result<(), ApiError> {
+    send(authorized(Request::patch(&format!("{}/{}", MESSAGES_API, id)), token).json(update)).await.map(|_| ())
+}
+
+#[function_component(Admin)]
+pub fn admin() -> Html {
+    let token = use_state(|| SessionStorage::get::<String>(TOKEN_KEY).ok());
+
+    use_effect_with((), |_| {
+        if let Some(window) = window() {
+            window.scroll_to_with_x_and_y(0.0, 0.0);
+        }
+        || {}
+    });
+
+    let on_login = {
+        let token = token.clone();
+        Callback::from(move |new_token: String| {
+            let _ = SessionStorage::set(TOKEN_KEY, &new_token);
+            token.set(Some(new_token));
+        })
+    };
+    let on_logout = {
+        let token = token.clone();
+        Callback::from(move |_| {
+            SessionStorage::delete(TOKEN_KEY);
+            token.set(None);
+        })
+    };
+
+    html! {
+        <div class="min-h-screen pt-15 pb-10">
+            <div class="max-w-6xl mx-auto px-4 sm:px-6 lg:px-8">
+                <Heading text="Inbox" sub_heading="Messages received through the contact form." />
+                {match (*token).clone() {
+                    Some(token) => html! { <Inbox token={token} on_logout={on_logout} /> },
+                    None => html! { <AdminLogin on_login={on_login} /> },
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct AdminLoginProps {
+    on_login: Callback<String>,
+}
+
+#[function_component(AdminLogin)]
+fn admin_login(props: &AdminLoginProps) -> Html {
+    let password_ref = use_node_ref();
+    let error = use_state(|| None::<String>);
+    let busy = use_state(|| false);
+
+    let on_submit = {
+        let password_ref = password_ref.clone();
+        let (error, busy) = (error.clone(), busy.clone());
+        let on_login = props.on_login.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(input) = password_ref.cast::<HtmlInputElement>() else { return };
+            let password = input.value();
+            let (error, busy, on_login) = (error.clone(), busy.clone(), on_login.clone());
+            busy.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = login(password).await;
+                busy.set(false);
+                match result {
+                    Ok(token) => on_login.emit(token),
+                    Err(err) => error.set(Some(err)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit} class={classes!(CARD, "max-w-md", "mx-auto", "space-y-4")} style={CARD_STYLE}>
+            <label for="admin-password" class="block text-sm font-medium text-gray-300">{"Password"}</label>
+            <input ref={password_ref} id="admin-password" type="password" autocomplete="current-password" required=true class={INPUT} />
+            <button type="submit" disabled={*busy} class={classes!(BUTTON, "w-full")}>
+                {if *busy { "Logging in..." } else { "Log in" }}
+            </button>
+            if let Some(error) = &*error {
+                <div class="p-3 rounded-lg border bg-red-900/20 border-red-600 text-red-400" role="alert">{error}</div>
+            }
+        </form>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct InboxProps {
+    token: String,
+    on_logout: Callback<()>,
+}
+
+#[function_component(Inbox)]
+fn inbox_view(props: &InboxProps) -> Html {
+    let messages = use_state(|| None::<Vec<InboxMessage>>);
+    let error = use_state(|| None::<String>);
+    let query = use_state(String::new);
+    let folder = use_state(Folder::default);
+    let selected = use_state(|| None::<i64>);
+    let reload = use_state(|| 0u32);
+
+    {
+        let (messages, error) = (messages.clone(), error.clone());
+        let token = props.token.clone();
+        let on_logout = props.on_logout.clone();
+        use_effect_with(*reload, move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_messages(&token).await {
+                    Ok(loaded) => {
+                        messages.set(Some(loaded));
+                        error.set(None);
+                    }
+                    Err(ApiError::LoggedOut) => on_logout.emit(()),
+                    Err(ApiError::Failed(err)) => error.set(Some(err)),
+                }
+            });
+            || ()
+        });
+    }
+
+    // applied locally straight away, reloaded from the server if it didn't stick
+    let update = {
+        let (messages, error, reload) = (messages.clone(), error.clone(), reload.clone());
+        let token = props.token.clone();
+        let on_logout = props.on_logout.clone();
+        Callback::from(move |(id, update): (i64, MessageUpdate)| {
+            if let Some(mut current) = (*messages).clone() {
+                for message in current.iter_mut().filter(|message| message.id == id) {
+                    message.read = update.read.unwrap_or(message.read);
+                    message.spam = update.spam.unwrap_or(message.spam);
+                }
+                messages.set(Some(current));
+            }
+            let (error, reload, token, on_logout) = (error.clone(), reload.clone(), token.clone(), on_logout.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match update_message(&token, id, &update).await {
+                    Ok(()) => {}
+                    Err(ApiError::LoggedOut) => on_logout.emit(()),
+                    Err(ApiError::Failed(err)) => {
+                        error.set(Some(err));
+                        reload.set(*reload + 1);
+                    }
+                }
+            });
+        })
+    };
+
+    let on_search = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+    let on_refresh = {
+        let reload = reload.clone();
+        Callback::from(move |_| reload.set(*reload + 1))
+    };
+    let on_logout = {
+        let token = props.token.clone();
+        let on_logout = props.on_logout.clone();
+        Callback::from(move |_| {
+            let (token, on_logout) = (token.clone(), on_logout.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                let _ = send(authorized(Request::post(LOGOUT_API), &token).build()).await;
+                on_logout.emit(());
+            });
+        })
+    };
+
+    let Some(all) = &*messages else {
+        return html! {
+            <div class={CARD} style={CARD_STYLE}>
+                {match &*error {
+                    Some(error) => html! { <div class="text-red-400" role="alert">{error}</div> },
+                    None => html! { <div class="text-gray-400 font-mono">{"Loading messages..."}</div> },
+                }}
+            </div>
+        };
+    };
+
+    let shown = inbox::search(all, *folder, &query);
+    let now = (js_sys::Date::now() / 1000.0) as u64;
+    let counts = inbox::daily_counts(all, now, CHART_DAYS);
+    let csv_href = format!("data:text/csv;charset=utf-8,{}", String::from(js_sys::encode_uri_component(&inbox::to_csv(&shown))));
+    let open = shown.iter().find(|message| Some(message.id) == *selected).copied();
+
+    html! {
+        <div class={classes!(CARD, "space-y-6")} style={CARD_STYLE}>
+            // toolbar
+            <div class="flex flex-col md:flex-row gap-3 md:items-center">
+                <input type="search" placeholder="Search name, email, subject or text" value={(*query).clone()} oninput={on_search} class={classes!(INPUT, "md:flex-1")} aria-label="Search messages" />
+                <div class="flex gap-2 flex-wrap">
+                    <a href={csv_href} download={format!("messages-{}.csv", inbox::date(now))} class={OUTLINE_BUTTON}>{"Export CSV"}</a>
+                    <button onclick={on_refresh} class={OUTLINE_BUTTON}>{"Refresh"}</button>
+                    <button onclick={on_logout} class={OUTLINE_BUTTON}>{"Log out"}</button>
+                </div>
+            </div>
+
+            if let Some(error) = &*error {
+                <div class="p-3 rounded-lg border bg-red-900/20 border-red-600 text-red-400" role="alert">{error}</div>
+            }
+
+            <VolumeChart counts={counts} />
+
+            // folders
+            <div class="flex gap-1 font-mono text-sm" role="tablist">
+                { for Folder::ALL.iter().map(|&entry| {
+                    let count = all.iter().filter(|message| entry.contains(message)).count();
+                    let active = entry == *folder;
+                    let (folder, selected) = (folder.clone(), selected.clone());
+                    html! {
+                        <button
+                            role="tab"
+                            aria-selected={active.to_string()}
+                            onclick={Callback::from(move |_| { folder.set(entry); selected.set(None); })}
+                            class={classes!(
+                                "px-3", "py-1", "border", "border-red-600", "cursor-pointer", "transition-colors", "duration-200",
+                                if active { "bg-red-600 text-white" } else { "text-red-600 hover:bg-red-600/30" },
+                            )}
+                        >
+                            {format!("{} ({})", entry.label(), count)}
+                        </button>
+                    }
+                })}
+            </div>
+
+            <div class="grid lg:grid-cols-5 gap-6">
+                // message list
+                <ul class="lg:col-span-2 space-y-2 max-h-[60vh] overflow-y-auto pr-1">
+                    if shown.is_empty() {
+                        <li class="text-gray-500 font-mono text-sm">{"No messages."}</li>
+                    }
+                    { for shown.iter().map(|message| {
+                        let id = message.id;
+                        let is_open = Some(id) == *selected;
+                        let unread = !message.read;
+                        let onclick = {
+                            let (selected, update) = (selected.clone(), update.clone());
+                            Callback::from(move |_| {
+                                selected.set(Some(id));
+                                if unread {
+                                    update.emit((id, MessageUpdate { read: Some(true), ..Default::default() }));
+                                }
+                            })
+                        };
+                        html! {
+                            <li>
+                                <button
+                                    onclick={onclick}
+                                    aria-current={is_open.then_some("true")}
+                                    class={classes!(
+                                        "w-full", "text-left", "p-3", "border-3", "transition-all", "duration-300", "cursor-pointer",
+                                        if is_open { "bg-red-600/30 border-red-600/40" } else { "bg-gray-500/25 border-gray-900 hover:border-gray-600" },
+                                    )}
+                                >
+                                    <div class="flex items-center gap-2">
+                                        if unread {
+                                            <span class="w-2 h-2 rounded-full bg-red-600 flex-shrink-0" aria-label="Unread"></span>
+                                        }
+                                        <span class={classes!("truncate", "text-white", unread.then_some("font-bold"))}>{&message.name}</span>
+                                        <span class="ml-auto text-xs text-gray-400 font-mono flex-shrink-0">{inbox::date(message.received)}</span>
+                                    </div>
+                                    <div class="truncate text-sm text-gray-300">
+                                        {if message.subject.is_empty() { "(no subject)" } else { &message.subject }}
+                                    </div>
+                                </button>
+                            </li>
+                        }
+                    })}
+                </ul>
+
+                // open message
+                <div class="lg:col-span-3">
+                    {match open {
+                        Some(message) => {
+                            let id = message.id;
+                            let toggle_read = {
+                                let (update, read) = (update.clone(), message.read);
+                                Callback::from(move |_| update.emit((id, MessageUpdate { read: Some(!read), ..Default::default() })))
+                            };
+                            let toggle_spam = {
+                                let (update, spam) = (update.clone(), message.spam);
+                                Callback::from(move |_| update.emit((id, MessageUpdate { spam: Some(!spam), ..Default::default() })))
+                            };
+                            html! {
+                                <article class="p-4 bg-[#1a1a1a] border border-gray-600 rounded-lg space-y-4">
+                                    <header class="space-y-1">
+                                        <h2 class="text-xl font-bold text-white break-words">
+                                            {if message.subject.is_empty() { "(no subject)" } else { &message.subject }}
+                                        </h2>
+                                        <div class="text-sm text-gray-300 break-all">
+                                            {format!("{} <{}>", message.name, message.email)}
+                                        </div>
+                                        <div class="text-xs text-gray-500 font-mono">{format!("{} UTC", inbox::date_time(message.received))}</div>
+                                    </header>
+                                    <p class="text-gray-200 whitespace-pre-wrap break-words">{&message.message}</p>
+                                    <div class="flex flex-wrap gap-2">
+                                        <a href={inbox::reply_url(message)} class={BUTTON}>{"Reply"}</a>
+                                        <button onclick={toggle_read} class={OUTLINE_BUTTON}>
+                                            {if message.read { "Mark unread" } else { "Mark read" }}
+                                        </button>
+                                        <button onclick={toggle_spam} class={OUTLINE_BUTTON}>
+                                            {if message.spam { "Not spam" } else { "Spam" }}
+                                        </button>
+                                    </div>
+                                </article>
+                            }
+                        }
+                        None => html! {
+                            <div class="p-4 text-gray-500 font-mono text-sm">{"Pick a message to read it."}</div>
+                        },
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
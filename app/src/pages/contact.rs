@@ -2,35 +2,192 @@
 use yew::prelude::*;
 use web_sys::window;
 use gloo_net::http::Request;
-use wasm_bindgen::JsCast;
-use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::HtmlInputElement;
 use wasm_bindgen::closure::Closure;
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use gloo_events::EventListener;
+use gloo_timers::callback::Timeout;
 
+use crate::api::fetch_json;
 use crate::components::social_buttons::SocialButtons;
 use crate::components::heading::Heading;
+use crate::components::attachment_picker::AttachmentPicker;
+use crate::components::loading_bar::{format_bytes, LoadingBar};
+use crate::contact::attachments::ATTACHMENT_FIELD;
+use crate::contact::backend::{mailto_url, ContactBackend, MultipartPost, Outgoing};
+use crate::contact::captcha::{self, CaptchaConfig, Challenge, Proof, WorkerMessage, WORKER_SCRIPT, WORKER_WASM};
+use crate::contact::error::ContactError;
+use crate::contact::outbox::{Outbox, QueueStatus, DRAFT_KEY, OUTBOX_KEY};
+use crate::contact::spam::FormToken;
+use crate::contact::validate::{self, Field, FieldError};
+use crate::contact::{ContactConfig, Submission, CONFIG_PATH, LOCAL_CONFIG_PATH};
 
-#[derive(Serialize, Deserialize, Clone)]
-struct FormData {
-    name: String,
-    email: String,
-    subject: String,
-    message: String,
-    #[serde(rename = "g-recaptcha-response")]
-    recaptcha_response: String,
+// unix seconds of this session's sends, for the form's own rate limit
+const SENT_KEY: &str = "contact_sent";
+// kept like the draft, a reload isn't a new form as far as the time to submit goes
+const FORM_TOKEN_KEY: &str = "contact_form_token";
+
+// what the bar under the form shows while a message is on its way
+#[derive(Clone, Copy, PartialEq)]
+enum Progress {
+    Proving(f64),
+    Uploading { loaded: f64, total: f64 },
+}
+
+enum Status {
+    Sent,
+    Queued { error: ContactError, mailto: Option<String> },    // transient, worth offering the mailto fallback
+    Failed(ContactError),
+}
+
+// the local override written by `cargo run --bin mock_contact` wins, a site without either uses the apps script
+async fn fetch_contact_config() -> ContactConfig {
+    for path in [LOCAL_CONFIG_PATH, CONFIG_PATH] {
+        let Ok(response) = Request::get(path).send().await else { continue };
+        if !response.ok() {
+            continue;
+        }
+        // dev servers answer missing files with index.html, which just fails to parse
+        match response.json::<ContactConfig>().await {
+            Ok(config) => return config,
+            Err(err) => tracing::debug!("No contact config at {}: {}", path, err),
+        }
+    }
+    ContactConfig::default()
+}
+
+// every change goes through storage, so a send that finishes late never writes back a stale copy
+fn change_outbox<T>(view: &UseStateHandle<Outbox>, change: impl FnOnce(&mut Outbox) -> T) -> T {
+    let mut outbox: Outbox = LocalStorage::get(OUTBOX_KEY).unwrap_or_default();
+    let result = change(&mut outbox);
+    if outbox.messages.is_empty() {
+        LocalStorage::delete(OUTBOX_KEY);
+    } else if let Err(err) = LocalStorage::set(OUTBOX_KEY, &outbox) {
+        tracing::warn!("Couldn't save the outbox: {}", err);
+    }
+    view.set(outbox);
+    result
+}
+
+fn is_online() -> bool {
+    window().is_none_or(|window| window.navigator().on_line())
+}
+
+// one at a time, whatever is due; failures wait out their backoff again
+async fn flush_outbox(config: ContactConfig, view: UseStateHandle<Outbox>, status: UseStateHandle<Option<Status>>) {
+    if !is_online() {
+        return;
+    }
+    // a recaptcha site can't answer the captcha for the visitor, so the outbox asks them to (see send_answered)
+    if !config.retries_unattended() {
+        change_outbox(&view, |outbox| {
+            for queued in outbox.take_due(js_sys::Date::now()) {
+                outbox.needs_captcha(queued.id);
+            }
+        });
+        return;
+    }
+    for queued in change_outbox(&view, |outbox| outbox.take_due(js_sys::Date::now())) {
+        send_queued(&config, queued.id, queued.submission, &view, &status).await;
+    }
+}
+
+async fn send_queued(config: &ContactConfig, id: u64, submission: Submission, view: &UseStateHandle<Outbox>, status: &UseStateHandle<Option<Status>>) {
+    let result = submit_with_captcha(config, submission, &[], Callback::noop()).await;
+    change_outbox(view, |outbox| match result {
+        Ok(()) => {
+            tracing::info!("Sent queued message {}", id);
+            outbox.remove(id);
+            status.set(Some(Status::Sent));
+        }
+        Err(err) if err.is_transient() => outbox.retry_later(id, js_sys::Date::now(), err.to_string()),
+        Err(err) => outbox.reject(id, err.to_string()),
+    });
+}
+
+// a queued message on a recaptcha site, once the visitor has ticked the box again
+fn send_answered(config: ContactConfig, id: u64, view: UseStateHandle<Outbox>, status: UseStateHandle<Option<Status>>) {
+    let token = recaptcha_response();
+    if token.is_empty() {
+        status.set(Some(Status::Failed(ContactError::Captcha("Please complete the reCAPTCHA verification.".to_string()))));
+        return;
+    }
+    let Some(queued) = change_outbox(&view, |outbox| outbox.take_answered(id)) else { return };
+    let submission = Submission { recaptcha_response: token, ..queued.submission };
+    wasm_bindgen_futures::spawn_local(async move {
+        send_queued(&config, id, submission, &view, &status).await;
+        // a token is only good for one try either way
+        reset_recaptcha();
+    });
+}
+
+// what google's widget put in the form once the box was ticked, empty before that
+fn recaptcha_response() -> String {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector("textarea[name='g-recaptcha-response']").ok().flatten())
+        .and_then(|textarea| textarea.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+        .map(|textarea| textarea.value())
+        .unwrap_or_default()
+}
+
+fn reset_recaptcha() {
+    if let Some(window) = web_sys::window() {
+        if let Ok(grecaptcha) = js_sys::Reflect::get(&window, &"grecaptcha".into()) {
+            if let Ok(reset_fn) = js_sys::Reflect::get(&grecaptcha, &"reset".into()) {
+                if let Ok(reset_fn) = reset_fn.dyn_into::<js_sys::Function>() {
+                    let _ = reset_fn.call0(&js_sys::Object::new());
+                }
+            }
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
-struct FormResponse {
-    result: String,
+const INPUT_CLASS: &str = "w-full px-4 py-3 bg-[#1a1a1a] border rounded-lg text-white placeholder-gray-400 \
+                           focus:border-red-600 focus:ring-1 focus:ring-red-600 focus:outline-none transition-colors \
+                           disabled:opacity-50 disabled:cursor-not-allowed";
+
+// what's wrong with the field under it, and for the message how much room is left
+fn field_feedback(field: Field, text: &str, error: Option<FieldError>) -> Html {
+    let length = validate::length(text);
+    html! {
+        <div class="flex justify-between gap-4 mt-1 text-sm min-h-5">
+            <span id={format!("{}-error", field.label().to_lowercase())} class="text-red-400" aria-live="polite">
+                {error.map(|error| format!("{} {}", field.label(), error))}
+            </span>
+            if field == Field::Message {
+                <span class={classes!("font-mono", "flex-shrink-0", if length > field.max() { "text-red-400" } else { "text-gray-500" })}>
+                    {format!("{} / {}", length, field.max())}
+                </span>
+            }
+        </div>
+    }
 }
 
 #[function_component(Contact)]
 pub fn contact() -> Html {
-    use_effect_with((), |_| {
-        if let Some(window) = window() {
-            window.scroll_to_with_x_and_y(0.0, 0.0);
-        }
+    let config = use_state(|| None::<ContactConfig>);
+    {
+        let config = config.clone();
+        use_effect_with((), move |_| {
+            if let Some(window) = window() {
+                window.scroll_to_with_x_and_y(0.0, 0.0);
+            }
+            wasm_bindgen_futures::spawn_local(async move {
+                config.set(Some(fetch_contact_config().await));
+            });
+            || {}
+        });
+    }
+
+    let site_key = match config.as_ref().and_then(|config| config.captcha.as_ref()) {
+        Some(CaptchaConfig::Recaptcha { site_key }) => Some(site_key.clone()),
+        _ => None,
+    };
+    use_effect_with(site_key.clone(), |site_key| {
+        let Some(site_key) = site_key.clone() else { return };
 
         // define render function globally so reCAPTCHA can call it
         let render_recaptcha = Closure::wrap(Box::new(move || {
@@ -40,7 +197,7 @@ pub fn contact() -> Html {
                         if let Ok(render) = render.dyn_into::<js_sys::Function>() {
                             let params = js_sys::Object::new();
                             js_sys::Reflect::set(&params, &"sitekey".into(), 
-                                &"6LfHdcsrAAAAAA4ndXu6pT_KvO1sdOxdPIRX3q12".into()).unwrap();
+                                &site_key.as_str().into()).unwrap();
                             js_sys::Reflect::set(&params, &"theme".into(), 
                                 &"dark".into()).unwrap();
                             
@@ -80,148 +237,224 @@ pub fn contact() -> Html {
                 }
             }
         }
-        || {}
     });
 
-    let form_data = use_state(|| FormData {
-        name: String::new(),
-        email: String::new(),
-        subject: String::new(),
-        message: String::new(),
-        recaptcha_response: String::new(),
+    // whatever was typed before a reload or a failed send comes back
+    let form_data = use_state(|| LocalStorage::get::<Submission>(DRAFT_KEY).unwrap_or_default());
+    use_effect_with((*form_data).clone(), |draft| {
+        if *draft == Submission::default() {
+            LocalStorage::delete(DRAFT_KEY);
+        } else if let Err(err) = LocalStorage::set(DRAFT_KEY, draft) {
+            tracing::warn!("Couldn't save the draft: {}", err);
+        }
     });
-    
+
     let is_submitting = use_state(|| false);
-    let submission_status = use_state(|| None::<String>);
+    let submission_status = use_state(|| None::<Status>);
+    let progress = use_state(|| None::<Progress>);
+    let attachments = use_state(Vec::<web_sys::File>::new);
+    // for the time-to-submit check; a restored draft was typed on an earlier visit, which took however long
+    // it took, so it counts from the epoch instead of blocking a send straight after the reload
+    let opened = use_state(|| if *form_data == Submission::default() { js_sys::Date::now() } else { 0.0 });
+    // the same check made by a backend that times the form itself, with a token it hands out when the form
+    // opens and again after every send (a stored message uses its token up)
+    let form_token = use_state(|| LocalStorage::get::<String>(FORM_TOKEN_KEY).ok());
+    let sends = use_state(|| 0u32);
+    {
+        let form_token = form_token.clone();
+        use_effect_with(((*config).clone(), *sends), move |(config, sends)| {
+            let url = config.as_ref().and_then(|config| config.backend.backend().form_token_url());
+            if let Some(url) = url.filter(|_| *sends > 0 || form_token.is_none()) {
+                wasm_bindgen_futures::spawn_local(async move {
+                    match fetch_json::<FormToken>(&url).await {
+                        Ok(issued) => {
+                            if let Err(err) = LocalStorage::set(FORM_TOKEN_KEY, &issued.token) {
+                                tracing::warn!("Couldn't save the form token: {}", err);
+                            }
+                            form_token.set(Some(issued.token));
+                        }
+                        Err(err) => tracing::warn!("No form token, the backend will refuse the message: {}", err),
+                    }
+                });
+            }
+            || ()
+        });
+    }
 
-    let on_name_change = {
-        let form_data = form_data.clone();
-        Callback::from(move |e: Event| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            let mut data = (*form_data).clone();
-            data.name = input.value();
-            form_data.set(data);
-        })
+    let outbox = use_state(Outbox::default);
+    {
+        let outbox = outbox.clone();
+        use_effect_with((), move |_| change_outbox(&outbox, Outbox::interrupted));
+    }
+    // retries when the earliest message is due, `online` makes them all due now
+    {
+        let (outbox, submission_status) = (outbox.clone(), submission_status.clone());
+        use_effect_with(((*config).clone(), outbox.next_try()), move |(config, next_try)| {
+            let timeout = config.clone().zip(*next_try).map(|(config, next_try)| {
+                let delay = (next_try - js_sys::Date::now()).clamp(0.0, u32::MAX as f64) as u32;
+                let outbox = outbox.clone();
+                Timeout::new(delay, move || {
+                    wasm_bindgen_futures::spawn_local(flush_outbox(config, outbox, submission_status));
+                })
+            });
+            let online = window().map(|window| {
+                EventListener::new(&window, "online", move |_| change_outbox(&outbox, |outbox| outbox.wake(js_sys::Date::now())))
+            });
+            move || drop((timeout, online))
+        });
+    }
+
+    // a field's problems show once it's been left (or submitted), then update with every keystroke
+    let touched = use_state(HashSet::<Field>::new);
+    let errors = validate::field_errors(&form_data);
+    let error_for = |field: Field| -> Option<FieldError> {
+        errors.iter().find(|(wrong, _)| *wrong == field && touched.contains(&field)).map(|(_, error)| error.clone())
     };
 
-    let on_email_change = {
+    let on_input = |field: Field| {
         let form_data = form_data.clone();
-        Callback::from(move |e: Event| {
+        Callback::from(move |e: InputEvent| {
             let input: HtmlInputElement = e.target_unchecked_into();
             let mut data = (*form_data).clone();
-            data.email = input.value();
+            field.set(&mut data, input.value());
             form_data.set(data);
         })
     };
-
-    let on_subject_change = {
-        let form_data = form_data.clone();
-        Callback::from(move |e: Event| {
-            let input: HtmlInputElement = e.target_unchecked_into();
-            let mut data = (*form_data).clone();
-            data.subject = input.value();
-            form_data.set(data);
+    let on_blur = |field: Field| {
+        let touched = touched.clone();
+        Callback::from(move |_: FocusEvent| {
+            if !touched.contains(&field) {
+                let mut fields = (*touched).clone();
+                fields.insert(field);
+                touched.set(fields);
+            }
         })
     };
 
-    let on_message_change = {
+    let on_honeypot = {
         let form_data = form_data.clone();
-        Callback::from(move |e: Event| {
+        Callback::from(move |e: InputEvent| {
             let input: HtmlInputElement = e.target_unchecked_into();
-            let mut data = (*form_data).clone();
-            data.message = input.value();
-            form_data.set(data);
+            form_data.set(Submission { honeypot: input.value(), ..(*form_data).clone() });
         })
     };
 
     let on_submit = {
         let form_data = form_data.clone();
+        let opened = opened.clone();
+        let (form_token, sends) = (form_token.clone(), sends.clone());
         let is_submitting = is_submitting.clone();
         let submission_status = submission_status.clone();
+        let config = config.clone();
+        let outbox = outbox.clone();
+        let progress = progress.clone();
+        let attachments = attachments.clone();
+        let touched = touched.clone();
         
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
             
+            let Some(config) = (*config).clone() else { return };
             if !*is_submitting {
-                // get reCAPTCHA response using DOM query
-                let recaptcha_response = if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        if let Ok(Some(textarea)) = document.query_selector("textarea[name='g-recaptcha-response']") {
-                            let textarea: web_sys::HtmlTextAreaElement = textarea.dyn_into().unwrap();
-                            textarea.value()
-                        } else {
-                            String::new()
-                        }
-                    } else {
-                        String::new()
-                    }
-                } else {
-                    String::new()
-                };
-                
-                if recaptcha_response.is_empty() {
-                    submission_status.set(Some("Please complete the reCAPTCHA verification.".to_string()));
+                let mut data = (*form_data).clone();
+
+                if let Err(err) = validate::validate(&data) {
+                    touched.set(Field::ALL.into_iter().collect());
+                    submission_status.set(Some(Status::Failed(err)));
+                    return;
+                }
+
+                // the checks the receiving end makes too, no point solving a captcha for a message it'll drop
+                data.form_seconds = Some(((js_sys::Date::now() - *opened) / 1000.0) as u64);
+                data.form_token = (*form_token).clone();
+                let mut sent: Vec<u64> = SessionStorage::get(SENT_KEY).unwrap_or_default();
+                let now = (js_sys::Date::now() / 1000.0) as u64;
+                let checked = config.spam.check(&data).and_then(|()| config.spam.check_time(data.form_seconds));
+                if let Err(reason) = checked.and_then(|()| config.spam.check_rate(&mut sent, now)) {
+                    tracing::warn!("Not sending what looks like spam: {}", reason);
+                    submission_status.set(Some(Status::Failed(ContactError::Spam)));
                     return;
                 }
+                if let Err(err) = SessionStorage::set(SENT_KEY, &sent) {
+                    tracing::warn!("Couldn't count the message against the rate limit: {}", err);
+                }
+
+                if matches!(config.captcha, Some(CaptchaConfig::Recaptcha { .. })) {
+                    data.recaptcha_response = recaptcha_response();
+
+                    if data.recaptcha_response.is_empty() {
+                        submission_status.set(Some(Status::Failed(ContactError::Captcha(
+                            "Please complete the reCAPTCHA verification.".to_string()
+                        ))));
+                        return;
+                    }
+                }
                 
                 is_submitting.set(true);
                 submission_status.set(None);
                 
-                let mut data = (*form_data).clone();
-                data.recaptcha_response = recaptcha_response;
-                
                 let is_submitting_clone = is_submitting.clone();
                 let submission_status_clone = submission_status.clone();
                 let form_data_clone = form_data.clone();
+                let outbox = outbox.clone();
+                let progress = progress.clone();
+                let attachments = attachments.clone();
+                let opened = opened.clone();
+                let sends = sends.clone();
                 
                 wasm_bindgen_futures::spawn_local(async move {
-                    let result = submit_form(data).await;
+                    let backend = config.backend.backend();
+                    let on_progress = {
+                        let progress = progress.clone();
+                        Callback::from(move |now| progress.set(Some(now)))
+                    };
+                    let result = submit_with_captcha(&config, data.clone(), &attachments, on_progress).await;
+                    progress.set(None);
                     is_submitting_clone.set(false);
                     
                     match result {
-                        Ok(_) => {
-                            submission_status_clone.set(Some("Message submitted successfully. I'll get back to you soon.".to_string()));
+                        Ok(()) => {
+                            submission_status_clone.set(Some(Status::Sent));
                             // reset form
-                            form_data_clone.set(FormData {
-                                name: String::new(),
-                                email: String::new(),
-                                subject: String::new(),
-                                message: String::new(),
-                                recaptcha_response: String::new(),
-                            });
-                            
-                            // reset reCAPTCHA by calling the global reset function if available
-                            // reset on error too
-                            if let Some(window) = web_sys::window() {
-                                if let Ok(grecaptcha) = js_sys::Reflect::get(&window, &"grecaptcha".into()) {
-                                    if let Ok(reset_fn) = js_sys::Reflect::get(&grecaptcha, &"reset".into()) {
-                                        if let Ok(reset_fn) = reset_fn.dyn_into::<js_sys::Function>() {
-                                            let _ = reset_fn.call0(&js_sys::Object::new());
-                                        }
-                                    }
-                                }
-                            }
+                            form_data_clone.set(Submission::default());
+                            attachments.set(Vec::new());
+                            opened.set(js_sys::Date::now());
+                            sends.set(*sends + 1);
+                        }
+                        // files can't wait in localStorage, so a message with attachments stays in the form instead
+                        Err(err) if err.is_transient() && attachments.is_empty() => {
+                            tracing::warn!("Contact backend unavailable, queueing the message: {}", err);
+                            let error = match err {
+                                ContactError::Network(_) => format!("Couldn't reach the {}: {}", backend.name(), err),
+                                _ => format!("The {} couldn't take it: {}", backend.name(), err),
+                            };
+                            change_outbox(&outbox, |outbox| outbox.push(data.clone(), js_sys::Date::now(), Some(error)));
+                            // it's safe in the outbox now, with the form token it'll use up
+                            form_data_clone.set(Submission::default());
+                            sends.set(*sends + 1);
+                            submission_status_clone.set(Some(Status::Queued {
+                                error: err,
+                                mailto: config.fallback_email.as_deref().map(|address| mailto_url(address, &data)),
+                            }));
                         }
                         Err(err) => {
-                            submission_status_clone.set(Some(format!("Error sending message: {}", err)));
-                            
-                            // reset reCAPTCHA on error too
-                            if let Some(window) = web_sys::window() {
-                                if let Ok(grecaptcha) = js_sys::Reflect::get(&window, &"grecaptcha".into()) {
-                                    if let Ok(reset_fn) = js_sys::Reflect::get(&grecaptcha, &"reset".into()) {
-                                        if let Ok(reset_fn) = reset_fn.dyn_into::<js_sys::Function>() {
-                                            let _ = reset_fn.call0(&js_sys::Object::new());
-                                        }
-                                    }
-                                }
-                            }
+                            tracing::warn!("Contact message not sent: {:?}", err);
+                            submission_status_clone.set(Some(Status::Failed(err)));
                         }
                     }
+                    // a token is only good for one try either way
+                    reset_recaptcha();
                 });
             }
         })
     };
 
+    let accepts_files = config.as_ref().is_some_and(|config| config.backend.backend().multipart(&form_data).is_some());
+    let on_attachments = {
+        let attachments = attachments.clone();
+        Callback::from(move |files| attachments.set(files))
+    };
+
     html! {
         <div class="min-h-screen pt-15 pb-10" >
             <div class="max-w-4xl mx-auto px-4 sm:px-6 lg:px-8">
@@ -233,14 +466,14 @@ pub fn contact() -> Html {
                 >
                     <div class="relative z-10">
                         <Heading 
-                            src = "/static/contact/CONTACT_1.png"
-                            alt = "Contact"
+                            text="Contact"
                             sub_heading = "Want to drop a message? Collaborate? Ask a question?"
                         ></Heading>
                         
                         // contact form
                         <div class="max-w-2xl mx-auto mb-12">
-                            <form onsubmit={on_submit} class="space-y-6">
+                            // novalidate: the messages under the fields replace the browser's bubbles
+                            <form onsubmit={on_submit} novalidate=true class="space-y-6">
                                 // name and email row
                                 <div class="grid md:grid-cols-2 gap-6">
                                     <div>
@@ -252,15 +485,16 @@ pub fn contact() -> Html {
                                             id="name"
                                             name="name"
                                             value={form_data.name.clone()}
-                                            onchange={on_name_change}
+                                            oninput={on_input(Field::Name)}
+                                            onblur={on_blur(Field::Name)}
+                                            aria-invalid={error_for(Field::Name).is_some().to_string()}
+                                            aria-describedby="name-error"
                                             required=true
                                             disabled={*is_submitting}
-                                            class="w-full px-4 py-3 bg-[#1a1a1a] border border-gray-600 rounded-lg 
-                                                   text-white placeholder-gray-400 focus:border-red-600 focus:ring-1 
-                                                   focus:ring-red-600 focus:outline-none transition-colors
-                                                   disabled:opacity-50 disabled:cursor-not-allowed"
+                                            class={classes!(INPUT_CLASS, if error_for(Field::Name).is_some() { "border-red-600" } else { "border-gray-600" })}
                                             placeholder="Your name"
                                         />
+                                        {field_feedback(Field::Name, &form_data.name, error_for(Field::Name))}
                                     </div>
                                     <div>
                                         <label for="email" class="block text-sm font-medium text-gray-300 mb-2">
@@ -271,15 +505,16 @@ pub fn contact() -> Html {
                                             id="email"
                                             name="email"
                                             value={form_data.email.clone()}
-                                            onchange={on_email_change}
+                                            oninput={on_input(Field::Email)}
+                                            onblur={on_blur(Field::Email)}
+                                            aria-invalid={error_for(Field::Email).is_some().to_string()}
+                                            aria-describedby="email-error"
                                             required=true
                                             disabled={*is_submitting}
-                                            class="w-full px-4 py-3 bg-[#1a1a1a] border border-gray-600 rounded-lg 
-                                                   text-white placeholder-gray-400 focus:border-red-600 focus:ring-1 
-                                                   focus:ring-red-600 focus:outline-none transition-colors
-                                                   disabled:opacity-50 disabled:cursor-not-allowed"
+                                            class={classes!(INPUT_CLASS, if error_for(Field::Email).is_some() { "border-red-600" } else { "border-gray-600" })}
                                             placeholder="your.email@example.com"
                                         />
+                                        {field_feedback(Field::Email, &form_data.email, error_for(Field::Email))}
                                     </div>
                                 </div>
                                 
@@ -293,15 +528,16 @@ pub fn contact() -> Html {
                                         id="subject"
                                         name="subject"
                                         value={form_data.subject.clone()}
-                                        onchange={on_subject_change}
+                                        oninput={on_input(Field::Subject)}
+                                        onblur={on_blur(Field::Subject)}
+                                        aria-invalid={error_for(Field::Subject).is_some().to_string()}
+                                        aria-describedby="subject-error"
                                         required=false
                                         disabled={*is_submitting}
-                                        class="w-full px-4 py-3 bg-[#1a1a1a] border border-gray-600 rounded-lg 
-                                               text-white placeholder-gray-400 focus:border-red-600 focus:ring-1 
-                                               focus:ring-red-600 focus:outline-none transition-colors
-                                               disabled:opacity-50 disabled:cursor-not-allowed"
+                                        class={classes!(INPUT_CLASS, if error_for(Field::Subject).is_some() { "border-red-600" } else { "border-gray-600" })}
                                         placeholder="What's this about?"
                                     />
+                                    {field_feedback(Field::Subject, &form_data.subject, error_for(Field::Subject))}
                                 </div>
                                 
                                 // message
@@ -314,31 +550,77 @@ pub fn contact() -> Html {
                                         name="message"
                                         rows="6"
                                         value={form_data.message.clone()}
-                                        onchange={on_message_change}
+                                        oninput={on_input(Field::Message)}
+                                        onblur={on_blur(Field::Message)}
+                                        aria-invalid={error_for(Field::Message).is_some().to_string()}
+                                        aria-describedby="message-error"
                                         required=true
                                         disabled={*is_submitting}
-                                        class="w-full px-4 py-3 bg-[#1a1a1a] border border-gray-600 rounded-lg 
-                                               text-white placeholder-gray-400 focus:border-red-600 focus:ring-1 
-                                               focus:ring-red-600 focus:outline-none transition-colors resize-vertical
-                                               disabled:opacity-50 disabled:cursor-not-allowed"
+                                        class={classes!(INPUT_CLASS, if error_for(Field::Message).is_some() { "border-red-600" } else { "border-gray-600" }, "resize-vertical")}
                                         placeholder="What's on your mind?"
                                     />
+                                    {field_feedback(Field::Message, &form_data.message, error_for(Field::Message))}
                                 </div>
 
+                                // attachments, for the services that take files
+                                if accepts_files {
+                                    <div>
+                                        <span class="block text-sm font-medium text-gray-300 mb-2">{"Attachments"}</span>
+                                        <AttachmentPicker
+                                            files={(*attachments).clone()}
+                                            on_change={on_attachments}
+                                            disabled={*is_submitting}
+                                        />
+                                    </div>
+                                }
+
                                 // submit button + recaptcha row
                                 <div class="flex flex-col sm:flex-row items-center justify-center gap-15">
-                                    // recaptcha container
-                                        <div 
-                                            id="recaptcha-container"
-                                            class="g-recaptcha transform scale-90 sm:scale-100"
-                                            data-sitekey="6LfHdcsrAAAAAA4ndXu6pT_KvO1sdOxdPIRX3q12"
-                                            data-theme="dark"
-                                        ></div>
+                                    // recaptcha container, only when the configured backend checks one
+                                    {match *progress {
+                                        Some(Progress::Proving(done)) => html! {
+                                            <LoadingBar label="Proving you're human" progress={Some(done)} />
+                                        },
+                                        Some(Progress::Uploading { loaded, total }) => html! {
+                                            <LoadingBar
+                                                label="Uploading"
+                                                progress={Some(loaded / total.max(1.0))}
+                                                detail={format!("{} / {}", format_bytes(loaded as u64), format_bytes(total as u64))}
+                                            />
+                                        },
+                                        None => html! {},
+                                    }}
+                                    {if let Some(site_key) = site_key.clone() {
+                                        html! {
+                                            <div 
+                                                id="recaptcha-container"
+                                                class="g-recaptcha transform scale-90 sm:scale-100"
+                                                data-sitekey={site_key}
+                                                data-theme="dark"
+                                            ></div>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
                                     
+                                    // a trap for bots that fill in every field, out of sight and out of the tab order
+                                    <div class="absolute -left-[9999px] w-px h-px overflow-hidden" aria-hidden="true">
+                                        <label for="website">{"Website"}</label>
+                                        <input
+                                            type="text"
+                                            id="website"
+                                            name="website"
+                                            tabindex="-1"
+                                            autocomplete="off"
+                                            value={form_data.honeypot.clone()}
+                                            oninput={on_honeypot}
+                                        />
+                                    </div>
+
                                     // submit button
                                     <button
                                         type="submit"
-                                        disabled={*is_submitting}
+                                        disabled={*is_submitting || config.is_none()}
                                         class="px-8 py-3 bg-red-600 hover:bg-red-700 disabled:bg-gray-600 
                                                text-white font-semibold rounded-lg transition-colors duration-200
                                                disabled:cursor-not-allowed disabled:opacity-50 cursor-pointer
@@ -349,22 +631,104 @@ pub fn contact() -> Html {
                                 </div>
                                 
                                 // status message
-                                {if let Some(ref status) = *submission_status {
-                                    let (bg_color, text_color, border_color) = if status.contains("successfully") {
-                                        ("bg-green-900/20", "text-green-400", "border-green-500")
-                                    } else {
-                                        ("bg-red-900/20", "text-red-400", "border-red-600")
-                                    };
-                                    
-                                    html! {
-                                        <div class={format!("mt-4 p-4 rounded-lg border {} {} {}", bg_color, border_color, text_color)}>
-                                            {status}
+                                {match &*submission_status {
+                                    Some(Status::Sent) => html! {
+                                        <div class="mt-4 p-4 rounded-lg border bg-green-900/20 border-green-500 text-green-400">
+                                            {"Message submitted successfully. I'll get back to you soon."}
+                                        </div>
+                                    },
+                                    Some(Status::Failed(err)) => html! {
+                                        <div class="mt-4 p-4 rounded-lg border bg-red-900/20 border-red-600 text-red-400" role="alert">
+                                            {match err {
+                                                ContactError::Invalid { .. } => "Please fix the highlighted fields.".to_string(),
+                                                ContactError::Spam => err.to_string(),
+                                                err => format!("Error sending message: {}", err),
+                                            }}
                                         </div>
-                                    }
-                                } else {
-                                    html! {}
+                                    },
+                                    Some(Status::Queued { error, mailto }) => html! {
+                                        <div class="mt-4 p-4 rounded-lg border bg-yellow-900/20 border-yellow-500 text-yellow-400">
+                                            {match error {
+                                                ContactError::Network(_) => "You seem to be offline. Your message is saved in the outbox below and will be sent as soon as the connection is back.",
+                                                _ => "The server is having trouble. Your message is saved in the outbox below and will be retried in a little while.",
+                                            }}
+                                            if config.as_ref().is_some_and(|config| !config.retries_unattended()) {
+                                                <p class="mt-2">{"When it's ready to go, the outbox will ask you to tick the reCAPTCHA again."}</p>
+                                            }
+                                            {if let Some(mailto) = mailto {
+                                                html! {
+                                                    <a href={mailto.clone()} class="block mt-2 text-white underline hover:text-red-400">
+                                                        {"Send it from your mail app instead"}
+                                                    </a>
+                                                }
+                                            } else {
+                                                html! {}
+                                            }}
+                                        </div>
+                                    },
+                                    None => html! {},
                                 }}
                             </form>
+
+                            // messages waiting for the connection
+                            if !outbox.messages.is_empty() {
+                                <div class="mt-8">
+                                    <h2 class="text-sm font-mono text-gray-400 mb-2">{"Outbox"}</h2>
+                                    <ul class="space-y-2">
+                                        { for outbox.messages.iter().map(|queued| {
+                                            let id = queued.id;
+                                            let (label, class) = match &queued.status {
+                                                QueueStatus::Sending => ("Sending...".to_string(), "text-yellow-400"),
+                                                QueueStatus::Waiting if queued.attempts == 0 => ("Waiting to send".to_string(), "text-yellow-400"),
+                                                QueueStatus::Waiting => (format!("Waiting to retry ({} failed attempts)", queued.attempts), "text-yellow-400"),
+                                                QueueStatus::NeedsCaptcha => ("Tick the reCAPTCHA above, then Send".to_string(), "text-yellow-400"),
+                                                QueueStatus::Rejected(err) => (format!("Not sent: {}", err), "text-red-400"),
+                                            };
+                                            let on_send = {
+                                                let (config, outbox, status) = (config.clone(), outbox.clone(), submission_status.clone());
+                                                Callback::from(move |_| {
+                                                    if let Some(config) = (*config).clone() {
+                                                        send_answered(config, id, outbox.clone(), status.clone());
+                                                    }
+                                                })
+                                            };
+                                            let on_cancel = {
+                                                let outbox = outbox.clone();
+                                                Callback::from(move |_| { change_outbox(&outbox, |outbox| outbox.remove(id)); })
+                                            };
+                                            // a rejected message goes back into the form to be fixed
+                                            let on_edit = {
+                                                let (outbox, form_data, opened) = (outbox.clone(), form_data.clone(), opened.clone());
+                                                Callback::from(move |_| {
+                                                    if let Some(queued) = change_outbox(&outbox, |outbox| outbox.remove(id)) {
+                                                        form_data.set(queued.submission);
+                                                        opened.set(0.0);    // like a restored draft
+                                                    }
+                                                })
+                                            };
+                                            html! {
+                                                <li class="flex items-center gap-3 p-3 bg-[#1a1a1a] border border-gray-600 rounded-lg">
+                                                    <div class="min-w-0 flex-1">
+                                                        <div class="truncate text-white">
+                                                            {if queued.submission.subject.is_empty() { &queued.submission.message } else { &queued.submission.subject }}
+                                                        </div>
+                                                        <div class={classes!("text-xs", "font-mono", class)} title={queued.last_error.clone()}>{label}</div>
+                                                    </div>
+                                                    if queued.status == QueueStatus::NeedsCaptcha {
+                                                        <button type="button" onclick={on_send} class="text-sm text-white underline hover:text-red-400 cursor-pointer">{"Send"}</button>
+                                                    }
+                                                    if matches!(queued.status, QueueStatus::Rejected(_)) {
+                                                        <button type="button" onclick={on_edit} class="text-sm text-white underline hover:text-red-400 cursor-pointer">{"Edit"}</button>
+                                                    }
+                                                    if queued.status != QueueStatus::Sending {
+                                                        <button type="button" onclick={on_cancel} class="text-sm text-white underline hover:text-red-400 cursor-pointer">{"Cancel"}</button>
+                                                    }
+                                                </li>
+                                            }
+                                        })}
+                                    </ul>
+                                </div>
+                            }
                         </div>
 
                         <SocialButtons
@@ -379,43 +743,158 @@ pub fn contact() -> Html {
     }
 }
 
-async fn submit_form(form_data: FormData) -> Result<FormResponse, String> {
-    let url = "https://script.google.com/macros/s/AKfycbwLckDBcah084esScg4oIG0IvmgCb_KPfsjPS979BxWQj8fFVvP6Ia_AF2gbOUHgWgajw/exec";
-    
-    // convert form data to JSON string manually to match FormEasy expectations
-    let json_body = serde_json::to_string(&form_data)
-        .map_err(|e| format!("Failed to serialize form data: {:?}", e))?;
-    
-    let response = Request::post(url)
-        .header("Content-Type", "text/plain;charset=utf-8")
-        .body(json_body)
-        .map_err(|e| format!("Failed to create request: {:?}", e))?
-        .send()
-        .await
-        .map_err(|e| {
-            // more detailed error handling
-            let error_msg = format!("{:?}", e);
-            if error_msg.contains("CORS") || error_msg.contains("Failed to fetch") {
-                "CORS error: Make sure Google Apps Script is properly configured and deployed as a web app with 'Anyone' access.".to_string()
-            } else {
-                format!("Network error: {}", error_msg)
-            }
-        })?;
-
-    if response.ok() {
-        // try parse as JSON, but handle cases where it might not be JSON
-        match response.json::<FormResponse>().await {
-            Ok(form_response) => Ok(form_response),
-            Err(_) => {
-                // if JSON parsing fails, assume success if we got 200 response
-                Ok(FormResponse {
-                    result: "success".to_string()
-                })
+// starts src/bin/pow_worker.rs from a blob, the way wasm-bindgen's no-modules output wants to be loaded
+fn spawn_worker() -> Result<web_sys::Worker, String> {
+    let origin = window().ok_or("no window")?.location().origin().map_err(|e| format!("{:?}", e))?;
+    let script = format!("importScripts('{0}{1}'); wasm_bindgen('{0}{2}');", origin, WORKER_SCRIPT, WORKER_WASM);
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/javascript");
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&script.into()), &options)
+        .map_err(|e| format!("{:?}", e))?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(|e| format!("{:?}", e))?;
+    let worker = web_sys::Worker::new(&url).map_err(|e| format!("{:?}", e));
+    let _ = web_sys::Url::revoke_object_url(&url);
+    worker
+}
+
+// a promise and the functions that settle it, for awaiting callback apis like workers and xhr
+fn promise() -> (js_sys::Promise, js_sys::Function, js_sys::Function) {
+    let (mut resolve, mut reject) = (None, None);
+    let promise = js_sys::Promise::new(&mut |res, rej| {
+        resolve = Some(res);
+        reject = Some(rej);
+    });
+    (promise, resolve.expect("executor runs synchronously"), reject.expect("executor runs synchronously"))
+}
+
+// fetches a challenge and has the worker solve it, reporting roughly how far along it is
+async fn prove_work(challenge_url: &str, progress: Callback<Progress>) -> Result<Proof, ContactError> {
+    let response = Request::get(challenge_url).send().await.map_err(|e| ContactError::Network(e.to_string()))?;
+    if !response.ok() {
+        return Err(ContactError::http(response.status(), &response.text().await.unwrap_or_default()));
+    }
+    let challenge: Challenge = response.json().await.map_err(|e| ContactError::Captcha(format!("bad challenge: {}", e)))?;
+    let worker = spawn_worker().map_err(|e| ContactError::Captcha(format!("couldn't start the worker: {}", e)))?;
+
+    // the promise settles from the worker's messages
+    let (done, resolve, reject) = promise();
+    let difficulty = challenge.difficulty;
+    let on_message = Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+        match event.data().as_string().and_then(|json| serde_json::from_str::<WorkerMessage>(&json).ok()) {
+            Some(WorkerMessage::Progress { tried }) => progress.emit(Progress::Proving(captcha::expected_progress(tried, difficulty))),
+            Some(WorkerMessage::Solved { nonce }) => {
+                let _ = resolve.call1(&JsValue::NULL, &JsValue::from_f64(nonce as f64));
             }
+            None => {}
         }
-    } else {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        Err(format!("HTTP {} error: {}", status, error_text))
+    });
+    let on_error = Closure::<dyn Fn(web_sys::Event)>::new(move |_| {
+        let _ = reject.call1(&JsValue::NULL, &"the captcha worker crashed".into());
+    });
+    worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    let _ = worker.post_message(&serde_json::to_string(&challenge).unwrap_or_default().into());
+
+    let result = wasm_bindgen_futures::JsFuture::from(done).await;
+    worker.terminate();
+    let nonce = result.map_err(|e| ContactError::Captcha(e.as_string().unwrap_or_else(|| format!("{:?}", e))))?;
+    Ok(Proof { salt: challenge.salt, nonce: nonce.as_f64().unwrap_or_default() as u64 })
+}
+
+// answers the proof of work first when the backend wants one, then posts as json or, with files, as multipart
+async fn submit_with_captcha(
+    config: &ContactConfig,
+    mut submission: Submission,
+    files: &[web_sys::File],
+    progress: Callback<Progress>,
+) -> Result<(), ContactError> {
+    if let Some(CaptchaConfig::ProofOfWork { challenge_url }) = &config.captcha {
+        submission.proof_of_work = Some(prove_work(challenge_url, progress.clone()).await?);
+    }
+    let backend = config.backend.backend();
+    if files.is_empty() {
+        return submit_form(backend.as_ref(), &submission).await;
+    }
+    let post = backend
+        .multipart(&submission)
+        .ok_or_else(|| ContactError::Request(format!("the {} can't take attachments", backend.name())))?;
+    upload(backend.as_ref(), post, files, progress).await
+}
+
+// xhr rather than fetch, fetch can't tell how far along an upload is
+async fn upload(backend: &dyn ContactBackend, post: MultipartPost, files: &[web_sys::File], progress: Callback<Progress>) -> Result<(), ContactError> {
+    let failed = |e: JsValue| ContactError::Request(format!("{:?}", e));
+    let form = web_sys::FormData::new().map_err(failed)?;
+    for (name, value) in &post.fields {
+        form.append_with_str(name, value).map_err(failed)?;
+    }
+    for file in files {
+        form.append_with_blob_and_filename(ATTACHMENT_FIELD, file, &file.name()).map_err(failed)?;
+    }
+    let xhr = web_sys::XmlHttpRequest::new().map_err(failed)?;
+    xhr.open("POST", &post.url).map_err(failed)?;
+    for (name, value) in &post.headers {
+        xhr.set_request_header(name, value).map_err(failed)?;
     }
-}
\ No newline at end of file
+
+    let (done, resolve, reject) = promise();
+    let on_load = Closure::<dyn Fn()>::new(move || {
+        let _ = resolve.call0(&JsValue::NULL);
+    });
+    let on_error = Closure::<dyn Fn()>::new(move || {
+        let _ = reject.call0(&JsValue::NULL);
+    });
+    let on_progress = Closure::<dyn Fn(web_sys::ProgressEvent)>::new(move |event: web_sys::ProgressEvent| {
+        if event.length_computable() {
+            progress.emit(Progress::Uploading { loaded: event.loaded(), total: event.total() });
+        }
+    });
+    xhr.set_onload(Some(on_load.as_ref().unchecked_ref()));
+    xhr.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    xhr.set_onabort(Some(on_error.as_ref().unchecked_ref()));
+    if let Ok(uploading) = xhr.upload() {
+        uploading.set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+    }
+    xhr.send_with_opt_form_data(Some(&form)).map_err(failed)?;
+
+    if wasm_bindgen_futures::JsFuture::from(done).await.is_err() {
+        return Err(if blocked_by_cors(&post.url).await {
+            ContactError::Cors { url: post.url }
+        } else {
+            ContactError::Network("the upload didn't go through".to_string())
+        });
+    }
+    let text = xhr.response_text().ok().flatten().unwrap_or_default();
+    backend.response(xhr.status().unwrap_or_default(), &text)
+}
+
+// a fetch that fails gets tried again without cors: if that one gets through the service is up,
+// it just doesn't answer this origin
+async fn blocked_by_cors(url: &str) -> bool {
+    is_online() && Request::get(url).mode(web_sys::RequestMode::NoCors).send().await.is_ok()
+}
+
+async fn submit_form(backend: &dyn ContactBackend, submission: &Submission) -> Result<(), ContactError> {
+    let (url, headers, body) = match backend.request(submission)? {
+        Outgoing::Post { url, headers, body } => (url, headers, body),
+        Outgoing::Open(url) => {
+            let window = window().ok_or_else(|| ContactError::Request("no window".to_string()))?;
+            return window.location().set_href(&url).map_err(|e| ContactError::Request(format!("{:?}", e)));
+        }
+    };
+
+    let mut request = Request::post(&url);
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    // fetch rejects without a status for offline, dns and cors failures alike, the browser keeps which one to itself
+    let sent = request.body(body).map_err(|e| ContactError::Request(e.to_string()))?.send().await;
+    let response = match sent {
+        Ok(response) => response,
+        Err(_) if blocked_by_cors(&url).await => return Err(ContactError::Cors { url }),
+        Err(err) => return Err(ContactError::Network(err.to_string())),
+    };
+
+    let text = response.text().await.unwrap_or_default();
+    backend.response(response.status(), &text)
+}
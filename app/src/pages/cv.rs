@@ -0,0 +1,107 @@
+// pages/cv.rs
+use yew::prelude::*;
+use yew_router::prelude::*;
+use web_sys::window;
+
+use crate::content::ExperienceItem;
+use crate::cv;
+use crate::router::Route;
+
+// one experience or education entry, laid out like the pdf: title and dates, institution, then the points
+fn entry(item: &ExperienceItem) -> Html {
+    html! {
+        <div class="mb-4 break-inside-avoid">
+            <div class="flex justify-between items-baseline gap-4">
+                <h3 class="font-bold text-[11pt]">{&item.title}</h3>
+                <span class="text-gray-600 text-[10pt] whitespace-nowrap">{&item.date}</span>
+            </div>
+            <p class="text-gray-600 text-[10pt]">{&item.institution}</p>
+            if let Some(description) = &item.description {
+                <ul class="mt-1 space-y-0.5 text-[10pt]">
+                    { for description.iter().map(|point| html! {
+                        <li class="flex items-start">
+                            <span class="text-red-600 mr-2">{"•"}</span>
+                            <span>{point}</span>
+                        </li>
+                    })}
+                </ul>
+            }
+        </div>
+    }
+}
+
+fn section(title: &str, body: Html) -> Html {
+    html! {
+        <section class="mt-6">
+            <h2 class="text-red-600 font-bold text-[13pt] border-b-2 border-red-600 mb-3">{title}</h2>
+            {body}
+        </section>
+    }
+}
+
+// the cv on a sheet of paper, for printing. built-in content only, not the server's, so it always matches
+// the downloadable pdf, which is rendered from the same at build time (src/cv.rs)
+#[function_component(Cv)]
+pub fn cv() -> Html {
+    let cv::Cv { profile, experience, education, skills } = cv::Cv::built_in();
+
+    use_effect_with((), |_| {
+        if let Some(window) = window() {
+            window.scroll_to_with_x_and_y(0.0, 0.0);
+        }
+        || {}
+    });
+
+    let print = Callback::from(|_| {
+        if let Some(window) = window() {
+            let _ = window.print();
+        }
+    });
+
+    html! {
+        <div class="min-h-screen pt-4 pb-10 px-4">
+            // toolbar, left out of the printout by the print stylesheet (styles/main.css)
+            <div class="max-w-[210mm] mx-auto mb-4 flex flex-wrap justify-end gap-3 font-mono text-sm">
+                <Link<Route> to={Route::About} classes="mr-auto px-4 py-2 text-gray-300 hover:text-white">
+                    {"< Back"}
+                </Link<Route>>
+                <button
+                    onclick={print}
+                    class="px-4 py-2 bg-[#1a1a1a] hover:bg-red-600 text-white border-2 border-black hover:border-red-900 rounded-lg cursor-pointer transition-colors duration-300"
+                >
+                    {"Print"}
+                </button>
+                <a
+                    href={cv::PDF_PATH}
+                    download="KR_CV.pdf"
+                    class="px-4 py-2 bg-red-600 hover:bg-red-700 text-white border-2 border-black rounded-lg transition-colors duration-300"
+                >
+                    {"Download PDF"}
+                </a>
+            </div>
+
+            <article id="cv" class="max-w-[210mm] mx-auto bg-white text-gray-900 p-[14mm] shadow-2xl leading-snug">
+                <header>
+                    <h1 class="font-bold text-[24pt] leading-tight">{&profile.name}</h1>
+                    <ul class="mt-1 text-[9pt]">
+                        { for profile.links.iter().map(|link| html! {
+                            <li>
+                                {format!("{}: ", link.label)}
+                                <a href={link.url.clone()} target="_blank" class="text-red-600 hover:underline">{&link.url}</a>
+                            </li>
+                        })}
+                    </ul>
+                    <div class="mt-3 space-y-1 text-[10pt]">
+                        { for profile.summary.iter().map(|paragraph| html! { <p>{paragraph}</p> }) }
+                    </div>
+                </header>
+
+                {section("Experience", html! { for experience.iter().map(entry) })}
+                {section("Education", html! { for education.iter().map(entry) })}
+                {section("Technologies", html! {
+                    <p class="text-[10pt]">{skills.iter().map(|skill| skill.name.as_str()).collect::<Vec<_>>().join(", ")}</p>
+                })}
+            </article>
+        </div>
+    }
+}
@@ -1,118 +1,92 @@
 // pages/doom_projects.rs
+use std::collections::HashMap;
 use yew::prelude::*;
+use yew_router::prelude::*;
+use serde::{Deserialize, Serialize};
 use web_sys::window;
+use crate::doom::stats::WadStats;
 use crate::components::doom_project_item::{DoomProjectItem};
 use crate::components::heading::{Heading};
+use crate::api::{fetch_json, use_content};
+use crate::pages::play::engine_installed;
+use crate::content::{self, DOOM_PROJECTS_API};
 
-// struct to hold project data
-#[derive(Clone, PartialEq)]
-pub struct Project {
-    pub title: String,
-    pub description: String,
-    pub image_src: String,
-    pub image_alt: Option<String>,
-    pub additional_images: Vec<String>,
+// query string for deep links, e.g. /doom-projects?map=jammy&image=2
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DoomProjectsQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<usize>,
+}
+
+// url friendly project id derived from the title ("Cathedral of Charybdis" -> "cathedral-of-charybdis")
+pub fn slug(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// project slug -> released wad, relative to the app directory (also read by the wad build tool)
+const WADS_PATH: &str = "/static/doom_projects/wads.json";
+// generated by `cargo run --bin wad -- stats` from WADS_PATH, keyed by project slug
+const MAP_STATS_PATH: &str = "/static/doom_projects/map_stats.json";
+
+pub async fn fetch_map_stats() -> Result<HashMap<String, WadStats>, String> {
+    fetch_json(MAP_STATS_PATH).await
+}
+
+// site urls of the released wads
+pub async fn fetch_wad_urls() -> Result<HashMap<String, String>, String> {
+    let wads: HashMap<String, String> = fetch_json(WADS_PATH).await?;
+    Ok(wads.into_iter().map(|(slug, path)| (slug, format!("/{}", path.trim_start_matches('/')))).collect())
 }
 
-// TODO: make smaller thumbnails if page loads slowly (involves refactoring image_src to thumbnail_src)
 #[function_component(DoomProjects)]
 pub fn doom_projects() -> Html {
-    use_effect_with((), {
-        move |_| {
-            // scroll to top when component mounts
-            if let Some(window) = window() {
-                window.scroll_to_with_x_and_y(0.0, 0.0);
+    let query = use_location()
+        .and_then(|location| location.query::<DoomProjectsQuery>().ok())
+        .unwrap_or_default();
+
+    use_effect_with(query.map.clone(), {
+        move |map| {
+            let document = window().and_then(|window| window.document());
+            match map.as_ref().and_then(|map| document?.get_element_by_id(map)) {
+                // deep link: bring the linked map into view
+                Some(element) => element.scroll_into_view(),
+                // scroll to top when component mounts
+                None => {
+                    if let Some(window) = window() {
+                        window.scroll_to_with_x_and_y(0.0, 0.0);
+                    }
+                }
             }
             || {}
         }
     });
 
-    // project item definitions (temporarily hardcoded, can be moved to db later)
-    let projects = vec![
-        Project {
-            title: "Cathedral of Charybdis".to_string(),
-            description: "A dark and atmospheric map. All are swallowed by the shadow of the cathedral. Can you resist the evil cult of Charybdis?".to_string(),
-            image_src: "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_1.png".to_string(),
-            image_alt: Some("Cathedral of Charybdis".to_string()),
-            additional_images: vec![
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_2.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_3.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_4.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_5.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_6.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_7.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_8.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_9.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_10.png".to_string(),
-                "/static/doom_projects/cathedral_of_charybdis/cathedral_of_charybdis_11.png".to_string(),
-            ],
-        },
-        Project {
-            title: "Jammy".to_string(),
-            description: "A gimmicky challenge map involving a lot of scripted terrain transformation and light slaughter. Inspired by Doom64 MAP19. Push through and don't stand still!".to_string(),
-            image_src: "/static/doom_projects/jammy/jammy_1.png".to_string(),
-            image_alt: Some("Jammy".to_string()),
-            additional_images: vec![
-                "/static/doom_projects/jammy/jammy_2.png".to_string(),
-                "/static/doom_projects/jammy/jammy_3.png".to_string(),
-                "/static/doom_projects/jammy/jammy_4.png".to_string(),
-                "/static/doom_projects/jammy/jammy_5.png".to_string(),
-                "/static/doom_projects/jammy/jammy_6.png".to_string(),
-            ],
+    // stats panels are optional, the page works without them
+    let map_stats = use_state(HashMap::<String, WadStats>::new);
+    let playable = use_state(|| false);
+    {
+        let (map_stats, playable) = (map_stats.clone(), playable.clone());
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_map_stats().await {
+                    Ok(loaded) => map_stats.set(loaded),
+                    Err(err) => tracing::warn!("Map stats unavailable: {}", err),
+                }
+                playable.set(engine_installed().await);
+            });
+            || ()
+        });
+    }
 
-        },
-        Project {
-            title: "Whispers of Change".to_string(),
-            description: "A short and atmospheric map with story elements and light puzzles. Co-authored by Erik Lindstrand and made in 1 day for Chalmers March GameJam 2024.".to_string(),
-            image_src: "/static/doom_projects/whispers_of_change/whispers_of_change_1.png".to_string(),
-            image_alt: Some("Whispers of Change".to_string()),
-            additional_images: vec![
-                "/static/doom_projects/whispers_of_change/whispers_of_change_2.png".to_string(),
-                "/static/doom_projects/whispers_of_change/whispers_of_change_3.png".to_string(),
-                "/static/doom_projects/whispers_of_change/whispers_of_change_4.png".to_string(),
-                "/static/doom_projects/whispers_of_change/whispers_of_change_5.png".to_string(),
-            ],
-        },
-        Project {
-            title: "SWEDEN".to_string(),
-            description: "An adventure map that has nothing to do with Sweden. Explore the demonic presence aroused in the ruins by human interference.".to_string(),
-            image_src: "/static/doom_projects/sweden/sweden_1.png".to_string(),
-            image_alt: Some("SWEDEN".to_string()),
-            additional_images: vec![
-                "/static/doom_projects/sweden/sweden_2.png".to_string(),
-                "/static/doom_projects/sweden/sweden_3.png".to_string(),
-                "/static/doom_projects/sweden/sweden_4.png".to_string(),
-                "/static/doom_projects/sweden/sweden_5.png".to_string(),
-                "/static/doom_projects/sweden/sweden_6.png".to_string(),
-                "/static/doom_projects/sweden/sweden_7.png".to_string(),
-                "/static/doom_projects/sweden/sweden_8.png".to_string(),
-            ],
-            
-        },
-        Project {
-            title: "ΣΣΑΣ".to_string(),
-            description: "A map that is definitely not inspired by a real military base. Discover the hellish secrets buried beneath military inefficiency and bureaucracy!".to_string(),
-            image_src: "/static/doom_projects/ssas/ssas_1.png".to_string(),
-            image_alt: Some("ΣΣΑΣ".to_string()),
-            additional_images: vec![
-                "/static/doom_projects/ssas/ssas_2.png".to_string(),
-                "/static/doom_projects/ssas/ssas_3.png".to_string(),
-                "/static/doom_projects/ssas/ssas_4.png".to_string(),
-                "/static/doom_projects/ssas/ssas_5.png".to_string(),
-                "/static/doom_projects/ssas/ssas_6.png".to_string(),
-                "/static/doom_projects/ssas/ssas_7.png".to_string(),
-                "/static/doom_projects/ssas/ssas_8.png".to_string(),
-                "/static/doom_projects/ssas/ssas_9.png".to_string(),
-                "/static/doom_projects/ssas/ssas_10.png".to_string(),
-                "/static/doom_projects/ssas/ssas_11.png".to_string(),
-                "/static/doom_projects/ssas/ssas_12.png".to_string(),
-                "/static/doom_projects/ssas/ssas_13.png".to_string(),
-                "/static/doom_projects/ssas/ssas_14.png".to_string(),
-                "/static/doom_projects/ssas/ssas_15.png".to_string(),
-                "/static/doom_projects/ssas/ssas_16.png".to_string(),
-            ],
-        },
-    ];
+    // served by the server crate when it's running, built in otherwise
+    let projects = use_content(DOOM_PROJECTS_API, content::doom_projects::doom_projects);
 
     html! {
         <main class="min-h-screen text-white pt-8 pb-10">
@@ -120,21 +94,28 @@ pub fn doom_projects() -> Html {
                 
                 // page heading
                 <Heading 
-                    src="/static/doom_projects/DOOM_PROJECTS_1.png" 
-                    alt="Doom Projects"
+                    text="Doom Projects"
                     sub_heading="All maps made for limit-removing source ports and tested in GZDoom."
                 />
 
                 // projects grid
                 <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-8 justify-items-center">
-                    { for projects.iter().map(|project| html! {
-                        <DoomProjectItem
-                            title={project.title.clone()}
-                            description={project.description.clone()}
-                            image_src={project.image_src.clone()}
-                            image_alt={project.image_alt.clone()}
-                            additional_images={project.additional_images.clone()}
-                        />
+                    { for projects.iter().map(|project| {
+                        let id = slug(&project.title);
+                        let linked = query.map.as_deref() == Some(id.as_str());
+                        html! {
+                            <DoomProjectItem
+                                title={project.title.clone()}
+                                description={project.description.clone()}
+                                image_src={project.image_src.clone()}
+                                image_alt={project.image_alt.clone()}
+                                gallery={project.gallery.clone()}
+                                open_image={if linked { query.image } else { None }}
+                                stats={map_stats.get(&id).cloned()}
+                                playable={*playable}
+                                id={id}
+                            />
+                        }
                     })}
                 </div>
 
@@ -2,6 +2,8 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 use crate::router::Route;
 use crate::components::model_viewer::ModelViewer;
+use crate::pages::doom_projects::DoomProjectsQuery;
+use crate::renderer::metadata::{Hotspot, HotspotLink};
 use crate::app::NavbarContext;
 
 #[hook]
@@ -13,9 +15,32 @@ fn use_navigation() -> Callback<Route> {
     })
 }
 
+// follow a hotspot link from the cathedral model
+#[hook]
+fn use_hotspot_navigation() -> Callback<Hotspot> {
+    let navigator = use_navigator().unwrap();
+
+    Callback::from(move |hotspot: Hotspot| {
+        let query = match hotspot.link {
+            HotspotLink::Map { map } => DoomProjectsQuery { map: Some(map), image: None },
+            HotspotLink::Screenshot { map, image } => DoomProjectsQuery { map: Some(map), image: Some(image) },
+            HotspotLink::Post { url } => {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().set_href(&url);
+                }
+                return;
+            }
+        };
+        if let Err(err) = navigator.push_with_query(&Route::DoomProjects, &query) {
+            tracing::error!("Failed to follow hotspot {}: {}", hotspot.id, err);
+        }
+    })
+}
+
 #[function_component(Home)]
 pub fn home() -> Html {
     let navigate = use_navigation();
+    let on_hotspot = use_hotspot_navigation();
 
     let navbar_context = use_context::<NavbarContext>().expect("NavbarContext not found");
 
@@ -25,17 +50,26 @@ pub fn home() -> Html {
             if navbar_context.is_default_navbar { "pt-40" } else { "pb-15" }
         )}>
 
-            // model canvas is button to doom projects
-            <button
-                title = "Cathedral of Charybdis"
-                onclick={Callback::from(move |_| navigate.emit(Route::DoomProjects))}
-                class = "cursor-pointer">
+            // model viewer (drag to orbit, scroll/pinch to zoom, click hotspots)
+            <div class="relative">
                 <ModelViewer 
                     obj_path="/static/cathedral/cathedral.obj"
+                    alt="Cathedral of Charybdis"
                     width={650}
                     height={650}
+                    on_hotspot={on_hotspot}
                 />
-            </button>
+
+                // separate button to doom projects so dragging the model doesn't navigate away
+                <button
+                    onclick={Callback::from(move |_| navigate.emit(Route::DoomProjects))}
+                    class="group absolute bottom-4 left-1/2 -translate-x-1/2 bg-[#2b2b2b]/80 hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-2 px-4 rounded transition-all duration-200 cursor-pointer font-mono text-sm">
+                    <div class="flex items-center justify-center gap-2">
+                        <span>{"ENTER DOOM PROJECTS"}</span>
+                        <span class="text-xs group-hover:translate-x-1 transition-transform duration-200">{"→"}</span>
+                    </div>
+                </button>
+            </div>
 
             // model made text anchored to top/bottom left depending on navbar type
             <img 
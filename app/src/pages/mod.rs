@@ -2,4 +2,7 @@ pub mod home;
 pub mod about;
 pub mod projects;
 pub mod doom_projects;
-pub mod contact;
\ No newline at end of file
+pub mod contact;
+pub mod play;
+pub mod admin;
+pub mod cv;
\ No newline at end of file
@@ -0,0 +1,249 @@
+// pages/play.rs
+use gloo_events::EventListener;
+use gloo_net::http::{Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::{window, HtmlIFrameElement, MessageEvent};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::loading_bar::LoadingBar;
+use crate::pages::doom_projects::{fetch_map_stats, fetch_wad_urls, DoomProjectsQuery};
+use crate::router::Route;
+
+// engine host page, see the comment at its top for the files it expects next to it
+const PLAYER_PATH: &str = "/static/play/player.html";
+// the emscripten source port it runs, which isn't in the repo or built by deploy.yml yet
+const ENGINE_PATH: &str = "/static/play/engine/doom.wasm";
+
+// e.g. /play/jammy?slot=MAP02 for multi-map wads, the first map otherwise
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<String>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PlayProps {
+    pub map: String,    // doom project slug
+}
+
+#[derive(Clone, PartialEq)]
+enum Session {
+    Resolving,
+    Booting { src: String, slot: String },
+    Running { src: String, slot: String },
+    Failed(String),
+}
+
+// -warp arguments and freedoom iwad for a map slot: E2M4 -> "2 4" on freedoom1, MAP07 -> "7" on freedoom2
+fn warp(slot: &str) -> Option<(String, &'static str)> {
+    let slot = slot.to_ascii_uppercase();
+    if let Some(number) = slot.strip_prefix("MAP") {
+        return Some((number.parse::<u32>().ok()?.to_string(), "freedoom2.wad"));
+    }
+    let (episode, map) = slot.strip_prefix('E')?.split_once('M')?;
+    Some((format!("{} {}", episode.parse::<u32>().ok()?, map.parse::<u32>().ok()?), "freedoom1.wad"))
+}
+
+// nothing offers Play until a source port is installed. a dev server answering every path with index.html
+// doesn't count as one
+pub async fn engine_installed() -> bool {
+    match RequestBuilder::new(ENGINE_PATH).method(Method::HEAD).send().await {
+        Ok(response) => response.ok() && !response.headers().get("content-type").is_some_and(|kind| kind.starts_with("text/html")),
+        Err(_) => false,
+    }
+}
+
+// player url and the map slot it warps to
+async fn resolve(map: &str, slot: Option<String>) -> Result<(String, String), String> {
+    if !engine_installed().await {
+        return Err("In-browser play isn't available yet".to_string());
+    }
+    // stats only exist for wads the build found, so a slug without them has nothing to load (see deploy.yml)
+    let maps = fetch_map_stats().await?.remove(map).map(|stats| stats.maps).unwrap_or_default();
+    if maps.is_empty() {
+        return Err(format!("'{}' isn't available to play yet", map));
+    }
+    let pwad = fetch_wad_urls().await?.remove(map).ok_or_else(|| format!("No released wad for '{}'", map))?;
+
+    let slot = match slot {
+        Some(slot) if maps.iter().any(|m| m.name.eq_ignore_ascii_case(&slot)) => slot.to_ascii_uppercase(),
+        Some(slot) => return Err(format!("'{}' has no map {}", map, slot)),
+        None => maps[0].name.clone(),
+    };
+    let (warp, iwad) = warp(&slot).ok_or_else(|| format!("Can't warp to {}", slot))?;
+
+    let query = [("iwad", format!("/static/play/{}", iwad)), ("pwad", pwad), ("warp", warp)]
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, js_sys::encode_uri_component(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    Ok((format!("{}?{}", PLAYER_PATH, query), slot))
+}
+
+#[function_component(Play)]
+pub fn play(props: &PlayProps) -> Html {
+    let slot = use_location()
+        .and_then(|location| location.query::<PlayQuery>().ok())
+        .unwrap_or_default()
+        .slot;
+    let session = use_state(|| Session::Resolving);
+    let fullscreen = use_state(|| false);
+    let frame_ref = use_node_ref();
+
+    {
+        let session = session.clone();
+        use_effect_with((props.map.clone(), slot), move |(map, slot)| {
+            let (map, slot) = (map.clone(), slot.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                match resolve(&map, slot).await {
+                    Ok((src, slot)) => session.set(Session::Booting { src, slot }),
+                    Err(err) => {
+                        tracing::error!("Can't play {}: {}", map, err);
+                        session.set(Session::Failed(err));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    // status reported by the host page (re-registered per session state so the handler sees the current one)
+    {
+        let session = session.clone();
+        use_effect_with((*session).clone(), move |_| {
+            let window = window().unwrap();
+            let origin = window.location().origin().unwrap_or_default();
+            let listener = EventListener::new(&window, "message", move |event| {
+                let Some(event) = event.dyn_ref::<MessageEvent>() else { return };
+                if event.origin() != origin {
+                    return;
+                }
+                let field = |name: &str| {
+                    js_sys::Reflect::get(&event.data(), &name.into()).ok().and_then(|value| value.as_string())
+                };
+                match (field("type").as_deref(), (*session).clone()) {
+                    (Some("doom-ready"), Session::Booting { src, slot }) => session.set(Session::Running { src, slot }),
+                    (Some("doom-error"), _) => {
+                        session.set(Session::Failed(field("message").unwrap_or_else(|| "Engine failed".to_string())))
+                    }
+                    _ => {}
+                }
+            });
+            move || drop(listener)
+        });
+    }
+
+    // track fullscreen so the button label follows Escape exits too
+    {
+        let fullscreen = fullscreen.clone();
+        use_effect_with((), move |_| {
+            let document = window().and_then(|window| window.document()).unwrap();
+            let listener = EventListener::new(&document.clone(), "fullscreenchange", move |_| {
+                fullscreen.set(document.fullscreen_element().is_some());
+            });
+            move || drop(listener)
+        });
+    }
+
+    // teardown: blank the frame before yew removes it so audio and the main loop stop immediately
+    {
+        let frame_ref = frame_ref.clone();
+        use_effect_with((), move |_| {
+            move || {
+                if let Some(document) = window().and_then(|window| window.document()) {
+                    if document.fullscreen_element().is_some() {
+                        document.exit_fullscreen();
+                    }
+                }
+                if let Some(frame) = frame_ref.cast::<HtmlIFrameElement>() {
+                    frame.set_src("about:blank");
+                }
+            }
+        });
+    }
+
+    let toggle_fullscreen = {
+        let frame_ref = frame_ref.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(document) = window().and_then(|window| window.document()) else { return };
+            if document.fullscreen_element().is_some() {
+                document.exit_fullscreen();
+            } else if let Some(frame) = frame_ref.cast::<HtmlIFrameElement>() {
+                if let Err(err) = frame.request_fullscreen() {
+                    tracing::warn!("Fullscreen refused: {:?}", err);
+                }
+            }
+        })
+    };
+
+    // hand keyboard focus to the game once it's up
+    let on_frame_load = {
+        let frame_ref = frame_ref.clone();
+        Callback::from(move |_: Event| {
+            if let Some(frame) = frame_ref.cast::<HtmlIFrameElement>().and_then(|frame| frame.content_window()) {
+                let _ = frame.focus();
+            }
+        })
+    };
+
+    let title = props.map.replace('-', " ").to_uppercase();
+    let back_query = DoomProjectsQuery { map: Some(props.map.clone()), image: None };
+    let button_class = "bg-[#2b2b2b] hover:bg-red-600 border-2 border-red-600 text-red-600 hover:text-white font-bold py-2 px-4 rounded transition-all duration-200 cursor-pointer font-mono text-sm";
+
+    html! {
+        <main class="min-h-screen text-white pt-8 pb-10">
+            <div class="container mx-auto pt-4 px-4 max-w-6xl">
+                // toolbar
+                <div class="flex flex-wrap items-center gap-3 mb-4 font-mono">
+                    <Link<Route, DoomProjectsQuery> to={Route::DoomProjects} query={Some(back_query)} classes={button_class}>
+                        {"← BACK"}
+                    </Link<Route, DoomProjectsQuery>>
+                    <h1 class="text-xl font-bold text-red-600 mr-auto">
+                        {title}
+                        if let Session::Booting { slot, .. } | Session::Running { slot, .. } = &*session {
+                            <span class="text-gray-400 text-base">{format!(" · {}", slot)}</span>
+                        }
+                    </h1>
+                    if matches!(*session, Session::Running { .. }) {
+                        <button onclick={toggle_fullscreen} class={button_class}>
+                            { if *fullscreen { "EXIT FULLSCREEN" } else { "FULLSCREEN" } }
+                        </button>
+                    }
+                </div>
+
+                // game
+                <div class="relative aspect-[16/10] bg-black border-4 border-[#0b0b0a]">
+                    if let Session::Booting { src, .. } | Session::Running { src, .. } = &*session {
+                        <iframe
+                            ref={frame_ref}
+                            src={src.clone()}
+                            title={format!("{} (playable)", props.map)}
+                            allow="fullscreen; autoplay"
+                            onload={on_frame_load}
+                            class="absolute inset-0 w-full h-full"
+                        />
+                    }
+                    {match &*session {
+                        Session::Resolving | Session::Booting { .. } => html! {
+                            <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                                <LoadingBar label="LOADING" />
+                            </div>
+                        },
+                        Session::Failed(err) => html! {
+                            <div class="absolute inset-0 flex items-center justify-center text-red-600 font-mono text-center p-4">
+                                {err}
+                            </div>
+                        },
+                        Session::Running { .. } => html! {},
+                    }}
+                </div>
+
+                <p class="mt-3 text-gray-500 font-mono text-xs text-center">
+                    {"Click the game to capture the mouse, Esc releases it. Runs on Freedoom, so textures and monsters may look different from the original."}
+                </p>
+            </div>
+        </main>
+    }
+}
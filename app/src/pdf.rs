@@ -0,0 +1,295 @@
+// pdf.rs
+// just enough pdf 1.4 for text documents like the cv: helvetica, lines and links, no embedded fonts
+// or compression, so the output is plain enough to diff and the same data always gives the same bytes
+use std::fmt::Write;
+
+// points, 72 per inch
+pub const A4: (f64, f64) = (595.0, 842.0);
+
+// helvetica's advance widths in 1/1000 em for ascii 32..=126, from adobe's afm files
+const HELVETICA: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // space to /
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0 to ?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @ to O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P to _
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // ` to o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p to ~
+];
+const HELVETICA_BOLD: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+// two of the standard 14 fonts, every reader has them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    const ALL: [Font; 2] = [Font::Regular, Font::Bold];
+
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+        }
+    }
+
+    fn advance(self, byte: u8) -> u16 {
+        let widths = match self {
+            Font::Regular => &HELVETICA,
+            Font::Bold => &HELVETICA_BOLD,
+        };
+        match byte {
+            32..=126 => widths[byte as usize - 32],
+            0x95 => 350,            // bullet
+            0x96 => 556,            // en dash
+            0x97 => 1000,           // em dash
+            0x91..=0x94 => 333,     // curly quotes
+            _ => 556,               // accented letters are about as wide as the average
+        }
+    }
+
+    // width of a line of text in points
+    pub fn width(self, text: &str, size: f64) -> f64 {
+        encode(text).into_iter().map(|byte| self.advance(byte) as f64).sum::<f64>() * size / 1000.0
+    }
+}
+
+// winansi, the standard fonts' encoding: latin-1 plus typographic punctuation, anything else becomes "?"
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .filter_map(|c| match c {
+            '\u{2022}' => Some(0x95),
+            '\u{2013}' => Some(0x96),
+            '\u{2014}' => Some(0x97),
+            '\u{2018}' => Some(0x91),
+            '\u{2019}' => Some(0x92),
+            '\u{201c}' => Some(0x93),
+            '\u{201d}' => Some(0x94),
+            '\u{20ac}' => Some(0x80),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => Some(c as u8),
+            // variation selectors and joiners belong to the emoji before them, which is already a "?"
+            '\u{fe00}'..='\u{fe0f}' | '\u{200d}' => None,
+            _ => Some(b'?'),
+        })
+        .collect()
+}
+
+// a (string) literal, with the characters that would end or escape it escaped
+fn literal(text: &[u8]) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for &byte in text {
+        if matches!(byte, b'(' | b')' | b'\\') {
+            out.push(b'\\');
+        }
+        out.push(byte);
+    }
+    out.push(b')');
+    out
+}
+
+// greedy word wrap to lines no wider than width; a single word wider than that gets a line to itself
+pub fn wrap(text: &str, font: Font, size: f64, width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if !line.is_empty() && font.width(&candidate, size) > width {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        } else {
+            line = candidate;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// 0..1 rgb
+pub type Color = (f64, f64, f64);
+
+#[derive(Default)]
+struct Page {
+    content: Vec<u8>,
+    links: Vec<([f64; 4], String)>,
+}
+
+// pages are drawn on in order, coordinates are from the bottom left corner as pdf has them
+pub struct Document {
+    title: String,
+    size: (f64, f64),
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new(title: &str, size: (f64, f64)) -> Document {
+        Document { title: title.to_string(), size, pages: vec![Page::default()] }
+    }
+
+    pub fn size(&self) -> (f64, f64) {
+        self.size
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn add_page(&mut self) {
+        self.pages.push(Page::default());
+    }
+
+    fn content(&mut self) -> &mut Vec<u8> {
+        &mut self.pages.last_mut().expect("a document always has a page").content
+    }
+
+    // x and y are the start of the baseline
+    pub fn text(&mut self, x: f64, y: f64, font: Font, size: f64, color: Color, text: &str) {
+        let operators = format!("BT /{} {} Tf {:.3} {:.3} {:.3} rg {:.2} {:.2} Td ", font.resource(), size, color.0, color.1, color.2, x, y);
+        let content = self.content();
+        content.extend_from_slice(operators.as_bytes());
+        content.extend_from_slice(&literal(&encode(text)));
+        content.extend_from_slice(b" Tj ET\n");
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), width: f64, color: Color) {
+        let operators = format!(
+            "{:.3} {:.3} {:.3} RG {} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+            color.0, color.1, color.2, width, from.0, from.1, to.0, to.1
+        );
+        self.content().extend_from_slice(operators.as_bytes());
+    }
+
+    // makes the area from (x, y) up and right a clickable link
+    pub fn link(&mut self, x: f64, y: f64, width: f64, height: f64, url: &str) {
+        let page = self.pages.last_mut().expect("a document always has a page");
+        page.links.push(([x, y, x + width, y + height], url.to_string()));
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // catalog, page tree, info and the fonts come first, then each page and its content stream
+        let fonts = Font::ALL.len();
+        let first_page = 4 + fonts;
+        let kids: Vec<String> = (0..self.pages.len()).map(|index| format!("{} 0 R", first_page + index * 2)).collect();
+
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), self.pages.len()).into_bytes(),
+            [b"<< /Title ".as_slice(), &literal(&encode(&self.title)), b" /Producer (app/src/pdf.rs) >>"].concat(),
+        ];
+        for font in Font::ALL {
+            objects.push(format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", font.base_font()).into_bytes());
+        }
+        let font_resources: String = Font::ALL.iter().enumerate().map(|(index, font)| format!("/{} {} 0 R ", font.resource(), 4 + index)).collect();
+
+        for (index, page) in self.pages.iter().enumerate() {
+            let mut annotations = String::new();
+            for ([left, bottom, right, top], url) in &page.links {
+                let url = String::from_utf8_lossy(&literal(url.as_bytes())).into_owned();
+                let _ = write!(
+                    annotations,
+                    "<< /Type /Annot /Subtype /Link /Rect [{:.2} {:.2} {:.2} {:.2}] /Border [0 0 0] /A << /S /URI /URI {} >> >> ",
+                    left, bottom, right, top, url
+                );
+            }
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {}>> >> /Contents {} 0 R /Annots [{}] >>",
+                    self.size.0,
+                    self.size.1,
+                    font_resources,
+                    first_page + index * 2 + 1,
+                    annotations
+                )
+                .into_bytes(),
+            );
+            objects.push([format!("<< /Length {} >>\nstream\n", page.content.len()).as_bytes(), &page.content, b"\nendstream"].concat());
+        }
+
+        // the cross-reference table points at where each object starts
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = out.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(table, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(table, "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref);
+        out.extend_from_slice(table.as_bytes());
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_and_wraps_text() {
+        // "Hi" is 722 + 222 thousandths
+        assert!((Font::Regular.width("Hi", 10.0) - 9.44).abs() < 1e-9);
+        assert!(Font::Bold.width("Hi", 10.0) > Font::Regular.width("Hi", 10.0));
+
+        let lines = wrap("the quick brown fox jumps over the lazy dog", Font::Regular, 10.0, 80.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| Font::Regular.width(line, 10.0) <= 80.0));
+        assert_eq!(lines.join(" "), "the quick brown fox jumps over the lazy dog");
+        assert_eq!(wrap("supercalifragilistic", Font::Regular, 10.0, 5.0), ["supercalifragilistic"]);
+        assert!(wrap("  ", Font::Regular, 10.0, 80.0).is_empty());
+    }
+
+    #[test]
+    fn encodes_and_escapes_strings() {
+        assert_eq!(encode("Café – ok"), b"Caf\xe9 \x96 ok");
+        assert_eq!(encode("⚖️ law"), b"? law");
+        assert_eq!(literal(b"a (b) \\c"), b"(a \\(b\\) \\\\c)");
+    }
+
+    #[test]
+    fn writes_a_consistent_file() {
+        let mut document = Document::new("Test", A4);
+        document.text(50.0, 800.0, Font::Bold, 12.0, (0.0, 0.0, 0.0), "Page one");
+        document.link(50.0, 790.0, 40.0, 12.0, "https://example.com");
+        document.add_page();
+        document.line((50.0, 700.0), (545.0, 700.0), 1.0, (0.8, 0.1, 0.1));
+        let bytes = document.to_bytes();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(bytes.starts_with(b"%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("(Page one) Tj"));
+        assert!(text.contains("/URI (https://example.com)"));
+
+        // startxref names the offset of the table, and every entry the offset of its object
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert!(bytes[startxref..].starts_with(b"xref\n"));
+        let table = String::from_utf8_lossy(&bytes[startxref..]);
+        let entries = table.lines().skip(3).take_while(|line| line.ends_with(" n "));
+        for (index, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(bytes[offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+        }
+        assert_eq!(document.to_bytes(), bytes);
+    }
+}
@@ -0,0 +1,131 @@
+// renderer/camera.rs
+// orbit camera circling a target point, with inertia after drags
+use std::f32::consts::FRAC_PI_2;
+
+use crate::renderer::math::{self, Mat4, Vec3};
+
+const ROTATE_SPEED: f32 = 0.005;    // radians per dragged pixel
+const ZOOM_SPEED: f32 = 0.001;      // per wheel delta unit
+const DAMPING: f32 = 0.92;          // fraction of velocity kept each frame
+const MIN_VELOCITY: f32 = 0.00001;
+const PITCH_LIMIT: f32 = FRAC_PI_2 - 0.05;  // stop just short of the poles to keep "up" stable
+
+// camera placement in orbit coordinates around the target
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraPose {
+    pub yaw: f32,       // radians around the y axis, 0 looks down -z
+    pub pitch: f32,     // radians above the horizon
+    pub distance: f32,
+}
+
+impl Default for CameraPose {
+    fn default() -> Self {
+        // matches the original fixed camera at (0, 0, 10)
+        CameraPose { yaw: 0.0, pitch: 0.0, distance: 10.0 }
+    }
+}
+
+pub struct OrbitCamera {
+    pub pose: CameraPose,
+    pub target: Vec3,
+    initial: CameraPose,
+    min_distance: f32,
+    max_distance: f32,
+    velocity: (f32, f32),   // (yaw, pitch) per frame, carried on after a drag
+    dragging: bool,
+}
+
+impl OrbitCamera {
+    pub fn new(initial: CameraPose, min_distance: f32, max_distance: f32) -> Self {
+        let mut camera = OrbitCamera {
+            pose: initial,
+            target: [0.0; 3],
+            initial,
+            min_distance,
+            max_distance: max_distance.max(min_distance),
+            velocity: (0.0, 0.0),
+            dragging: false,
+        };
+        camera.pose.distance = camera.clamp_distance(initial.distance);
+        camera.initial.distance = camera.pose.distance;
+        camera
+    }
+
+    pub fn begin_drag(&mut self) {
+        self.dragging = true;
+        self.velocity = (0.0, 0.0);
+    }
+
+    pub fn end_drag(&mut self) {
+        self.dragging = false;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    // is the camera still coasting after a drag
+    pub fn is_moving(&self) -> bool {
+        self.velocity.0.abs() > MIN_VELOCITY || self.velocity.1.abs() > MIN_VELOCITY
+    }
+
+    // drag by screen pixels
+    pub fn rotate(&mut self, dx: f32, dy: f32) {
+        let delta = (-dx * ROTATE_SPEED, dy * ROTATE_SPEED);
+        self.apply_rotation(delta);
+        self.velocity = delta;
+    }
+
+    // wheel delta (positive zooms out)
+    pub fn zoom_by_wheel(&mut self, delta: f32) {
+        self.zoom((delta * ZOOM_SPEED).exp());
+    }
+
+    // multiply distance, e.g. by the inverse of a pinch ratio
+    pub fn zoom(&mut self, factor: f32) {
+        if factor.is_finite() && factor > 0.0 {
+            self.pose.distance = self.clamp_distance(self.pose.distance * factor);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pose = self.initial;
+        self.velocity = (0.0, 0.0);
+        self.dragging = false;
+    }
+
+    // advance inertia by one frame
+    pub fn update(&mut self) {
+        if self.dragging {
+            return;
+        }
+        if self.is_moving() {
+            self.apply_rotation(self.velocity);
+            self.velocity = (self.velocity.0 * DAMPING, self.velocity.1 * DAMPING);
+        } else {
+            self.velocity = (0.0, 0.0);
+        }
+    }
+
+    pub fn eye(&self) -> Vec3 {
+        let CameraPose { yaw, pitch, distance } = self.pose;
+        math::add(self.target, [
+            distance * yaw.sin() * pitch.cos(),
+            distance * pitch.sin(),
+            distance * yaw.cos() * pitch.cos(),
+        ])
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        math::look_at(self.eye(), self.target, [0.0, 1.0, 0.0])
+    }
+
+    fn apply_rotation(&mut self, (yaw, pitch): (f32, f32)) {
+        self.pose.yaw += yaw;
+        self.pose.pitch = (self.pose.pitch + pitch).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    fn clamp_distance(&self, distance: f32) -> f32 {
+        distance.clamp(self.min_distance, self.max_distance)
+    }
+}
@@ -0,0 +1,111 @@
+// renderer/gpu.rs
+// webgl2 resource helpers: shaders, mesh buffers and textures
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    HtmlImageElement, WebGl2RenderingContext as Gl, WebGlBuffer, WebGlProgram, WebGlShader, WebGlTexture,
+    WebGlVertexArrayObject,
+};
+
+use crate::renderer::math::Vec3;
+use crate::renderer::obj::Mesh;
+
+pub struct GpuMesh {
+    pub vao: WebGlVertexArrayObject,
+    pub buffers: Vec<WebGlBuffer>,
+    pub vertex_count: i32,
+    pub texture: Option<WebGlTexture>,
+    pub color: Vec3,
+}
+
+impl GpuMesh {
+    pub fn upload(gl: &Gl, mesh: &Mesh, color: Vec3) -> Result<GpuMesh, JsValue> {
+        let vao = gl.create_vertex_array().ok_or("failed to create vertex array")?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let mut buffers = Vec::with_capacity(3);
+        for (location, data, size) in [(0, &mesh.positions, 3), (1, &mesh.normals, 3), (2, &mesh.uvs, 2)] {
+            let buffer = gl.create_buffer().ok_or("failed to create buffer")?;
+            gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&buffer));
+            gl.buffer_data_with_u8_array(Gl::ARRAY_BUFFER, as_bytes(data), Gl::STATIC_DRAW);
+            gl.enable_vertex_attrib_array(location);
+            gl.vertex_attrib_pointer_with_i32(location, size, Gl::FLOAT, false, 0, 0);
+            buffers.push(buffer);
+        }
+
+        gl.bind_vertex_array(None);
+        gl.bind_buffer(Gl::ARRAY_BUFFER, None);
+
+        Ok(GpuMesh {
+            vao,
+            buffers,
+            vertex_count: mesh.vertex_count() as i32,
+            texture: None,
+            color,
+        })
+    }
+
+    pub fn delete(&self, gl: &Gl) {
+        gl.delete_vertex_array(Some(&self.vao));
+        for buffer in &self.buffers {
+            gl.delete_buffer(Some(buffer));
+        }
+        gl.delete_texture(self.texture.as_ref());
+    }
+}
+
+// view a float slice as raw bytes for buffer uploads
+fn as_bytes(data: &[f32]) -> &[u8] {
+    // SAFETY: f32 has no padding and any bit pattern is a valid u8
+    unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data)) }
+}
+
+pub fn upload_texture(gl: &Gl, image: &HtmlImageElement) -> Result<WebGlTexture, JsValue> {
+    let texture = gl.create_texture().ok_or("failed to create texture")?;
+    gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+    gl.tex_image_2d_with_u32_and_u32_and_html_image_element(
+        Gl::TEXTURE_2D, 0, Gl::RGBA as i32, Gl::RGBA, Gl::UNSIGNED_BYTE, image,
+    )?;
+
+    // crisp pixel art settings
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::NEAREST as i32);
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::NEAREST as i32);
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::REPEAT as i32);
+    gl.tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::REPEAT as i32);
+    gl.bind_texture(Gl::TEXTURE_2D, None);
+    Ok(texture)
+}
+
+fn compile_shader(gl: &Gl, kind: u32, source: &str) -> Result<WebGlShader, JsValue> {
+    let shader = gl.create_shader(kind).ok_or("failed to create shader")?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+
+    if gl.get_shader_parameter(&shader, Gl::COMPILE_STATUS).as_bool().unwrap_or(false) {
+        Ok(shader)
+    } else {
+        let log = gl.get_shader_info_log(&shader).unwrap_or_default();
+        gl.delete_shader(Some(&shader));
+        Err(JsValue::from_str(&format!("shader compile error: {}", log)))
+    }
+}
+
+pub fn link_program(gl: &Gl, vertex_source: &str, fragment_source: &str) -> Result<WebGlProgram, JsValue> {
+    let vertex = compile_shader(gl, Gl::VERTEX_SHADER, vertex_source)?;
+    let fragment = compile_shader(gl, Gl::FRAGMENT_SHADER, fragment_source)?;
+    let program = gl.create_program().ok_or("failed to create program")?;
+    gl.attach_shader(&program, &vertex);
+    gl.attach_shader(&program, &fragment);
+    gl.link_program(&program);
+
+    // shaders are no longer needed once linked
+    gl.delete_shader(Some(&vertex));
+    gl.delete_shader(Some(&fragment));
+
+    if gl.get_program_parameter(&program, Gl::LINK_STATUS).as_bool().unwrap_or(false) {
+        Ok(program)
+    } else {
+        let log = gl.get_program_info_log(&program).unwrap_or_default();
+        gl.delete_program(Some(&program));
+        Err(JsValue::from_str(&format!("program link error: {}", log)))
+    }
+}
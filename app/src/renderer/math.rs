@@ -0,0 +1,118 @@
+// renderer/math.rs
+// minimal column-major matrix helpers (layout matches what webgl expects for uniformMatrix4fv)
+
+pub type Vec3 = [f32; 3];
+pub type Mat4 = [f32; 16];
+
+pub fn identity() -> Mat4 {
+    [
+        1.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ]
+}
+
+pub fn multiply(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut out = [0.0; 16];
+    for col in 0..4 {
+        for row in 0..4 {
+            out[col * 4 + row] = (0..4).map(|k| a[k * 4 + row] * b[col * 4 + k]).sum();
+        }
+    }
+    out
+}
+
+pub fn translation(v: Vec3) -> Mat4 {
+    let mut m = identity();
+    m[12] = v[0];
+    m[13] = v[1];
+    m[14] = v[2];
+    m
+}
+
+pub fn scaling(s: f32) -> Mat4 {
+    let mut m = identity();
+    m[0] = s;
+    m[5] = s;
+    m[10] = s;
+    m
+}
+
+pub fn rotation_y(angle: f32) -> Mat4 {
+    let (s, c) = angle.sin_cos();
+    let mut m = identity();
+    m[0] = c;
+    m[2] = -s;
+    m[8] = s;
+    m[10] = c;
+    m
+}
+
+// matrix * (x, y, z, w)
+pub fn transform(m: &Mat4, v: [f32; 4]) -> [f32; 4] {
+    std::array::from_fn(|row| m[row] * v[0] + m[4 + row] * v[1] + m[8 + row] * v[2] + m[12 + row] * v[3])
+}
+
+// rotate a point around the y axis, same convention as rotation_y
+pub fn rotate_y(v: Vec3, angle: f32) -> Vec3 {
+    let (s, c) = angle.sin_cos();
+    [c * v[0] + s * v[2], v[1], -s * v[0] + c * v[2]]
+}
+
+// same convention as three.js PerspectiveCamera (vertical fov in degrees)
+pub fn perspective(fov_y_deg: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+    let f = 1.0 / (fov_y_deg.to_radians() / 2.0).tan();
+    let range_inv = 1.0 / (near - far);
+    [
+        f / aspect, 0.0, 0.0, 0.0,
+        0.0, f, 0.0, 0.0,
+        0.0, 0.0, (near + far) * range_inv, -1.0,
+        0.0, 0.0, 2.0 * near * far * range_inv, 0.0,
+    ]
+}
+
+pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+    let z = normalize(sub(eye, target));
+    let x = normalize(cross(up, z));
+    let y = cross(z, x);
+    [
+        x[0], y[0], z[0], 0.0,
+        x[1], y[1], z[1], 0.0,
+        x[2], y[2], z[2], 0.0,
+        -dot(x, eye), -dot(y, eye), -dot(z, eye), 1.0,
+    ]
+}
+
+pub fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn scale(v: Vec3, s: f32) -> Vec3 {
+    [v[0] * s, v[1] * s, v[2] * s]
+}
+
+pub fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub fn normalize(v: Vec3) -> Vec3 {
+    let len = dot(v, v).sqrt();
+    if len > 0.0 {
+        [v[0] / len, v[1] / len, v[2] / len]
+    } else {
+        v
+    }
+}
@@ -0,0 +1,91 @@
+// renderer/metadata.rs
+// per-model metadata loaded from a json file next to the obj (cathedral.obj -> cathedral.json)
+use serde::{Deserialize, Serialize};
+
+use crate::renderer::math::Vec3;
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct ModelMetadata {
+    #[serde(default)]
+    pub hotspots: Vec<Hotspot>,
+}
+
+// clickable area of the model
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Hotspot {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    // box corners as fractions of the model's bounding box (0,0,0 = min corner, 1,1,1 = max corner),
+    // so regions survive re-exporting the model at a different scale
+    pub min: Vec3,
+    pub max: Vec3,
+    pub link: HotspotLink,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HotspotLink {
+    Map { map: String },                        // doom project by slug
+    Screenshot { map: String, image: usize },   // gallery image of a doom project (0 = cover image)
+    Post { url: String },                       // blog post or any other page
+}
+
+impl Hotspot {
+    fn contains(&self, point: Vec3) -> bool {
+        (0..3).all(|axis| point[axis] >= self.min[axis] && point[axis] <= self.max[axis])
+    }
+
+    fn volume(&self) -> f32 {
+        (0..3).map(|axis| (self.max[axis] - self.min[axis]).abs()).product()
+    }
+}
+
+impl ModelMetadata {
+    pub fn parse(json: &str) -> Result<ModelMetadata, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid model metadata: {}", e))
+    }
+
+    // hotspot containing a point given in normalized model coordinates, smallest region wins when they overlap
+    pub fn hotspot_at(&self, point: Vec3) -> Option<&Hotspot> {
+        self.hotspots
+            .iter()
+            .filter(|hotspot| hotspot.contains(point))
+            .min_by(|a, b| a.volume().total_cmp(&b.volume()))
+    }
+}
+
+// pre-rendered turntable strip written by the build step, frames laid out left to right,
+// frame 0 faces the default camera and each following frame turns the model by 360 / frames degrees
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TurntableManifest {
+    pub src: String,
+    pub frames: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+}
+
+impl TurntableManifest {
+    pub fn parse(json: &str) -> Result<TurntableManifest, String> {
+        let manifest: TurntableManifest =
+            serde_json::from_str(json).map_err(|e| format!("Invalid turntable manifest: {}", e))?;
+        if manifest.frames == 0 || manifest.frame_width == 0 || manifest.frame_height == 0 {
+            return Err("Turntable manifest has no frames".to_string());
+        }
+        Ok(manifest)
+    }
+}
+
+// metadata path for a model, e.g. /static/cathedral/cathedral.obj -> /static/cathedral/cathedral.json
+pub fn metadata_path(obj_path: &str) -> String {
+    match obj_path.strip_suffix(".obj") {
+        Some(stem) => format!("{}.json", stem),
+        None => format!("{}.json", obj_path),
+    }
+}
+
+// turntable manifest path for a model, e.g. /static/cathedral/cathedral.obj -> /static/cathedral/cathedral.turntable.json
+pub fn turntable_path(obj_path: &str) -> String {
+    format!("{}.turntable.json", obj_path.strip_suffix(".obj").unwrap_or(obj_path))
+}
@@ -0,0 +1,12 @@
+// renderer/mod.rs
+pub mod camera;
+pub mod gpu;
+pub mod math;
+pub mod metadata;
+pub mod mtl;
+pub mod obj;
+pub mod pick;
+pub mod raster;
+pub mod scene;
+pub mod staging;
+pub mod support;
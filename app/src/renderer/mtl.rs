@@ -0,0 +1,71 @@
+// renderer/mtl.rs
+// wavefront mtl parser, only the parts that matter for flat doom textures (diffuse colour, diffuse map, opacity)
+use std::collections::HashMap;
+
+use crate::renderer::math::Vec3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    pub name: String,
+    pub diffuse: Vec3,
+    pub texture: Option<String>,    // map_Kd, relative to the mtl file
+    pub opacity: f32,
+}
+
+impl Material {
+    fn new(name: &str) -> Self {
+        Material { name: name.to_string(), diffuse: [1.0; 3], texture: None, opacity: 1.0 }
+    }
+}
+
+pub fn parse(source: &str) -> HashMap<String, Material> {
+    let mut materials = HashMap::new();
+    let mut current: Option<Material> = None;
+
+    for line in source.lines() {
+        let line = line.trim();
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match keyword {
+            "newmtl" => {
+                if let Some(material) = current.take() {
+                    materials.insert(material.name.clone(), material);
+                }
+                current = Some(Material::new(rest));
+            }
+            "Kd" => {
+                if let (Some(material), Some(color)) = (current.as_mut(), parse_vec3(rest)) {
+                    material.diffuse = color;
+                }
+            }
+            "map_Kd" => {
+                // options like "-s 1 1 1" can precede the file name, which is always last
+                if let (Some(material), Some(path)) = (current.as_mut(), rest.split_whitespace().last()) {
+                    material.texture = Some(path.to_string());
+                }
+            }
+            "d" => {
+                if let (Some(material), Ok(opacity)) = (current.as_mut(), rest.parse::<f32>()) {
+                    material.opacity = opacity;
+                }
+            }
+            "Tr" => {
+                if let (Some(material), Ok(transparency)) = (current.as_mut(), rest.parse::<f32>()) {
+                    material.opacity = 1.0 - transparency;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(material) = current {
+        materials.insert(material.name.clone(), material);
+    }
+    materials
+}
+
+fn parse_vec3(values: &str) -> Option<Vec3> {
+    let mut values = values.split_whitespace().map(|v| v.parse::<f32>().ok());
+    Some([values.next()??, values.next()??, values.next()??])
+}
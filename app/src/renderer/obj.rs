@@ -0,0 +1,228 @@
+// renderer/obj.rs
+// wavefront obj parser producing flat (non-indexed) triangle meshes, one per object/group like three.js OBJLoader
+use crate::renderer::math::{self, Vec3};
+
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub name: String,
+    pub material: Option<String>,
+    pub positions: Vec<f32>,    // xyz per vertex
+    pub normals: Vec<f32>,      // xyz per vertex
+    pub uvs: Vec<f32>,          // uv per vertex
+}
+
+impl Mesh {
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len() / 3
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Model {
+    pub meshes: Vec<Mesh>,
+    pub material_libraries: Vec<String>,    // mtllib file names, relative to the obj
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Bounds {
+    pub fn center(&self) -> Vec3 {
+        [
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+            (self.min[2] + self.max[2]) / 2.0,
+        ]
+    }
+
+    pub fn size(&self) -> Vec3 {
+        math::sub(self.max, self.min)
+    }
+}
+
+impl Model {
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut points = self.meshes.iter().flat_map(|mesh| mesh.positions.chunks_exact(3));
+        let first = points.next()?;
+        let mut bounds = Bounds { min: [first[0], first[1], first[2]], max: [first[0], first[1], first[2]] };
+        for p in points {
+            for (axis, value) in p.iter().enumerate() {
+                bounds.min[axis] = bounds.min[axis].min(*value);
+                bounds.max[axis] = bounds.max[axis].max(*value);
+            }
+        }
+        Some(bounds)
+    }
+}
+
+pub fn parse(source: &str) -> Model {
+    let mut positions: Vec<Vec3> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+
+    let mut meshes: Vec<Mesh> = Vec::new();
+    let mut current = Mesh::default();
+    let mut material_libraries = Vec::new();
+
+    for line in source.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(keyword) = parts.next() else { continue };
+
+        match keyword {
+            "v" => positions.push(parse_floats(parts)),
+            "vn" => normals.push(parse_floats(parts)),
+            "vt" => {
+                let uv: Vec3 = parse_floats(parts);
+                uvs.push([uv[0], uv[1]]);
+            }
+            "o" | "g" => {
+                // start a new mesh, keeping the material active (obj materials carry over between groups)
+                let name = parts.collect::<Vec<_>>().join(" ");
+                let material = current.material.clone();
+                finish_mesh(&mut meshes, std::mem::take(&mut current));
+                current.name = name;
+                current.material = material;
+            }
+            "mtllib" => material_libraries.extend(parts.map(str::to_string)),
+            "usemtl" => {
+                let material = parts.next().map(str::to_string);
+                if current.vertex_count() > 0 && current.material != material {
+                    let name = current.name.clone();
+                    finish_mesh(&mut meshes, std::mem::take(&mut current));
+                    current.name = name;
+                }
+                current.material = material;
+            }
+            "f" => {
+                let corners: Vec<FaceVertex> = parts.filter_map(|corner| {
+                    FaceVertex::parse(corner, positions.len(), uvs.len(), normals.len())
+                }).collect();
+
+                // triangulate polygon as a fan
+                for i in 1..corners.len().saturating_sub(1) {
+                    push_triangle(&mut current, [corners[0], corners[i], corners[i + 1]], &positions, &uvs, &normals);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    finish_mesh(&mut meshes, current);
+    Model { meshes, material_libraries }
+}
+
+fn finish_mesh(meshes: &mut Vec<Mesh>, mesh: Mesh) {
+    if mesh.vertex_count() > 0 {
+        meshes.push(mesh);
+    }
+}
+
+fn parse_floats<'a>(parts: impl Iterator<Item = &'a str>) -> Vec3 {
+    let mut out = [0.0; 3];
+    for (slot, value) in out.iter_mut().zip(parts) {
+        *slot = value.parse().unwrap_or(0.0);
+    }
+    out
+}
+
+#[derive(Clone, Copy)]
+struct FaceVertex {
+    position: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
+
+impl FaceVertex {
+    // parse "v", "v/vt", "v//vn" or "v/vt/vn" (1-based, negative indices are relative to the end)
+    fn parse(corner: &str, position_count: usize, uv_count: usize, normal_count: usize) -> Option<Self> {
+        let mut indices = corner.split('/');
+        let position = resolve_index(indices.next()?, position_count)?;
+        let uv = indices.next().and_then(|i| resolve_index(i, uv_count));
+        let normal = indices.next().and_then(|i| resolve_index(i, normal_count));
+        Some(FaceVertex { position, uv, normal })
+    }
+}
+
+fn resolve_index(raw: &str, count: usize) -> Option<usize> {
+    let index: i64 = raw.parse().ok()?;
+    let resolved = if index < 0 { count as i64 + index } else { index - 1 };
+    (0..count as i64).contains(&resolved).then_some(resolved as usize)
+}
+
+fn push_triangle(mesh: &mut Mesh, corners: [FaceVertex; 3], positions: &[Vec3], uvs: &[[f32; 2]], normals: &[Vec3]) {
+    let points = corners.map(|c| positions[c.position]);
+
+    // flat normal for corners that don't reference one
+    let face_normal = math::normalize(math::cross(
+        math::sub(points[1], points[0]),
+        math::sub(points[2], points[0]),
+    ));
+
+    for (corner, point) in corners.iter().zip(points) {
+        mesh.positions.extend_from_slice(&point);
+        let normal = corner.normal.map(|i| normals[i]).unwrap_or(face_normal);
+        mesh.normals.extend_from_slice(&normal);
+        let uv = corner.uv.map(|i| uvs[i]).unwrap_or([0.0, 0.0]);
+        mesh.uvs.extend_from_slice(&uv);
+    }
+}
+
+// mesh names exported from ultimate doom builder look like "MAP01_MAP01_<TEXTURE>_<TEXTURE>"
+pub fn texture_name(mesh_name: &str) -> Option<String> {
+    let parts: Vec<&str> = mesh_name.split('_').collect();
+    if parts.len() < 3 || parts[0] != "MAP01" || parts[1] != "MAP01" {
+        return None;
+    }
+
+    let remaining = &parts[2..];
+    let name = if remaining.len() == 2 && remaining[0] == remaining[1] {
+        remaining[0].to_string()
+    } else {
+        remaining.join("_")
+    };
+
+    (name != "undefined").then_some(name)
+}
+
+// rough colour guess for meshes whose texture failed to load
+pub fn fallback_color(texture_name: Option<&str>) -> Vec3 {
+    let Some(name) = texture_name else {
+        return hex_color(0xcccccc);
+    };
+    let name = name.to_lowercase();
+
+    let color = if name.contains("fire") && name.contains("blu") {
+        0x4444ff
+    } else if name.contains("fire") && name.contains("lav") {
+        0xff4400
+    } else if name.contains("fire") {
+        0xff8800
+    } else if name.contains("wood") {
+        0x996633
+    } else if name.contains("metal") {
+        0xaaaacc
+    } else if name.contains("stone") || name.contains("rock") {
+        0x888866
+    } else if name.contains("door") {
+        0x663311
+    } else if name.contains("wall") {
+        0xccbbaa
+    } else if name.contains("grey") || name.contains("gray") {
+        0x777777
+    } else {
+        0xcccccc
+    };
+
+    hex_color(color)
+}
+
+pub fn hex_color(hex: u32) -> Vec3 {
+    [
+        ((hex >> 16) & 0xff) as f32 / 255.0,
+        ((hex >> 8) & 0xff) as f32 / 255.0,
+        (hex & 0xff) as f32 / 255.0,
+    ]
+}
@@ -0,0 +1,80 @@
+// renderer/pick.rs
+// ray casting against mesh triangles, used to find what is under the cursor
+use crate::renderer::math::{self, Vec3};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,   // normalized
+}
+
+impl Ray {
+    pub fn at(&self, t: f32) -> Vec3 {
+        math::add(self.origin, math::scale(self.direction, t))
+    }
+}
+
+// ray from the eye through a point on screen, ndc coordinates in -1..1 with +y up
+pub fn screen_ray(eye: Vec3, target: Vec3, fov_y_deg: f32, aspect: f32, ndc: (f32, f32)) -> Ray {
+    let forward = math::normalize(math::sub(target, eye));
+    let right = math::normalize(math::cross(forward, [0.0, 1.0, 0.0]));
+    let up = math::cross(right, forward);
+
+    let half_height = (fov_y_deg.to_radians() / 2.0).tan();
+    let half_width = half_height * aspect;
+    let direction = math::add(
+        forward,
+        math::add(math::scale(right, ndc.0 * half_width), math::scale(up, ndc.1 * half_height)),
+    );
+
+    Ray { origin: eye, direction: math::normalize(direction) }
+}
+
+// möller-trumbore intersection, returns the distance along the ray (both faces count as hits)
+pub fn intersect_triangle(ray: &Ray, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+    const EPSILON: f32 = 1e-7;
+
+    let edge1 = math::sub(b, a);
+    let edge2 = math::sub(c, a);
+    let p = math::cross(ray.direction, edge2);
+    let determinant = math::dot(edge1, p);
+    if determinant.abs() < EPSILON {
+        return None;    // ray parallel to the triangle
+    }
+
+    let inverse = 1.0 / determinant;
+    let s = math::sub(ray.origin, a);
+    let u = math::dot(s, p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let q = math::cross(s, edge1);
+    let v = math::dot(ray.direction, q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = math::dot(edge2, q) * inverse;
+    (t > EPSILON).then_some(t)
+}
+
+// nearest hit against flat triangle lists (xyz per vertex, three vertices per triangle)
+pub fn closest_hit<'a>(ray: &Ray, meshes: impl IntoIterator<Item = &'a [f32]>) -> Option<Vec3> {
+    let mut closest: Option<f32> = None;
+
+    for positions in meshes {
+        for triangle in positions.chunks_exact(9) {
+            let a = [triangle[0], triangle[1], triangle[2]];
+            let b = [triangle[3], triangle[4], triangle[5]];
+            let c = [triangle[6], triangle[7], triangle[8]];
+            if let Some(t) = intersect_triangle(ray, a, b, c) {
+                if closest.is_none_or(|closest| t < closest) {
+                    closest = Some(t);
+                }
+            }
+        }
+    }
+
+    closest.map(|t| ray.at(t))
+}
@@ -0,0 +1,277 @@
+// renderer/raster.rs
+// cpu software rasterizer mirroring the webgl viewer: perspective correct uvs, nearest-neighbour textures,
+// ambient + two directional lights, alpha test and double sided faces. used by the build tools, not the web app
+use crate::renderer::camera::{CameraPose, OrbitCamera};
+use crate::renderer::math::{self, Vec3};
+use crate::renderer::obj::Mesh;
+use crate::renderer::staging::{
+    self, ALPHA_CUTOFF, AMBIENT_LIGHT, FIELD_OF_VIEW, FAR_PLANE, LIGHTS, NEAR_PLANE, SHININESS, SPECULAR_STRENGTH,
+};
+
+// rgba8 pixels, rows top to bottom
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+pub type Texture = Image;
+
+impl Image {
+    // nearest neighbour lookup with repeat wrapping, v = 0 is the bottom row like in webgl
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        let x = ((u.rem_euclid(1.0) * self.width as f32) as u32).min(self.width - 1);
+        let y = (((1.0 - v.rem_euclid(1.0)) * self.height as f32) as u32).min(self.height - 1);
+        let i = ((y * self.width + x) * 4) as usize;
+        std::array::from_fn(|c| self.pixels[i + c] as f32 / 255.0)
+    }
+}
+
+// one mesh and how to paint it
+pub struct Surface<'a> {
+    pub mesh: &'a Mesh,
+    pub texture: Option<&'a Texture>,
+    pub color: Vec3,
+}
+
+pub struct Shot {
+    pub width: u32,
+    pub height: u32,
+    pub camera: CameraPose,
+    pub rotation: f32,          // model turn around y, radians (same as the viewer's auto rotation)
+    pub background: [f32; 4],   // straight rgba, alpha 0 for a transparent image
+    pub supersample: u32,       // render at n x n resolution and average down
+}
+
+// vertex after projection, with everything the fragment stage interpolates
+#[derive(Clone, Copy)]
+struct ClipVertex {
+    clip: [f32; 4],
+    world: Vec3,
+    normal: Vec3,
+    uv: [f32; 2],
+}
+
+impl ClipVertex {
+    fn lerp(a: &ClipVertex, b: &ClipVertex, t: f32) -> ClipVertex {
+        let mix = |x: f32, y: f32| x + (y - x) * t;
+        ClipVertex {
+            clip: std::array::from_fn(|i| mix(a.clip[i], b.clip[i])),
+            world: std::array::from_fn(|i| mix(a.world[i], b.world[i])),
+            normal: std::array::from_fn(|i| mix(a.normal[i], b.normal[i])),
+            uv: std::array::from_fn(|i| mix(a.uv[i], b.uv[i])),
+        }
+    }
+}
+
+struct Target {
+    width: usize,
+    height: usize,
+    color: Vec<[f32; 4]>,   // straight alpha, like the webgl canvas
+    depth: Vec<f32>,
+}
+
+pub fn render(surfaces: &[Surface], model_transform: (f32, Vec3), shot: &Shot) -> Image {
+    let factor = shot.supersample.max(1);
+    let (width, height) = ((shot.width * factor) as usize, (shot.height * factor) as usize);
+    let mut target = Target {
+        width,
+        height,
+        color: vec![shot.background; width * height],
+        depth: vec![f32::INFINITY; width * height],
+    };
+
+    let camera = OrbitCamera::new(shot.camera, 0.0, f32::MAX);
+    let eye = camera.eye();
+    let view_projection = math::multiply(
+        &math::perspective(FIELD_OF_VIEW, shot.width as f32 / shot.height.max(1) as f32, NEAR_PLANE, FAR_PLANE),
+        &camera.view_matrix(),
+    );
+    let model = staging::model_matrix(model_transform, shot.rotation);
+    let (scale, _) = model_transform;
+
+    for surface in surfaces {
+        let mesh = surface.mesh;
+        let vertex = |i: usize| {
+            let local = [mesh.positions[i * 3], mesh.positions[i * 3 + 1], mesh.positions[i * 3 + 2]];
+            let world = math::transform(&model, [local[0], local[1], local[2], 1.0]);
+            let world = [world[0], world[1], world[2]];
+            let normal = [mesh.normals[i * 3], mesh.normals[i * 3 + 1], mesh.normals[i * 3 + 2]];
+            ClipVertex {
+                clip: math::transform(&view_projection, [world[0], world[1], world[2], 1.0]),
+                world,
+                // rotation and uniform scale only, so the normal just needs the same turn
+                normal: math::scale(math::rotate_y(normal, shot.rotation), scale.signum()),
+                uv: [mesh.uvs[i * 2], mesh.uvs[i * 2 + 1]],
+            }
+        };
+
+        for first in (0..mesh.vertex_count()).step_by(3) {
+            if first + 2 >= mesh.vertex_count() {
+                break;
+            }
+            let triangle = [vertex(first), vertex(first + 1), vertex(first + 2)];
+            let polygon = clip_near(&triangle);
+            for i in 1..polygon.len().saturating_sub(1) {
+                draw_triangle(&mut target, surface, eye, [polygon[0], polygon[i], polygon[i + 1]]);
+            }
+        }
+    }
+
+    resolve(&target, factor as usize)
+}
+
+// cut away the part of a triangle behind the near plane (z < -w in clip space)
+fn clip_near(triangle: &[ClipVertex; 3]) -> Vec<ClipVertex> {
+    let distance = |v: &ClipVertex| v.clip[2] + v.clip[3];
+    let mut out = Vec::with_capacity(4);
+
+    for i in 0..3 {
+        let (current, next) = (&triangle[i], &triangle[(i + 1) % 3]);
+        let (d_current, d_next) = (distance(current), distance(next));
+        if d_current >= 0.0 {
+            out.push(*current);
+        }
+        if (d_current >= 0.0) != (d_next >= 0.0) {
+            out.push(ClipVertex::lerp(current, next, d_current / (d_current - d_next)));
+        }
+    }
+    out
+}
+
+fn draw_triangle(target: &mut Target, surface: &Surface, eye: Vec3, triangle: [ClipVertex; 3]) {
+    let (width, height) = (target.width as f32, target.height as f32);
+
+    // perspective divide + viewport, screen y grows downwards
+    let screen: [[f32; 4]; 3] = std::array::from_fn(|i| {
+        let [x, y, z, w] = triangle[i].clip;
+        let inverse_w = 1.0 / w;
+        [
+            (x * inverse_w * 0.5 + 0.5) * width,
+            (1.0 - (y * inverse_w * 0.5 + 0.5)) * height,
+            z * inverse_w,
+            inverse_w,
+        ]
+    });
+
+    let edge = |a: [f32; 4], b: [f32; 4], x: f32, y: f32| (b[0] - a[0]) * (y - a[1]) - (b[1] - a[1]) * (x - a[0]);
+    let area = edge(screen[0], screen[1], screen[2][0], screen[2][1]);
+    if area.abs() < f32::EPSILON {
+        return;
+    }
+    // counter-clockwise in gl terms is clockwise once y is flipped
+    let front_facing = area < 0.0;
+
+    let min_x = screen.iter().map(|s| s[0]).fold(f32::INFINITY, f32::min).floor().max(0.0) as usize;
+    let max_x = screen.iter().map(|s| s[0]).fold(f32::NEG_INFINITY, f32::max).ceil().min(width) as usize;
+    let min_y = screen.iter().map(|s| s[1]).fold(f32::INFINITY, f32::min).floor().max(0.0) as usize;
+    let max_y = screen.iter().map(|s| s[1]).fold(f32::NEG_INFINITY, f32::max).ceil().min(height) as usize;
+
+    for py in min_y..max_y {
+        for px in min_x..max_x {
+            let (x, y) = (px as f32 + 0.5, py as f32 + 0.5);
+            let weights = [
+                edge(screen[1], screen[2], x, y) / area,
+                edge(screen[2], screen[0], x, y) / area,
+                edge(screen[0], screen[1], x, y) / area,
+            ];
+            if weights.iter().any(|&w| w < 0.0) {
+                continue;
+            }
+
+            let depth: f32 = (0..3).map(|i| weights[i] * screen[i][2]).sum();
+            let index = py * target.width + px;
+            if !(-1.0..=1.0).contains(&depth) || depth >= target.depth[index] {
+                continue;
+            }
+
+            // perspective correct interpolation
+            let perspective: [f32; 3] = std::array::from_fn(|i| weights[i] * screen[i][3]);
+            let total: f32 = perspective.iter().sum();
+            let interpolate = |get: &dyn Fn(&ClipVertex) -> f32| {
+                (0..3).map(|i| perspective[i] * get(&triangle[i])).sum::<f32>() / total
+            };
+            let uv = [interpolate(&|v| v.uv[0]), interpolate(&|v| v.uv[1])];
+            let world: Vec3 = std::array::from_fn(|c| interpolate(&|v| v.world[c]));
+            let normal: Vec3 = std::array::from_fn(|c| interpolate(&|v| v.normal[c]));
+
+            let Some(fragment) = shade(surface, uv, world, normal, front_facing, eye) else { continue };
+            target.depth[index] = depth;
+            target.color[index] = blend(target.color[index], fragment);
+        }
+    }
+}
+
+// same maths as the viewer's fragment shader
+fn shade(surface: &Surface, uv: [f32; 2], world: Vec3, normal: Vec3, front_facing: bool, eye: Vec3) -> Option<[f32; 4]> {
+    let mut base = [surface.color[0], surface.color[1], surface.color[2], 1.0];
+    if let Some(texture) = surface.texture {
+        let texel = texture.sample(uv[0], uv[1]);
+        base = std::array::from_fn(|c| base[c] * texel[c]);
+    }
+    if base[3] < ALPHA_CUTOFF {
+        return None;
+    }
+
+    let normal = math::scale(math::normalize(normal), if front_facing { 1.0 } else { -1.0 });
+    let view_dir = math::normalize(math::sub(eye, world));
+
+    let mut diffuse = AMBIENT_LIGHT;
+    let mut specular = 0.0;
+    for (position, intensity) in LIGHTS {
+        let light = staging::light_direction(position);
+        let n_dot_l = math::dot(normal, light).max(0.0);
+        diffuse += intensity * n_dot_l;
+        let half = math::normalize(math::add(light, view_dir));
+        specular += intensity * math::dot(normal, half).max(0.0).powf(SHININESS) * n_dot_l;
+    }
+
+    Some([
+        base[0] * diffuse + specular * SPECULAR_STRENGTH,
+        base[1] * diffuse + specular * SPECULAR_STRENGTH,
+        base[2] * diffuse + specular * SPECULAR_STRENGTH,
+        base[3],
+    ])
+}
+
+// blendFuncSeparate(SRC_ALPHA, ONE_MINUS_SRC_ALPHA, ONE, ONE_MINUS_SRC_ALPHA)
+fn blend(destination: [f32; 4], source: [f32; 4]) -> [f32; 4] {
+    let a = source[3];
+    [
+        source[0] * a + destination[0] * (1.0 - a),
+        source[1] * a + destination[1] * (1.0 - a),
+        source[2] * a + destination[2] * (1.0 - a),
+        a + destination[3] * (1.0 - a),
+    ]
+}
+
+// average supersampled pixels (weighted by alpha so transparent edges don't darken) and convert to rgba8
+fn resolve(target: &Target, factor: usize) -> Image {
+    let (width, height) = (target.width / factor, target.height / factor);
+    let mut pixels = Vec::with_capacity(width * height * 4);
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0.0f32; 4];
+            for sy in 0..factor {
+                for sx in 0..factor {
+                    let [r, g, b, a] = target.color[(y * factor + sy) * target.width + x * factor + sx];
+                    sum = [sum[0] + r * a, sum[1] + g * a, sum[2] + b * a, sum[3] + a];
+                }
+            }
+            let alpha = sum[3] / (factor * factor) as f32;
+            let unpremultiply = if sum[3] > 0.0 { 1.0 / sum[3] } else { 0.0 };
+            for channel in &sum[..3] {
+                pixels.push(to_byte(channel * unpremultiply));
+            }
+            pixels.push(to_byte(alpha));
+        }
+    }
+
+    Image { width: width as u32, height: height as u32, pixels }
+}
+
+fn to_byte(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
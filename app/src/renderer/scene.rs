@@ -0,0 +1,589 @@
+// renderer/scene.rs
+// webgl2 renderer for the model viewer: transparent background, ambient + two directional lights, slow y rotation
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use gloo_net::http::Request;
+use js_sys::{Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use yew::Callback;
+use web_sys::{
+    AbortController, AbortSignal, HtmlCanvasElement, HtmlImageElement, ReadableStreamDefaultReader,
+    WebGl2RenderingContext as Gl, WebGlContextAttributes, WebGlProgram, WebGlUniformLocation,
+};
+
+use crate::renderer::camera::{CameraPose, OrbitCamera};
+use crate::renderer::gpu::{self, GpuMesh};
+use crate::renderer::math::{self, Mat4, Vec3};
+use crate::renderer::metadata::{self, Hotspot, ModelMetadata};
+use crate::renderer::obj;
+use crate::renderer::pick::{self, Ray};
+use crate::renderer::staging::{self, ALPHA_CUTOFF, AMBIENT_LIGHT, FIELD_OF_VIEW, LIGHTS, SHININESS, SPECULAR_STRENGTH};
+
+const VERTEX_SHADER: &str = r#"#version 300 es
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+layout(location = 2) in vec2 a_uv;
+
+uniform mat4 u_model;
+uniform mat4 u_view;
+uniform mat4 u_projection;
+
+out vec3 v_normal;
+out vec3 v_view_position;
+out vec2 v_uv;
+
+void main() {
+    vec4 view_position = u_view * u_model * vec4(a_position, 1.0);
+    // model matrix only holds rotation + uniform scale, so it can transform normals directly
+    v_normal = mat3(u_view) * mat3(u_model) * a_normal;
+    v_view_position = view_position.xyz;
+    v_uv = a_uv;
+    gl_Position = u_projection * view_position;
+}
+"#;
+
+// the shading constants are spliced in from staging.rs so the software rasterizer can't drift from it
+fn fragment_shader() -> String {
+    format!(
+        "#version 300 es\nprecision highp float;\n\nconst float ALPHA_CUTOFF = {:?};\nconst float SHININESS = {:?};\nconst float SPECULAR_STRENGTH = {:?};\n{}",
+        ALPHA_CUTOFF, SHININESS, SPECULAR_STRENGTH, FRAGMENT_SHADER
+    )
+}
+
+const FRAGMENT_SHADER: &str = r#"
+in vec3 v_normal;
+in vec3 v_view_position;
+in vec2 v_uv;
+
+uniform sampler2D u_texture;
+uniform bool u_has_texture;
+uniform vec3 u_color;
+uniform vec3 u_ambient;
+uniform vec3 u_light_directions[2];    // view space, pointing towards the light
+uniform vec3 u_light_colors[2];
+
+out vec4 out_color;
+
+void main() {
+    vec4 base = vec4(u_color, 1.0);
+    if (u_has_texture) {
+        base *= texture(u_texture, v_uv);
+    }
+    if (base.a < ALPHA_CUTOFF) {
+        discard;    // alpha test for sprite-like textures
+    }
+
+    // double sided: flip normal for back faces
+    vec3 normal = normalize(v_normal) * (gl_FrontFacing ? 1.0 : -1.0);
+    vec3 view_dir = normalize(-v_view_position);
+
+    vec3 diffuse = u_ambient;
+    vec3 specular = vec3(0.0);
+    for (int i = 0; i < 2; i++) {
+        float n_dot_l = max(dot(normal, u_light_directions[i]), 0.0);
+        diffuse += u_light_colors[i] * n_dot_l;
+        vec3 half_dir = normalize(u_light_directions[i] + view_dir);
+        specular += u_light_colors[i] * pow(max(dot(normal, half_dir), 0.0), SHININESS) * n_dot_l;
+    }
+
+    // faint phong highlight (matches three.js MeshPhongMaterial default specular of 0x111111)
+    out_color = vec4(base.rgb * diffuse + specular * SPECULAR_STRENGTH, base.a);
+}
+"#;
+
+const ROTATION_SPEED: f32 = 0.0005;  // radians per frame
+
+// loading progress reported back to the component
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadState {
+    Downloading { loaded: u64, total: Option<u64> },   // bytes of the obj file
+    Textures { loaded: usize, total: usize },
+    Ready,
+    Failed(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewerOptions {
+    pub auto_rotate: bool,
+    pub initial_camera: CameraPose,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+struct Uniforms {
+    model: Option<WebGlUniformLocation>,
+    view: Option<WebGlUniformLocation>,
+    projection: Option<WebGlUniformLocation>,
+    texture: Option<WebGlUniformLocation>,
+    has_texture: Option<WebGlUniformLocation>,
+    color: Option<WebGlUniformLocation>,
+    ambient: Option<WebGlUniformLocation>,
+    light_directions: Option<WebGlUniformLocation>,
+    light_colors: Option<WebGlUniformLocation>,
+}
+
+// in-flight texture download, kept so it can be cancelled on teardown
+struct TextureRequest {
+    image: HtmlImageElement,
+    _on_load: Closure<dyn FnMut()>,
+    _on_error: Closure<dyn FnMut()>,
+}
+
+struct Scene {
+    gl: Gl,
+    program: WebGlProgram,
+    uniforms: Uniforms,
+    meshes: Vec<GpuMesh>,
+    model_transform: (f32, Vec3),  // (scale, position) applied to the model
+    bounds: Option<obj::Bounds>,
+    pick_geometry: Vec<Vec<f32>>,  // cpu copy of mesh positions for ray picking
+    metadata: ModelMetadata,
+    rotation: f32,
+    auto_rotate: bool,
+    camera: OrbitCamera,
+    texture_requests: Vec<TextureRequest>,
+    textures_total: usize,
+    textures_done: usize,
+    on_state: Callback<LoadState>,
+}
+
+impl Scene {
+    fn texture_finished(&mut self) {
+        self.textures_done += 1;
+        if self.textures_done < self.textures_total {
+            self.on_state.emit(LoadState::Textures { loaded: self.textures_done, total: self.textures_total });
+        } else {
+            // model and all of its textures are ready
+            self.on_state.emit(LoadState::Ready);
+        }
+    }
+
+    fn render(&mut self) {
+        let gl = &self.gl;
+        let width = gl.drawing_buffer_width();
+        let height = gl.drawing_buffer_height();
+
+        gl.viewport(0, 0, width, height);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);    // transparent
+        gl.clear(Gl::COLOR_BUFFER_BIT | Gl::DEPTH_BUFFER_BIT);
+
+        if self.meshes.is_empty() {
+            return;
+        }
+
+        // hold the model still while the user is dragging it around
+        if self.auto_rotate && !self.camera.is_dragging() {
+            self.rotation += ROTATION_SPEED;
+        }
+        self.camera.update();
+
+        let aspect = width as f32 / height.max(1) as f32;
+        let projection = math::perspective(FIELD_OF_VIEW, aspect, staging::NEAR_PLANE, staging::FAR_PLANE);
+        let view = self.camera.view_matrix();
+
+        let model = staging::model_matrix(self.model_transform, self.rotation);
+
+        gl.use_program(Some(&self.program));
+        gl.uniform_matrix4fv_with_f32_array(self.uniforms.model.as_ref(), false, &model);
+        gl.uniform_matrix4fv_with_f32_array(self.uniforms.view.as_ref(), false, &view);
+        gl.uniform_matrix4fv_with_f32_array(self.uniforms.projection.as_ref(), false, &projection);
+        gl.uniform3f(self.uniforms.ambient.as_ref(), AMBIENT_LIGHT, AMBIENT_LIGHT, AMBIENT_LIGHT);
+
+        let mut directions = Vec::with_capacity(6);
+        let mut colors = Vec::with_capacity(6);
+        for (position, intensity) in LIGHTS {
+            directions.extend_from_slice(&view_direction(&view, position));
+            colors.extend_from_slice(&[intensity; 3]);
+        }
+        gl.uniform3fv_with_f32_array(self.uniforms.light_directions.as_ref(), &directions);
+        gl.uniform3fv_with_f32_array(self.uniforms.light_colors.as_ref(), &colors);
+        gl.uniform1i(self.uniforms.texture.as_ref(), 0);
+
+        for mesh in &self.meshes {
+            gl.bind_vertex_array(Some(&mesh.vao));
+            gl.uniform3f(self.uniforms.color.as_ref(), mesh.color[0], mesh.color[1], mesh.color[2]);
+            gl.uniform1i(self.uniforms.has_texture.as_ref(), mesh.texture.is_some() as i32);
+            gl.active_texture(Gl::TEXTURE0);
+            gl.bind_texture(Gl::TEXTURE_2D, mesh.texture.as_ref());
+            gl.draw_arrays(Gl::TRIANGLES, 0, mesh.vertex_count);
+        }
+        gl.bind_vertex_array(None);
+    }
+
+    // hotspot under a screen position (ndc, +y up)
+    fn hotspot_at(&self, ndc: (f32, f32)) -> Option<Hotspot> {
+        let bounds = self.bounds?;
+        if self.metadata.hotspots.is_empty() {
+            return None;
+        }
+
+        let aspect = self.gl.drawing_buffer_width() as f32 / self.gl.drawing_buffer_height().max(1) as f32;
+        let ray = pick::screen_ray(self.camera.eye(), self.camera.target, FIELD_OF_VIEW, aspect, ndc);
+
+        // undo the model transform so the ray can be tested against the original obj coordinates
+        let (scale, position) = self.model_transform;
+        let ray = Ray {
+            origin: math::scale(math::rotate_y(math::sub(ray.origin, position), -self.rotation), 1.0 / scale),
+            direction: math::rotate_y(ray.direction, -self.rotation),
+        };
+        let hit = pick::closest_hit(&ray, self.pick_geometry.iter().map(Vec::as_slice))?;
+
+        let size = bounds.size();
+        let normalized: Vec3 = std::array::from_fn(|axis| {
+            if size[axis] > 0.0 { (hit[axis] - bounds.min[axis]) / size[axis] } else { 0.5 }
+        });
+        self.metadata.hotspot_at(normalized).cloned()
+    }
+
+    // free everything on the gpu and stop pending texture downloads
+    fn dispose(&mut self) {
+        for request in self.texture_requests.drain(..) {
+            request.image.set_onload(None);
+            request.image.set_onerror(None);
+            request.image.set_src("");
+        }
+        for mesh in self.meshes.drain(..) {
+            mesh.delete(&self.gl);
+        }
+        self.gl.delete_program(Some(&self.program));
+        // the context itself is kept, a retry reuses it on the same canvas
+    }
+}
+
+// direction from origin towards a light position, rotated into view space
+fn view_direction(view: &Mat4, position: Vec3) -> Vec3 {
+    let d = staging::light_direction(position);
+    math::normalize([
+        view[0] * d[0] + view[4] * d[1] + view[8] * d[2],
+        view[1] * d[0] + view[5] * d[1] + view[9] * d[2],
+        view[2] * d[0] + view[6] * d[1] + view[10] * d[2],
+    ])
+}
+
+// self-referencing requestAnimationFrame callback
+type FrameCallback = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
+// one running model viewer; dropping it stops the render loop, cancels loading and frees gpu resources
+pub struct Viewer {
+    scene: Rc<RefCell<Scene>>,
+    frame: FrameCallback,
+    frame_id: Rc<Cell<Option<i32>>>,
+    abort: Option<AbortController>,
+}
+
+impl Viewer {
+    // set up webgl on the canvas, start the render loop and load the model in the background
+    pub fn start(
+        canvas: &HtmlCanvasElement,
+        obj_path: &str,
+        options: ViewerOptions,
+        on_state: Callback<LoadState>,
+    ) -> Result<Viewer, JsValue> {
+        let attributes = WebGlContextAttributes::new();
+        attributes.set_alpha(true);          // keep transparent background
+        attributes.set_antialias(true);
+
+        let gl: Gl = canvas
+            .get_context_with_context_options("webgl2", &attributes)?
+            .ok_or_else(|| JsValue::from_str("WebGL2 not available"))?
+            .dyn_into()?;
+
+        gl.enable(Gl::DEPTH_TEST);
+        gl.enable(Gl::BLEND);
+        gl.blend_func_separate(Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+        gl.pixel_storei(Gl::UNPACK_FLIP_Y_WEBGL, 1);
+
+        let program = gpu::link_program(&gl, VERTEX_SHADER, &fragment_shader())?;
+        let uniforms = Uniforms {
+            model: gl.get_uniform_location(&program, "u_model"),
+            view: gl.get_uniform_location(&program, "u_view"),
+            projection: gl.get_uniform_location(&program, "u_projection"),
+            texture: gl.get_uniform_location(&program, "u_texture"),
+            has_texture: gl.get_uniform_location(&program, "u_has_texture"),
+            color: gl.get_uniform_location(&program, "u_color"),
+            ambient: gl.get_uniform_location(&program, "u_ambient"),
+            light_directions: gl.get_uniform_location(&program, "u_light_directions"),
+            light_colors: gl.get_uniform_location(&program, "u_light_colors"),
+        };
+
+        let scene = Rc::new(RefCell::new(Scene {
+            gl,
+            program,
+            uniforms,
+            meshes: Vec::new(),
+            model_transform: (1.0, [0.0; 3]),
+            bounds: None,
+            pick_geometry: Vec::new(),
+            metadata: ModelMetadata::default(),
+            rotation: 0.0,
+            auto_rotate: options.auto_rotate,
+            camera: OrbitCamera::new(options.initial_camera, options.min_distance, options.max_distance),
+            texture_requests: Vec::new(),
+            textures_total: 0,
+            textures_done: 0,
+            on_state,
+        }));
+
+        let viewer = Viewer {
+            scene: scene.clone(),
+            frame: Rc::new(RefCell::new(None)),
+            frame_id: Rc::new(Cell::new(None)),
+            abort: AbortController::new().ok(),
+        };
+        viewer.start_animation_loop();
+
+        let signal = viewer.abort.as_ref().map(|controller| controller.signal());
+        wasm_bindgen_futures::spawn_local(load_model(Rc::downgrade(&scene), obj_path.to_string(), signal));
+
+        Ok(viewer)
+    }
+
+    pub fn begin_drag(&self) {
+        self.scene.borrow_mut().camera.begin_drag();
+    }
+
+    pub fn drag(&self, dx: f32, dy: f32) {
+        self.scene.borrow_mut().camera.rotate(dx, dy);
+    }
+
+    pub fn end_drag(&self) {
+        self.scene.borrow_mut().camera.end_drag();
+    }
+
+    pub fn zoom_by_wheel(&self, delta: f32) {
+        self.scene.borrow_mut().camera.zoom_by_wheel(delta);
+    }
+
+    pub fn zoom(&self, factor: f32) {
+        self.scene.borrow_mut().camera.zoom(factor);
+    }
+
+    pub fn reset_view(&self) {
+        self.scene.borrow_mut().camera.reset();
+    }
+
+    pub fn set_auto_rotate(&self, auto_rotate: bool) {
+        self.scene.borrow_mut().auto_rotate = auto_rotate;
+    }
+
+    // hotspot under a canvas position in css pixels
+    pub fn hotspot_at(&self, x: f64, y: f64, width: f64, height: f64) -> Option<Hotspot> {
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let ndc = ((x / width * 2.0 - 1.0) as f32, (1.0 - y / height * 2.0) as f32);
+        self.scene.borrow().hotspot_at(ndc)
+    }
+
+    fn start_animation_loop(&self) {
+        let scene = self.scene.clone();
+        let next_frame = self.frame.clone();
+        let frame_id = self.frame_id.clone();
+
+        *self.frame.borrow_mut() = Some(Closure::new(move || {
+            scene.borrow_mut().render();
+            if let Some(callback) = next_frame.borrow().as_ref() {
+                frame_id.set(request_animation_frame(callback));
+            }
+        }));
+
+        if let Some(callback) = self.frame.borrow().as_ref() {
+            self.frame_id.set(request_animation_frame(callback));
+        };
+    }
+}
+
+impl Drop for Viewer {
+    fn drop(&mut self) {
+        if let (Some(window), Some(id)) = (web_sys::window(), self.frame_id.take()) {
+            let _ = window.cancel_animation_frame(id);
+        }
+        // dropping the closure breaks the frame -> closure -> frame cycle
+        self.frame.borrow_mut().take();
+        if let Some(controller) = &self.abort {
+            controller.abort();
+        }
+        self.scene.borrow_mut().dispose();
+    }
+}
+
+fn request_animation_frame(callback: &Closure<dyn FnMut()>) -> Option<i32> {
+    web_sys::window()?.request_animation_frame(callback.as_ref().unchecked_ref()).ok()
+}
+
+// download, parse and upload the model; gives up quietly if the viewer was dropped meanwhile
+async fn load_model(scene_ref: Weak<RefCell<Scene>>, obj_path: String, signal: Option<AbortSignal>) {
+    let on_progress = {
+        let scene = scene_ref.clone();
+        move |loaded: u64, total: Option<u64>| {
+            if let Some(scene) = scene.upgrade() {
+                scene.borrow().on_state.emit(LoadState::Downloading { loaded, total });
+            }
+        }
+    };
+
+    let result = fetch_with_progress(&obj_path, signal.as_ref(), on_progress).await;
+    let Some(scene) = scene_ref.upgrade() else { return };
+
+    let model = match result {
+        Ok(bytes) => obj::parse(&String::from_utf8_lossy(&bytes)),
+        Err(err) => {
+            tracing::error!("Failed to load OBJ: {}", err);
+            scene.borrow().on_state.emit(LoadState::Failed(err));
+            return;
+        }
+    };
+
+    tracing::info!("OBJ loaded successfully with {} meshes", model.meshes.len());
+    if model.meshes.is_empty() {
+        scene.borrow().on_state.emit(LoadState::Failed("Model contains no meshes".to_string()));
+        return;
+    }
+    add_model(&scene, model, &texture_directory(&obj_path));
+    drop(scene);
+
+    // hotspots are optional, a missing or broken metadata file only disables them
+    let path = metadata::metadata_path(&obj_path);
+    let result = fetch_with_progress(&path, signal.as_ref(), |_, _| {}).await;
+    let Some(scene) = scene_ref.upgrade() else { return };
+    match result.and_then(|bytes| ModelMetadata::parse(&String::from_utf8_lossy(&bytes))) {
+        Ok(metadata) => {
+            tracing::info!("Loaded {} hotspots", metadata.hotspots.len());
+            scene.borrow_mut().metadata = metadata;
+        }
+        Err(err) => tracing::warn!("No model metadata at {}: {}", path, err),
+    }
+}
+
+async fn fetch_with_progress(
+    path: &str,
+    signal: Option<&AbortSignal>,
+    on_progress: impl Fn(u64, Option<u64>),
+) -> Result<Vec<u8>, String> {
+    let response = Request::get(path)
+        .abort_signal(signal)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("HTTP {} error", response.status()));
+    }
+
+    // compressed responses report the compressed length, so only trust it as an upper bound
+    let total = response.headers().get("content-length").and_then(|length| length.parse::<u64>().ok());
+
+    let Some(body) = response.body() else {
+        return response.binary().await.map_err(|e| e.to_string());
+    };
+    let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    loop {
+        let chunk = JsFuture::from(reader.read()).await.map_err(|e| format!("Download interrupted: {:?}", e))?;
+        let done = Reflect::get(&chunk, &"done".into()).ok().and_then(|d| d.as_bool()).unwrap_or(true);
+        if done {
+            break;
+        }
+        let value = Reflect::get(&chunk, &"value".into()).map_err(|e| format!("{:?}", e))?;
+        bytes.extend_from_slice(&Uint8Array::new(&value).to_vec());
+        on_progress(bytes.len() as u64, total.filter(|&total| total >= bytes.len() as u64));
+    }
+
+    Ok(bytes)
+}
+
+// textures live next to the obj file, e.g. /static/cathedral/textures/
+fn texture_directory(obj_path: &str) -> String {
+    match obj_path.rfind('/') {
+        Some(index) => format!("{}/textures", &obj_path[..index]),
+        None => "textures".to_string(),
+    }
+}
+
+fn add_model(scene: &Rc<RefCell<Scene>>, model: obj::Model, texture_dir: &str) {
+    let mut state = scene.borrow_mut();
+
+    // scale and center
+    state.bounds = model.bounds();
+    if let Some(bounds) = &state.bounds {
+        state.model_transform = staging::fit_model(bounds);
+    }
+
+    let mut textures = Vec::new();
+    for mesh in &model.meshes {
+        let gpu_mesh = match GpuMesh::upload(&state.gl, mesh, [1.0; 3]) {
+            Ok(gpu_mesh) => gpu_mesh,
+            Err(err) => {
+                tracing::error!("Failed to upload mesh {}: {:?}", mesh.name, err);
+                continue;
+            }
+        };
+        let index = state.meshes.len();
+        state.meshes.push(gpu_mesh);
+
+        match obj::texture_name(&mesh.name) {
+            Some(name) => textures.push((index, name)),
+            None => {
+                // no texture name, apply neutral colour
+                tracing::debug!("No texture name found for mesh: {}", mesh.name);
+                state.meshes[index].color = obj::fallback_color(None);
+            }
+        }
+    }
+    state.pick_geometry = model.meshes.into_iter().map(|mesh| mesh.positions).collect();
+    tracing::info!("Model added successfully");
+
+    state.textures_total = textures.len();
+    if textures.is_empty() {
+        state.on_state.emit(LoadState::Ready);
+        return;
+    }
+    state.on_state.emit(LoadState::Textures { loaded: 0, total: textures.len() });
+
+    for (index, name) in textures {
+        let path = format!("{}/{}.png", texture_dir, name);
+        if let Some(request) = load_texture(Rc::downgrade(scene), index, path, name) {
+            state.texture_requests.push(request);
+        }
+    }
+}
+
+fn load_texture(scene: Weak<RefCell<Scene>>, mesh_index: usize, path: String, texture_name: String) -> Option<TextureRequest> {
+    let image = HtmlImageElement::new().ok()?;
+
+    let on_load = {
+        let scene = scene.clone();
+        let image = image.clone();
+        let texture_name = texture_name.clone();
+        Closure::once(move || {
+            let Some(scene) = scene.upgrade() else { return };
+            let mut state = scene.borrow_mut();
+            match gpu::upload_texture(&state.gl, &image) {
+                Ok(texture) => state.meshes[mesh_index].texture = Some(texture),
+                Err(_) => state.meshes[mesh_index].color = obj::fallback_color(Some(&texture_name)),
+            }
+            state.texture_finished();
+        })
+    };
+
+    let on_error = {
+        let path = path.clone();
+        Closure::once(move || {
+            tracing::warn!("Failed to load texture: {}", path);
+            let Some(scene) = scene.upgrade() else { return };
+            let mut state = scene.borrow_mut();
+            state.meshes[mesh_index].color = obj::fallback_color(Some(&texture_name));
+            state.texture_finished();
+        })
+    };
+
+    image.set_onload(Some(on_load.as_ref().unchecked_ref()));
+    image.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    image.set_src(&path);
+
+    Some(TextureRequest { image, _on_load: on_load, _on_error: on_error })
+}
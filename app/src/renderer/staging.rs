@@ -0,0 +1,45 @@
+// renderer/staging.rs
+// how a model is framed and lit, shared by the webgl viewer and the software rasterizer so both produce the same picture
+use crate::renderer::math::{self, Mat4, Vec3};
+use crate::renderer::obj::Bounds;
+
+pub const FIELD_OF_VIEW: f32 = 75.0;    // vertical, degrees
+pub const NEAR_PLANE: f32 = 0.1;
+pub const FAR_PLANE: f32 = 1000.0;
+
+pub const MODEL_SIZE: f32 = 10.0;       // largest model dimension after normalising
+pub const MODEL_Y_OFFSET: f32 = 1.5;
+
+pub const AMBIENT_LIGHT: f32 = 0.6;
+pub const LIGHTS: [(Vec3, f32); 2] = [  // directional lights as (position, intensity), shining towards the origin
+    ([5.0, 5.0, 5.0], 0.8),
+    ([-5.0, -2.0, -5.0], 0.3),
+];
+pub const SPECULAR_STRENGTH: f32 = 0.0667;  // matches three.js MeshPhongMaterial default specular of 0x111111
+pub const SHININESS: f32 = 30.0;
+pub const ALPHA_CUTOFF: f32 = 0.1;      // alpha test for sprite-like textures
+
+// (scale, position) that centers the model and scales it to MODEL_SIZE
+pub fn fit_model(bounds: &Bounds) -> (f32, Vec3) {
+    let size = bounds.size();
+    let max_dim = size[0].max(size[1]).max(size[2]);
+    if max_dim <= 0.0 {
+        return (1.0, [0.0; 3]);
+    }
+
+    let scale = MODEL_SIZE / max_dim;
+    let center = bounds.center();
+    (scale, [-center[0] * scale, -center[1] * scale + MODEL_Y_OFFSET, -center[2] * scale])
+}
+
+pub fn model_matrix((scale, position): (f32, Vec3), rotation: f32) -> Mat4 {
+    math::multiply(
+        &math::translation(position),
+        &math::multiply(&math::rotation_y(rotation), &math::scaling(scale)),
+    )
+}
+
+// unit vector pointing from the scene towards a light
+pub fn light_direction(position: Vec3) -> Vec3 {
+    math::normalize(position)
+}
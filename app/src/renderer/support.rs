@@ -0,0 +1,84 @@
+// renderer/support.rs
+// decides whether the live webgl viewer should run or the pre-rendered turntable should be shown instead
+use js_sys::Reflect;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{HtmlCanvasElement, Navigator, WebGl2RenderingContext, WebglLoseContext, Window};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FallbackReason {
+    NoWebGl2,
+    ReducedMotion,
+    LowPower,
+}
+
+impl FallbackReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            FallbackReason::NoWebGl2 => "WebGL2 is not available in this browser",
+            FallbackReason::ReducedMotion => "Reduced motion is enabled",
+            FallbackReason::LowPower => "Low-power device detected",
+        }
+    }
+
+    // the live viewer can still be opted into when the device is merely slow or motion is reduced
+    pub fn can_opt_in(&self) -> bool {
+        *self != FallbackReason::NoWebGl2
+    }
+}
+
+const MIN_CPU_CORES: f64 = 2.0;
+const MIN_DEVICE_MEMORY_GB: f64 = 2.0;
+
+// reason to skip the live viewer, checked in order of severity
+pub fn fallback_reason() -> Option<FallbackReason> {
+    let window = web_sys::window()?;
+
+    if !has_webgl2(&window) {
+        return Some(FallbackReason::NoWebGl2);
+    }
+    if prefers_reduced_motion(&window) {
+        return Some(FallbackReason::ReducedMotion);
+    }
+    if is_low_power(&window.navigator()) {
+        return Some(FallbackReason::LowPower);
+    }
+    None
+}
+
+fn has_webgl2(window: &Window) -> bool {
+    // probe with a throwaway canvas so the real one keeps a fresh context
+    let canvas = window
+        .document()
+        .and_then(|document| document.create_element("canvas").ok())
+        .and_then(|element| element.dyn_into::<HtmlCanvasElement>().ok());
+
+    let Some(context) = canvas.and_then(|canvas| canvas.get_context("webgl2").ok().flatten()) else {
+        return false;
+    };
+    // browsers only keep a handful of contexts alive, give this one back instead of waiting for gc
+    if let Ok(Some(extension)) = context.unchecked_into::<WebGl2RenderingContext>().get_extension("WEBGL_lose_context") {
+        extension.unchecked_into::<WebglLoseContext>().lose_context();
+    }
+    true
+}
+
+fn prefers_reduced_motion(window: &Window) -> bool {
+    matches!(window.match_media("(prefers-reduced-motion: reduce)"), Ok(Some(query)) if query.matches())
+}
+
+// few cores, little memory or data saver turned on
+fn is_low_power(navigator: &Navigator) -> bool {
+    let cores = navigator.hardware_concurrency();
+    // device memory and network info are chromium only, so read them loosely
+    let memory = Reflect::get(navigator, &"deviceMemory".into()).ok().and_then(|m| m.as_f64());
+    let save_data = Reflect::get(navigator, &"connection".into())
+        .ok()
+        .filter(|connection| connection.is_object())
+        .and_then(|connection| Reflect::get(&connection, &JsValue::from_str("saveData")).ok())
+        .and_then(|save_data| save_data.as_bool())
+        .unwrap_or(false);
+
+    (cores > 0.0 && cores < MIN_CPU_CORES)
+        || memory.is_some_and(|memory| memory < MIN_DEVICE_MEMORY_GB)
+        || save_data
+}
@@ -12,8 +12,14 @@ pub enum Route {
     //Avatar,
     #[at("/doom-projects")]
     DoomProjects,
+    #[at("/play/:map")]
+    Play { map: String },
     #[at("/contact")]
     Contact,
+    #[at("/admin")]     // contact inbox, not linked from the navbar
+    Admin,
+    #[at("/cv")]        // printable cv, the pdf download has the same content
+    Cv,
     #[not_found]
     #[at("/404")]
     NotFound
@@ -0,0 +1,324 @@
+// server/src/api.rs
+// routes: content lists and contact submissions under /api, the built site (when given) everywhere else
+use std::fs::File;
+use std::io::Read;
+use std::net::IpAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use app::contact::attachments::{self, ATTACHMENT_FIELD, MAX_TOTAL_SIZE};
+use app::contact::backend::SUBMISSION_FIELD;
+use app::contact::captcha::CHALLENGE_API;
+use app::contact::inbox::{Login, MessageUpdate, Session, LOGIN_API, LOGOUT_API, MESSAGES_API};
+use app::contact::spam::{self, FormToken, SpamRules, SPAM_CODE};
+use app::contact::validate::validate;
+use app::contact::multipart::{self, Part};
+use app::contact::Submission;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::auth::Sessions;
+use crate::challenges::Challenges;
+use crate::db::Db;
+use crate::form_tokens::FormTokens;
+use crate::rate_limit::RateLimiter;
+
+pub const CONTACT_API: &str = "/api/contact";
+// what backend::Server::form_token_url asks for when the form opens
+const FORM_TOKEN_API: &str = "/api/contact/form";
+const MAX_BODY: u64 = 64 * 1024;
+// a message with attachments: the files, plus room for the fields and the multipart framing
+const MAX_UPLOAD: u64 = MAX_TOTAL_SIZE + MAX_BODY;
+
+pub struct State {
+    pub db: Db,
+    pub limiter: Mutex<RateLimiter>,
+    pub sessions: Sessions,
+    pub login_limiter: Mutex<RateLimiter>,      // admin login attempts per address
+    pub challenges: Option<Challenges>,         // proof of work required, when set
+    pub forms: FormTokens,                      // when each open form was handed out, for the time to submit
+    pub spam: SpamRules,                        // the per-message rules, the limiter does the rate
+    pub allow_origin: String,
+    pub trust_forwarded: bool,      // behind a reverse proxy, the client is in X-Forwarded-For
+    pub site: Option<PathBuf>,
+}
+
+type Reply = Response<Box<dyn Read + Send>>;
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header names and values are ascii")
+}
+
+fn json_reply(status: u16, body: String) -> Reply {
+    Response::from_string(body).with_status_code(status).with_header(header("Content-Type", "application/json")).boxed()
+}
+
+fn error_reply(status: u16, message: &str) -> Reply {
+    json_reply(status, json!({ "error": message }).to_string())
+}
+
+pub fn handle(state: &State, mut request: Request) {
+    let path = request.url().split(['?', '#']).next().unwrap_or_default().to_string();
+    let method = request.method().clone();
+
+    let reply = if path.starts_with("/api/") {
+        let reply = match (&method, path.as_str()) {
+            (Method::Options, _) => Response::empty(StatusCode(204)).boxed(),
+            (Method::Post, CONTACT_API) => contact(state, &mut request),
+            (Method::Get, CHALLENGE_API) => challenge(state),
+            (Method::Get, FORM_TOKEN_API) => form_token(state),
+            (_, admin) if admin.starts_with("/api/admin/") => self::admin(state, &mut request, &method, admin),
+            (Method::Get, kind) => match state.db.content(kind) {
+                Ok(Some(items)) => json_reply(200, items),
+                Ok(None) => error_reply(404, "not found"),
+                Err(err) => {
+                    tracing::error!("Reading {} failed: {}", kind, err);
+                    error_reply(500, "couldn't read the database")
+                }
+            },
+            _ => error_reply(405, "method not allowed"),
+        };
+        reply
+            .with_header(header("Access-Control-Allow-Origin", &state.allow_origin))
+            .with_header(header("Access-Control-Allow-Methods", "GET, POST, PATCH, OPTIONS"))
+            .with_header(header("Access-Control-Allow-Headers", "Content-Type, Authorization"))
+    } else {
+        match (&method, &state.site) {
+            (Method::Get | Method::Head, Some(site)) => static_file(site, &path),
+            _ => error_reply(404, "not found"),
+        }
+    };
+
+    let status = reply.status_code().0;
+    tracing::info!("{} {} -> {}", method, path, status);
+    if let Err(err) = request.respond(reply) {
+        tracing::warn!("Failed to answer {}: {}", path, err);
+    }
+}
+
+fn client_address(state: &State, request: &Request) -> Option<IpAddr> {
+    if state.trust_forwarded {
+        let forwarded = request.headers().iter().find(|header| header.field.equiv("X-Forwarded-For"));
+        if let Some(address) = forwarded.and_then(|header| header.value.as_str().split(',').next()?.trim().parse().ok()) {
+            return Some(address);
+        }
+    }
+    request.remote_addr().map(|address| address.ip())
+}
+
+// the request's json body, or the reply refusing it
+fn read_json<T: DeserializeOwned>(request: &mut Request, what: &str) -> Result<T, Reply> {
+    let mut body = String::new();
+    if request.as_reader().take(MAX_BODY + 1).read_to_string(&mut body).is_err() {
+        return Err(error_reply(400, "body isn't utf-8 text"));
+    }
+    if body.len() as u64 > MAX_BODY {
+        return Err(error_reply(413, "body is too large"));
+    }
+    serde_json::from_str(&body).map_err(|err| error_reply(400, &format!("not {}: {}", what, err)))
+}
+
+// the submission and its files: a json body without any, the multipart one backend::Server sends with them
+fn read_submission(request: &mut Request) -> Result<(Submission, Vec<Part>), Reply> {
+    let content_type = request.headers().iter().find(|header| header.field.equiv("Content-Type")).map(|header| header.value.to_string());
+    let Some(boundary) = content_type.as_deref().and_then(multipart::boundary) else {
+        return read_json(request, "a contact submission").map(|submission| (submission, Vec::new()));
+    };
+    let mut body = Vec::new();
+    if request.as_reader().take(MAX_UPLOAD + 1).read_to_end(&mut body).is_err() {
+        return Err(error_reply(400, "couldn't read the body"));
+    }
+    if body.len() as u64 > MAX_UPLOAD {
+        return Err(error_reply(413, "body is too large"));
+    }
+    let parts = multipart::parse(&body, &boundary).map_err(|err| error_reply(400, &format!("not a multipart body: {}", err)))?;
+    let (files, fields): (Vec<Part>, Vec<Part>) = parts.into_iter().partition(|part| part.name == ATTACHMENT_FIELD);
+    let field = fields.iter().find(|part| part.name == SUBMISSION_FIELD).ok_or_else(|| error_reply(400, "no submission field"))?;
+    let submission = serde_json::from_slice(&field.data).map_err(|err| error_reply(400, &format!("not a contact submission: {}", err)))?;
+    Ok((submission, files))
+}
+
+fn contact(state: &State, request: &mut Request) -> Reply {
+    let Some(address) = client_address(state, request) else {
+        return error_reply(400, "unknown client");
+    };
+    let (submission, files) = match read_submission(request) {
+        Ok(read) => read,
+        Err(reply) => return reply,
+    };
+    // the same rules the form checks as the visitor types, anyone can post here without it
+    if let Err(err) = validate(&submission) {
+        return error_reply(422, &err.to_string());
+    }
+    let sizes: Vec<(String, u64)> = files.iter().map(|file| (file.filename.clone().unwrap_or_default(), file.data.len() as u64)).collect();
+    if let Err(err) = attachments::check_all(&sizes) {
+        return error_reply(422, &err.to_string());
+    }
+    // a bot shouldn't learn which rule caught it
+    if let Err(reason) = state.forms.check(&state.spam, &submission, Instant::now()) {
+        tracing::info!("Dropped a message from {} as spam: {}", address, reason);
+        return json_reply(403, json!({ "error": spam::REJECTION, "code": SPAM_CODE }).to_string());
+    }
+    if let Some(challenges) = &state.challenges {
+        if let Err(err) = challenges.redeem(submission.proof_of_work.as_ref(), Instant::now()) {
+            return error_reply(403, &err);
+        }
+    }
+
+    // only valid submissions count against the limit, typos shouldn't lock anyone out
+    let limited = state.limiter.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).check(address, Instant::now());
+    if let Err(retry) = limited {
+        let minutes = retry.as_secs().div_ceil(60).max(1);
+        return error_reply(429, &format!("too many messages, try again in {} minutes", minutes))
+            .with_header(header("Retry-After", &retry.as_secs().max(1).to_string()));
+    }
+
+    let received = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+    match state.db.insert_message(&submission, &files, received, &address.to_string()) {
+        Ok(id) => {
+            state.forms.spend(submission.form_token.as_deref());
+            tracing::info!("Message {} from {} <{}> with {} files", id, submission.name.trim(), submission.email.trim(), files.len());
+            json_reply(201, json!({ "ok": true, "id": id }).to_string())
+        }
+        Err(err) => {
+            tracing::error!("Storing a message failed: {}", err);
+            error_reply(500, "couldn't store the message")
+        }
+    }
+}
+
+fn challenge(state: &State) -> Reply {
+    let Some(challenges) = &state.challenges else {
+        return error_reply(404, "the proof of work captcha is disabled, start the server with --proof-of-work");
+    };
+    match challenges.issue(Instant::now()) {
+        Ok(challenge) => json_reply(200, json!(challenge).to_string()).with_header(header("Cache-Control", "no-store")),
+        Err(err) => {
+            tracing::warn!("No captcha challenge: {}", err);
+            error_reply(503, "try again in a few minutes")
+        }
+    }
+}
+
+fn form_token(state: &State) -> Reply {
+    match state.forms.issue(Instant::now()) {
+        Ok(token) => json_reply(200, json!(FormToken { token }).to_string()).with_header(header("Cache-Control", "no-store")),
+        Err(err) => {
+            tracing::warn!("No form token: {}", err);
+            error_reply(503, "try again in a few minutes")
+        }
+    }
+}
+
+fn bearer_token(request: &Request) -> Option<String> {
+    let authorization = request.headers().iter().find(|header| header.field.equiv("Authorization"))?;
+    authorization.value.as_str().strip_prefix("Bearer ").map(|token| token.trim().to_string())
+}
+
+fn admin(state: &State, request: &mut Request, method: &Method, path: &str) -> Reply {
+    if !state.sessions.enabled() {
+        return error_reply(404, "the admin api is disabled, set ADMIN_PASSWORD to enable it");
+    }
+    let now = Instant::now();
+
+    if (method, path) == (&Method::Post, LOGIN_API) {
+        let Some(address) = client_address(state, request) else {
+            return error_reply(400, "unknown client");
+        };
+        let login: Login = match read_json(request, "a login") {
+            Ok(login) => login,
+            Err(reply) => return reply,
+        };
+        // every attempt counts, the owner logs in rarely enough to never notice
+        let limited = state.login_limiter.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).check(address, now);
+        if limited.is_err() {
+            return error_reply(429, "too many attempts, try again later");
+        }
+        return match state.sessions.login(&login.password, now) {
+            Ok(Some(token)) => json_reply(200, json!(Session { token }).to_string()),
+            Ok(None) => {
+                tracing::warn!("Failed admin login from {}", address);
+                error_reply(401, "wrong password")
+            }
+            Err(err) => {
+                tracing::error!("Admin login failed: {}", err);
+                error_reply(500, "couldn't start a session")
+            }
+        };
+    }
+
+    let Some(token) = bearer_token(request).filter(|token| state.sessions.check(token, now)) else {
+        return error_reply(401, "not logged in");
+    };
+    let message_id = path.strip_prefix(MESSAGES_API).and_then(|rest| rest.strip_prefix('/')).and_then(|id| id.parse::<i64>().ok());
+    match (method, path, message_id) {
+        (Method::Post, LOGOUT_API, _) => {
+            state.sessions.logout(&token);
+            Response::empty(StatusCode(204)).boxed()
+        }
+        (Method::Get, MESSAGES_API, _) => match state.db.messages() {
+            Ok(messages) => json_reply(200, json!(messages).to_string()),
+            Err(err) => {
+                tracing::error!("Reading messages failed: {}", err);
+                error_reply(500, "couldn't read the database")
+            }
+        },
+        (Method::Patch, _, Some(id)) => {
+            let update: MessageUpdate = match read_json(request, "a message update") {
+                Ok(update) => update,
+                Err(reply) => return reply,
+            };
+            match state.db.update_message(id, &update) {
+                Ok(true) => json_reply(200, json!({ "ok": true }).to_string()),
+                Ok(false) => error_reply(404, "no such message"),
+                Err(err) => {
+                    tracing::error!("Updating message {} failed: {}", id, err);
+                    error_reply(500, "couldn't update the message")
+                }
+            }
+        }
+        _ => error_reply(404, "not found"),
+    }
+}
+
+// files of the built site (trunk's dist/), index.html for the root
+fn static_file(site: &Path, path: &str) -> Reply {
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative.components().any(|component| !matches!(component, Component::Normal(_))) {
+        return error_reply(404, "not found");
+    }
+    let mut file_path = site.join(relative);
+    if file_path.is_dir() {
+        file_path.push("index.html");
+    } else if !file_path.exists() && relative.extension().is_none() {
+        // a route of the app itself (/cv, /play/jammy), which the app's router takes from here
+        file_path = site.join("index.html");
+    }
+    match File::open(&file_path) {
+        Ok(file) => Response::from_file(file).with_header(header("Content-Type", mime(&file_path))).boxed(),
+        Err(_) => error_reply(404, "not found"),
+    }
+}
+
+fn mime(path: &Path) -> &'static str {
+    match path.extension().and_then(|extension| extension.to_str()).unwrap_or_default() {
+        "html" => "text/html; charset=utf-8",
+        "js" => "text/javascript",
+        "wasm" => "application/wasm",
+        "css" => "text/css",
+        "json" | "webmanifest" => "application/json",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,83 @@
+// server/src/auth.rs
+// admin sessions: one password from the environment, exchanged for random bearer tokens that expire
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const SESSION_LENGTH: Duration = Duration::from_secs(12 * 60 * 60);
+
+pub struct Sessions {
+    password: Option<String>,       // no password, no admin api
+    tokens: Mutex<HashMap<String, Instant>>,
+}
+
+impl Sessions {
+    pub fn new(password: Option<String>) -> Sessions {
+        Sessions { password: password.filter(|password| !password.is_empty()), tokens: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.password.is_some()
+    }
+
+    fn tokens(&self) -> std::sync::MutexGuard<'_, HashMap<String, Instant>> {
+        self.tokens.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn login(&self, password: &str, now: Instant) -> Result<Option<String>, String> {
+        let Some(expected) = &self.password else { return Ok(None) };
+        if !constant_time_eq(password.as_bytes(), expected.as_bytes()) {
+            return Ok(None);
+        }
+        let token = random_token()?;
+        let mut tokens = self.tokens();
+        tokens.retain(|_, &mut started| now.duration_since(started) < SESSION_LENGTH);
+        tokens.insert(token.clone(), now);
+        Ok(Some(token))
+    }
+
+    pub fn logout(&self, token: &str) {
+        self.tokens().remove(token);
+    }
+
+    pub fn check(&self, token: &str, now: Instant) -> bool {
+        self.tokens().get(token).is_some_and(|&started| now.duration_since(started) < SESSION_LENGTH)
+    }
+}
+
+// doesn't stop at the first differing byte, so response times don't give the password away
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |difference, (x, y)| difference | (x ^ y)) == 0
+}
+
+// 32 bytes of the kernel's randomness as hex
+pub fn random_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .map_err(|e| format!("no randomness for a session token: {}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hands_out_expiring_tokens_for_the_password() {
+        let sessions = Sessions::new(Some("iddqd".to_string()));
+        let now = Instant::now();
+        assert_eq!(sessions.login("idkfa", now), Ok(None));
+
+        let token = sessions.login("iddqd", now).unwrap().unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(sessions.check(&token, now + Duration::from_secs(60)));
+        assert!(!sessions.check(&token, now + SESSION_LENGTH));
+        assert!(!sessions.check("guess", now));
+
+        sessions.logout(&token);
+        assert!(!sessions.check(&token, now));
+        assert!(!Sessions::new(Some(String::new())).enabled());
+    }
+}
@@ -0,0 +1,81 @@
+// server/src/challenges.rs
+// proof of work captcha: random salts handed out once, each good for one message within a few minutes
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use app::contact::captcha::{self, Challenge, Proof};
+
+use crate::auth::random_token;
+
+const LIFETIME: Duration = Duration::from_secs(10 * 60);
+// unanswered challenges cost memory, past this many nobody gets a new one until some expire
+const MAX_OUTSTANDING: usize = 10_000;
+
+pub struct Challenges {
+    difficulty: u32,
+    issued: Mutex<HashMap<String, Instant>>,
+}
+
+impl Challenges {
+    pub fn new(difficulty: u32) -> Challenges {
+        Challenges { difficulty, issued: Mutex::new(HashMap::new()) }
+    }
+
+    fn issued(&self) -> std::sync::MutexGuard<'_, HashMap<String, Instant>> {
+        self.issued.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn issue(&self, now: Instant) -> Result<Challenge, String> {
+        let mut issued = self.issued();
+        issued.retain(|_, &mut at| now.duration_since(at) < LIFETIME);
+        if issued.len() >= MAX_OUTSTANDING {
+            return Err("too many open challenges".to_string());
+        }
+        let salt = random_token()?;
+        issued.insert(salt.clone(), now);
+        Ok(Challenge { salt, difficulty: self.difficulty })
+    }
+
+    // a salt is gone after one try, right or wrong, so answers can't be replayed
+    pub fn redeem(&self, proof: Option<&Proof>, now: Instant) -> Result<(), String> {
+        let proof = proof.ok_or("the captcha wasn't solved")?;
+        let issued = self.issued().remove(&proof.salt);
+        match issued {
+            Some(at) if now.duration_since(at) < LIFETIME => {}
+            Some(_) => return Err("the captcha expired, please try again".to_string()),
+            None => return Err("unknown captcha challenge".to_string()),
+        }
+        if !captcha::check(proof, self.difficulty) {
+            return Err("the captcha answer is wrong".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redeems_each_solved_challenge_once() {
+        let challenges = Challenges::new(8);
+        let now = Instant::now();
+        let challenge = challenges.issue(now).unwrap();
+        assert_eq!(challenge.difficulty, 8);
+        assert!(challenges.redeem(None, now).is_err());
+
+        let proof = captcha::solve(&challenge, |_| {});
+        assert_eq!(challenges.redeem(Some(&proof), now + Duration::from_secs(5)), Ok(()));
+        assert!(challenges.redeem(Some(&proof), now).is_err(), "replayed");
+
+        let late = captcha::solve(&challenges.issue(now).unwrap(), |_| {});
+        assert!(challenges.redeem(Some(&late), now + LIFETIME).is_err());
+
+        // a wrong nonce still uses the salt up
+        let challenge = challenges.issue(now).unwrap();
+        let wrong = (0..).map(|nonce| Proof { salt: challenge.salt.clone(), nonce }).find(|proof| !captcha::check(proof, 8)).unwrap();
+        assert!(challenges.redeem(Some(&wrong), now).is_err());
+        assert!(challenges.issued().is_empty());
+    }
+}
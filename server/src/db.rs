@@ -0,0 +1,220 @@
+// server/src/db.rs
+// sqlite storage: received contact messages, and the page content as json rows in display order
+use std::path::Path;
+use std::sync::Mutex;
+
+use app::contact::inbox::{InboxMessage, MessageUpdate};
+use app::contact::multipart::Part;
+use app::contact::Submission;
+use app::content::{self, DOOM_PROJECTS_API, EDUCATION_API, EXPERIENCE_API, PROJECTS_API};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+// applied in order, PRAGMA user_version remembers how many already ran
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE messages (
+        id INTEGER PRIMARY KEY,
+        received INTEGER NOT NULL,      -- unix seconds
+        name TEXT NOT NULL,
+        email TEXT NOT NULL,
+        subject TEXT NOT NULL,
+        message TEXT NOT NULL,
+        address TEXT NOT NULL           -- client ip, for tracing abuse
+    );
+    CREATE TABLE content (
+        kind TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        data TEXT NOT NULL,             -- one json item of the kind's list
+        PRIMARY KEY (kind, position)
+    );",
+    // admin inbox flags
+    "ALTER TABLE messages ADD COLUMN read INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE messages ADD COLUMN spam INTEGER NOT NULL DEFAULT 0;",
+    // files sent along with a message, as the visitor named them
+    "CREATE TABLE attachments (
+        message_id INTEGER NOT NULL REFERENCES messages (id),
+        filename TEXT NOT NULL,
+        content_type TEXT NOT NULL,
+        data BLOB NOT NULL
+    );",
+];
+
+// api path -> the app's built-in list, which seeds an empty database
+pub fn builtin_content() -> Vec<(&'static str, Vec<serde_json::Value>)> {
+    fn values<T: Serialize>(items: Vec<T>) -> Vec<serde_json::Value> {
+        items.iter().filter_map(|item| serde_json::to_value(item).ok()).collect()
+    }
+    vec![
+        (PROJECTS_API, values(content::projects::projects())),
+        (DOOM_PROJECTS_API, values(content::doom_projects::doom_projects())),
+        (EXPERIENCE_API, values(content::about::experience())),
+        (EDUCATION_API, values(content::about::education())),
+    ]
+}
+
+pub struct Db(Mutex<Connection>);
+
+impl Db {
+    pub fn open(path: &Path) -> Result<Db, String> {
+        let connection = Connection::open(path).map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+        Db::migrate(connection)
+    }
+
+    #[cfg(test)]
+    pub fn memory() -> Db {
+        Db::migrate(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn migrate(connection: Connection) -> Result<Db, String> {
+        let version: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0)).map_err(|e| e.to_string())?;
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            connection.execute_batch(migration).map_err(|e| format!("migration {} failed: {}", index + 1, e))?;
+            connection.pragma_update(None, "user_version", index + 1).map_err(|e| e.to_string())?;
+        }
+        Ok(Db(Mutex::new(connection)))
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // a panic mid-query leaves nothing half written that sqlite wouldn't have rolled back
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // fills in kinds that have no rows yet, or replaces everything with the built-in content when forced
+    pub fn seed(&self, force: bool) -> Result<(), String> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(|e| e.to_string())?;
+        for (kind, items) in builtin_content() {
+            let existing: usize = transaction
+                .query_row("SELECT COUNT(*) FROM content WHERE kind = ?1", [kind], |row| row.get(0))
+                .map_err(|e| e.to_string())?;
+            if existing > 0 && !force {
+                continue;
+            }
+            transaction.execute("DELETE FROM content WHERE kind = ?1", [kind]).map_err(|e| e.to_string())?;
+            for (position, item) in items.iter().enumerate() {
+                transaction
+                    .execute("INSERT INTO content (kind, position, data) VALUES (?1, ?2, ?3)", params![kind, position, item.to_string()])
+                    .map_err(|e| e.to_string())?;
+            }
+            tracing::info!("Seeded {} with {} items", kind, items.len());
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    // the kind's items as a json array, none for kinds the database doesn't have
+    pub fn content(&self, kind: &str) -> Result<Option<String>, String> {
+        let connection = self.connection();
+        let mut statement = connection.prepare_cached("SELECT data FROM content WHERE kind = ?1 ORDER BY position").map_err(|e| e.to_string())?;
+        let items = statement
+            .query_map([kind], |row| row.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string())?;
+        Ok((!items.is_empty()).then(|| format!("[{}]", items.join(","))))
+    }
+
+    // the message and its files together, or neither
+    pub fn insert_message(&self, submission: &Submission, files: &[Part], received: u64, address: &str) -> Result<i64, String> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction().map_err(|e| e.to_string())?;
+        transaction
+            .execute(
+                "INSERT INTO messages (received, name, email, subject, message, address) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![received as i64, submission.name.trim(), submission.email.trim(), submission.subject.trim(), submission.message.trim(), address],
+            )
+            .map_err(|e| e.to_string())?;
+        let id = transaction.last_insert_rowid();
+        for file in files {
+            transaction
+                .execute(
+                    "INSERT INTO attachments (message_id, filename, content_type, data) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        id,
+                        file.filename.as_deref().unwrap_or_default(),
+                        file.content_type.as_deref().unwrap_or("application/octet-stream"),
+                        file.data
+                    ],
+                )
+                .map_err(|e| e.to_string())?;
+        }
+        transaction.commit().map_err(|e| e.to_string())?;
+        Ok(id)
+    }
+
+    // newest first, all of them: a personal site's inbox fits in one response
+    pub fn messages(&self) -> Result<Vec<InboxMessage>, String> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare_cached("SELECT id, received, name, email, subject, message, read, spam FROM messages ORDER BY received DESC, id DESC")
+            .map_err(|e| e.to_string())?;
+        let messages = statement
+            .query_map([], |row| {
+                Ok(InboxMessage {
+                    id: row.get(0)?,
+                    received: row.get::<_, i64>(1)?.max(0) as u64,
+                    name: row.get(2)?,
+                    email: row.get(3)?,
+                    subject: row.get(4)?,
+                    message: row.get(5)?,
+                    read: row.get(6)?,
+                    spam: row.get(7)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| e.to_string());
+        messages
+    }
+
+    // false when there's no such message
+    pub fn update_message(&self, id: i64, update: &MessageUpdate) -> Result<bool, String> {
+        let connection = self.connection();
+        let changed = connection
+            .execute(
+                "UPDATE messages SET read = COALESCE(?2, read), spam = COALESCE(?3, spam) WHERE id = ?1",
+                params![id, update.read, update.spam],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(changed > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_content_once_and_keeps_edits() {
+        let db = Db::memory();
+        db.seed(false).unwrap();
+        let projects: Vec<content::Project> = serde_json::from_str(&db.content(PROJECTS_API).unwrap().unwrap()).unwrap();
+        assert_eq!(projects, content::projects::projects());
+        assert_eq!(db.content("/api/nothing").unwrap(), None);
+
+        db.connection().execute("DELETE FROM content WHERE kind = ?1 AND position > 0", [EXPERIENCE_API]).unwrap();
+        let experience = || serde_json::from_str::<Vec<content::ExperienceItem>>(&db.content(EXPERIENCE_API).unwrap().unwrap()).unwrap();
+        db.seed(false).unwrap();
+        assert_eq!(experience().len(), 1);
+        db.seed(true).unwrap();
+        assert_eq!(experience(), content::about::experience());
+    }
+
+    #[test]
+    fn stores_messages() {
+        let db = Db::memory();
+        let submission = Submission { name: " Guy ".to_string(), email: "guy@example.com".to_string(), message: "hi".to_string(), ..Default::default() };
+        assert_eq!(db.insert_message(&submission, &[], 1, "127.0.0.1"), Ok(1));
+        let file = Part { name: "attachment".to_string(), filename: Some("MAP07.wad".to_string()), content_type: None, data: b"PWAD".to_vec() };
+        assert_eq!(db.insert_message(&submission, &[file], 2, "127.0.0.1"), Ok(2));
+        let stored: (i64, String, String, Vec<u8>) = db
+            .connection()
+            .query_row("SELECT message_id, filename, content_type, data FROM attachments", [], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap();
+        assert_eq!(stored, (2, "MAP07.wad".to_string(), "application/octet-stream".to_string(), b"PWAD".to_vec()));
+        assert_eq!(db.update_message(1, &MessageUpdate { spam: Some(true), ..Default::default() }), Ok(true));
+        assert_eq!(db.update_message(2, &MessageUpdate { read: Some(true), ..Default::default() }), Ok(true));
+        assert_eq!(db.update_message(3, &MessageUpdate { read: Some(true), ..Default::default() }), Ok(false));
+
+        let messages = db.messages().unwrap();
+        assert_eq!(messages.iter().map(|message| (message.id, message.read, message.spam)).collect::<Vec<_>>(), [(2, true, false), (1, false, true)]);
+        assert_eq!(messages[1].name, "Guy");
+    }
+}
@@ -0,0 +1,90 @@
+// server/src/form_tokens.rs
+// the time to submit, measured here: the form gets a token when it opens and a message is only taken once its
+// token is old enough. form_seconds in the submission is the client's word, so nothing here reads it
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use app::contact::spam::{SpamReason, SpamRules};
+use app::contact::Submission;
+
+use crate::auth::random_token;
+
+// long enough for a form left open overnight, or a message waiting in the outbox for the connection
+const LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+// every page view takes one, past this many nobody gets a new one until some expire
+const MAX_OUTSTANDING: usize = 100_000;
+
+#[derive(Default)]
+pub struct FormTokens {
+    issued: Mutex<HashMap<String, Instant>>,
+}
+
+impl FormTokens {
+    fn issued(&self) -> std::sync::MutexGuard<'_, HashMap<String, Instant>> {
+        self.issued.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn issue(&self, now: Instant) -> Result<String, String> {
+        let mut issued = self.issued();
+        issued.retain(|_, &mut at| now.duration_since(at) < LIFETIME);
+        if issued.len() >= MAX_OUTSTANDING {
+            return Err("too many open forms".to_string());
+        }
+        let token = random_token()?;
+        issued.insert(token.clone(), now);
+        Ok(token)
+    }
+
+    // SpamRules::check plus the timing, from when the token was issued
+    pub fn check(&self, rules: &SpamRules, submission: &Submission, now: Instant) -> Result<(), SpamReason> {
+        rules.check(submission)?;
+        let issued = submission.form_token.as_ref().and_then(|token| self.issued().get(token).copied());
+        let issued = issued.filter(|&at| now.duration_since(at) < LIFETIME).ok_or(SpamReason::NoFormToken)?;
+        rules.check_time(Some(now.duration_since(issued).as_secs()))
+    }
+
+    // once its message is stored; a send that failed keeps it so the outbox can try again
+    pub fn spend(&self, token: Option<&str>) {
+        if let Some(token) = token {
+            self.issued().remove(token);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(form_token: Option<String>) -> Submission {
+        Submission {
+            name: "Doom Guy".to_string(),
+            email: "guy@example.com".to_string(),
+            message: "hi".to_string(),
+            form_seconds: Some(9999),
+            form_token,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn times_the_form_itself() {
+        let (tokens, rules, now) = (FormTokens::default(), SpamRules::default(), Instant::now());
+        let token = tokens.issue(now).unwrap();
+
+        // whatever the client says it took
+        assert_eq!(tokens.check(&rules, &submission(Some(token.clone())), now), Err(SpamReason::TooFast { seconds: 0 }));
+        assert_eq!(tokens.check(&rules, &submission(None), now + Duration::from_secs(60)), Err(SpamReason::NoFormToken));
+        assert_eq!(tokens.check(&rules, &submission(Some("made-up".to_string())), now), Err(SpamReason::NoFormToken));
+
+        let later = now + Duration::from_secs(rules.min_seconds);
+        assert_eq!(tokens.check(&rules, &submission(Some(token.clone())), later), Ok(()));
+        // still good for a retry until its message is stored
+        assert_eq!(tokens.check(&rules, &submission(Some(token.clone())), later), Ok(()));
+        tokens.spend(Some(&token));
+        assert_eq!(tokens.check(&rules, &submission(Some(token)), later), Err(SpamReason::NoFormToken));
+
+        let stale = tokens.issue(now).unwrap();
+        assert_eq!(tokens.check(&rules, &submission(Some(stale)), now + LIFETIME), Err(SpamReason::NoFormToken));
+    }
+}
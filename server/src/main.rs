@@ -0,0 +1,157 @@
+// server/src/main.rs
+// self-hosted backend for the site: stores contact messages and serves the page content from sqlite
+//
+//   cargo run -p server
+//   cargo run -p server -- --site app/dist --db site.db
+//
+// trunk serve proxies /api here; point the contact form at it with static/contact/backend.local.json:
+//   { "backend": { "type": "server", "url": "/api/contact" } }
+mod api;
+mod auth;
+mod challenges;
+mod db;
+mod form_tokens;
+mod rate_limit;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use app::contact::spam::SpamRules;
+
+use api::State;
+use auth::Sessions;
+use challenges::Challenges;
+use db::Db;
+use form_tokens::FormTokens;
+use rate_limit::RateLimiter;
+
+const USAGE: &str = "\
+usage:
+  server [--port <port>] [--db <file>] [--site <dir>] [--rate-limit <messages>] [--rate-window <seconds>]
+         [--min-seconds <seconds>] [--max-links <links>] [--allow-origin <origin>] [--trust-forwarded]
+         [--proof-of-work <bits>] [--reseed]
+
+serves GET /api/projects, /api/doom-projects, /api/experience and /api/education from the database
+(created and filled with the app's built-in content on first run, --reseed overwrites edits with it)
+and stores valid POST /api/contact submissions (json, or multipart with files the form's attachment rules
+allow, kept in the database's attachments table), at most --rate-limit (5) per client address every
+--rate-window (600) seconds. messages sent less than --min-seconds (3) after the form took its token
+from GET /api/contact/form (timed here, whatever the form says), without one, with more than
+--max-links (3) links or with the hidden honeypot field filled in are dropped as spam. --site also serves a trunk build from that directory. --trust-forwarded
+takes the client address from X-Forwarded-For, only use it behind a reverse proxy.
+--proof-of-work hands out captcha challenges at GET /api/contact/challenge and only accepts messages
+that solved one with that many leading zero bits (20 takes a second or so); pair it with
+  \"captcha\": { \"type\": \"proof_of_work\", \"challenge_url\": \"/api/contact/challenge\" }
+in the contact config.
+
+the admin inbox (/#/admin on the site, /api/admin/* here) is enabled by setting ADMIN_PASSWORD";
+
+struct Options {
+    port: u16,
+    db: PathBuf,
+    site: Option<PathBuf>,
+    rate_limit: usize,
+    rate_window: u64,
+    spam: SpamRules,
+    allow_origin: String,
+    trust_forwarded: bool,
+    proof_of_work: Option<u32>,
+    reseed: bool,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    tracing_subscriber::fmt().with_target(false).init();
+
+    match parse_options(&args).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        port: 8080,
+        db: PathBuf::from("site.db"),
+        site: None,
+        rate_limit: 5,
+        rate_window: 600,
+        spam: SpamRules::default(),
+        allow_origin: "*".to_string(),
+        trust_forwarded: false,
+        proof_of_work: None,
+        reseed: false,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().cloned().ok_or_else(|| format!("{} needs a value\n\n{}", arg, USAGE));
+        let number = |value: String| value.parse::<u64>().map_err(|e| format!("bad {}: {}", arg, e));
+        match arg.as_str() {
+            "--port" => options.port = value()?.parse().map_err(|e| format!("bad {}: {}", arg, e))?,
+            "--db" => options.db = PathBuf::from(value()?),
+            "--site" => options.site = Some(PathBuf::from(value()?)),
+            "--rate-limit" => options.rate_limit = number(value()?)? as usize,
+            "--rate-window" => options.rate_window = number(value()?)?,
+            "--min-seconds" => options.spam.min_seconds = number(value()?)?,
+            "--max-links" => options.spam.max_links = number(value()?)? as usize,
+            "--allow-origin" => options.allow_origin = value()?,
+            "--trust-forwarded" => options.trust_forwarded = true,
+            "--proof-of-work" => options.proof_of_work = Some(number(value()?)?.min(64) as u32),
+            "--reseed" => options.reseed = true,
+            _ => return Err(format!("unknown argument {}\n\n{}", arg, USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let db = Db::open(&options.db)?;
+    db.seed(options.reseed)?;
+    if let Some(site) = options.site.as_ref().filter(|site| !site.is_dir()) {
+        return Err(format!("{} is not a directory (run trunk build first)", site.display()));
+    }
+
+    let state = Arc::new(State {
+        db,
+        limiter: Mutex::new(RateLimiter::new(options.rate_limit, Duration::from_secs(options.rate_window))),
+        sessions: Sessions::new(std::env::var("ADMIN_PASSWORD").ok()),
+        login_limiter: Mutex::new(RateLimiter::new(10, Duration::from_secs(15 * 60))),
+        challenges: options.proof_of_work.map(Challenges::new),
+        forms: FormTokens::default(),
+        spam: SpamRules { max_messages: options.rate_limit, window_seconds: options.rate_window, ..options.spam },
+        allow_origin: options.allow_origin,
+        trust_forwarded: options.trust_forwarded,
+        site: options.site,
+    });
+
+    let server = Arc::new(tiny_http::Server::http(("127.0.0.1", options.port)).map_err(|e| format!("failed to listen on {}: {}", options.port, e))?);
+    tracing::info!("Listening on http://localhost:{} with {}", options.port, options.db.display());
+    if !state.sessions.enabled() {
+        tracing::info!("ADMIN_PASSWORD isn't set, the admin api is disabled");
+    }
+
+    // sqlite serialises on the one connection anyway, a few workers just keep slow clients from blocking the rest
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let (server, state) = (server.clone(), state.clone());
+            std::thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    api::handle(&state, request);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+    Ok(())
+}
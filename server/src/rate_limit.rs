@@ -0,0 +1,58 @@
+// server/src/rate_limit.rs
+// sliding window of recent submissions per client address
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+pub struct RateLimiter {
+    limit: usize,
+    window: Duration,
+    hits: HashMap<IpAddr, VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: usize, window: Duration) -> RateLimiter {
+        RateLimiter { limit, window, hits: HashMap::new() }
+    }
+
+    // counts the attempt if it's allowed, otherwise says how long until the oldest one expires
+    pub fn check(&mut self, address: IpAddr, now: Instant) -> Result<(), Duration> {
+        let window = self.window;
+        let hits = self.hits.entry(address).or_default();
+        while hits.front().is_some_and(|&hit| now.duration_since(hit) >= window) {
+            hits.pop_front();
+        }
+        if hits.len() >= self.limit {
+            let oldest = hits.front().copied().unwrap_or(now);
+            return Err(window.saturating_sub(now.duration_since(oldest)));
+        }
+        hits.push_back(now);
+
+        // forget quiet addresses now and then so the map doesn't grow forever
+        if self.hits.len() > 10_000 {
+            self.hits.retain(|_, hits| hits.back().is_some_and(|&hit| now.duration_since(hit) < window));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_a_burst_then_waits_for_the_window() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let (client, other) = ("10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap());
+        let start = Instant::now();
+
+        assert_eq!(limiter.check(client, start), Ok(()));
+        assert_eq!(limiter.check(client, start + Duration::from_secs(10)), Ok(()));
+        assert_eq!(limiter.check(client, start + Duration::from_secs(20)), Err(Duration::from_secs(40)));
+        assert_eq!(limiter.check(other, start + Duration::from_secs(20)), Ok(()));
+
+        // the first hit has aged out, the refused one never counted
+        assert_eq!(limiter.check(client, start + Duration::from_secs(60)), Ok(()));
+        assert!(limiter.check(client, start + Duration::from_secs(61)).is_err());
+    }
+}